@@ -4,16 +4,19 @@
 ///
 /// Rules:
 /// - Returns `0` before `cliff_time`.
-/// - Returns `0` for invalid schedules (`start_time >= end_time`) or negative rates.
+/// - Returns `0` for invalid schedules (`start_time >= end_time`), negative rates or a
+///   zero `rate_denominator`.
 /// - Uses `min(current_time, end_time)` so accrual is capped at stream end.
-/// - Multiplies elapsed seconds by `rate_per_second`, and on multiplication overflow
-///   returns `deposit_amount` (safe upper bound before final clamping).
+/// - Multiplies elapsed seconds by `rate_per_second` and divides by `rate_denominator`
+///   (rounding down), and on multiplication overflow returns `deposit_amount` (safe
+///   upper bound before final clamping).
 /// - Final result is clamped to `[0, deposit_amount]`.
 pub fn calculate_accrued_amount(
     start_time: u64,
     cliff_time: u64,
     end_time: u64,
     rate_per_second: i128,
+    rate_denominator: u64,
     deposit_amount: i128,
     current_time: u64,
 ) -> i128 {
@@ -21,7 +24,7 @@ pub fn calculate_accrued_amount(
         return 0;
     }
 
-    if start_time >= end_time || rate_per_second < 0 {
+    if start_time >= end_time || rate_per_second < 0 || rate_denominator == 0 {
         return 0;
     }
 
@@ -32,52 +35,361 @@ pub fn calculate_accrued_amount(
     };
 
     let accrued = match elapsed_seconds.checked_mul(rate_per_second) {
-        Some(amount) => amount,
+        Some(amount) => amount / rate_denominator as i128,
         None => deposit_amount,
     };
 
     accrued.min(deposit_amount).max(0)
 }
 
+/// Computes accrual for a schedule that releases `cliff_unlock_amount` as a lump
+/// sum at `cliff_time` and then streams linearly from `cliff_time` at
+/// `rate_per_second / rate_denominator` until `end_time`.
+///
+/// Returns `0` before `cliff_time`; the result is clamped to `[0, deposit_amount]`
+/// and multiplication overflow is treated like `calculate_accrued_amount`.
+pub fn calculate_cliff_unlock_accrued(
+    cliff_time: u64,
+    end_time: u64,
+    rate_per_second: i128,
+    rate_denominator: u64,
+    cliff_unlock_amount: i128,
+    deposit_amount: i128,
+    current_time: u64,
+) -> i128 {
+    if current_time < cliff_time
+        || cliff_time > end_time
+        || rate_per_second < 0
+        || rate_denominator == 0
+    {
+        return 0;
+    }
+
+    let elapsed_seconds = (current_time.min(end_time) - cliff_time) as i128;
+    let linear = match elapsed_seconds.checked_mul(rate_per_second) {
+        Some(amount) => amount / rate_denominator as i128,
+        None => deposit_amount,
+    };
+
+    linear
+        .saturating_add(cliff_unlock_amount)
+        .min(deposit_amount)
+        .max(0)
+}
+
+/// Computes the unlocked amount of a step-function (tranche) schedule.
+///
+/// `[start_time, end_time]` is split into `intervals` evenly spaced steps. Each
+/// step releases `deposit_amount / intervals`; the final step at `end_time`
+/// releases the whole remaining deposit, so an indivisible remainder lands in the
+/// last tranche.
+///
+/// Returns `0` for invalid schedules (`start_time >= end_time` or `intervals == 0`).
+pub fn calculate_stepped_amount(
+    start_time: u64,
+    end_time: u64,
+    intervals: u32,
+    deposit_amount: i128,
+    current_time: u64,
+) -> i128 {
+    if start_time >= end_time || intervals == 0 {
+        return 0;
+    }
+
+    let steps = steps_unlocked(start_time, end_time, intervals, current_time);
+    if steps >= intervals as u64 {
+        return deposit_amount.max(0);
+    }
+
+    let tranche = deposit_amount / intervals as i128;
+    (tranche * steps as i128).max(0)
+}
+
+/// Number of steps of a stepped schedule unlocked at `current_time`, in
+/// `[0, intervals]`. Step `k` unlocks once `(t - start) * intervals / duration >= k`.
+pub fn steps_unlocked(start_time: u64, end_time: u64, intervals: u32, current_time: u64) -> u64 {
+    if current_time <= start_time || start_time >= end_time {
+        return 0;
+    }
+    let duration = (end_time - start_time) as u128;
+    let elapsed = (current_time.min(end_time) - start_time) as u128;
+    (elapsed * intervals as u128 / duration) as u64
+}
+
+/// Time at which step `step` (1-based, at most `intervals`) of a stepped schedule
+/// unlocks. Step `intervals` always unlocks at `end_time`.
+pub fn step_unlock_time(start_time: u64, end_time: u64, intervals: u32, step: u64) -> u64 {
+    let duration = (end_time - start_time) as u128;
+    let offset = (step as u128 * duration).div_ceil(intervals as u128);
+    start_time + offset as u64
+}
+
+/// Fixed-point one for `curve_fraction`: fractions of the schedule are scaled by 1e18.
+const CURVE_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Binary digits of the fractional exponent resolved by repeated square roots.
+const CURVE_FRACTION_BITS: u32 = 20;
+
+/// Computes accrual for an exponential curve:
+/// `deposit × ((t - start) / (end - start))^(exponent_bps / 10_000)`.
+///
+/// `exponent_bps = 10_000` matches a linear stream that exactly spends its deposit;
+/// larger exponents back-load the release, smaller ones front-load it. Returns `0`
+/// before `cliff_time` and for invalid schedules (`start_time >= end_time` or
+/// `exponent_bps == 0`), and the whole deposit from `end_time` on. Rounds down.
+pub fn calculate_curved_amount(
+    start_time: u64,
+    cliff_time: u64,
+    end_time: u64,
+    exponent_bps: u32,
+    deposit_amount: i128,
+    current_time: u64,
+) -> i128 {
+    if current_time < cliff_time || start_time >= end_time || exponent_bps == 0 {
+        return 0;
+    }
+    if current_time >= end_time {
+        return deposit_amount.max(0);
+    }
+    if current_time <= start_time {
+        return 0;
+    }
+
+    let elapsed = (current_time - start_time) as u128;
+    let duration = (end_time - start_time) as u128;
+    let fraction = curve_fraction(elapsed * CURVE_SCALE / duration, exponent_bps);
+
+    // Split the deposit so `deposit × fraction` cannot overflow.
+    let scale = CURVE_SCALE as i128;
+    let fraction = fraction as i128;
+    let accrued = deposit_amount / scale * fraction + deposit_amount % scale * fraction / scale;
+    accrued.min(deposit_amount).max(0)
+}
+
+/// `x^(exponent_bps / 10_000)` for a fixed-point `x` in `[0, CURVE_SCALE]`.
+///
+/// The whole part of the exponent is applied by square-and-multiply; the
+/// fractional part bit by bit, multiplying in `x^(1/2)`, `x^(1/4)`, ... for each
+/// set binary digit. Every step rounds down, so the result never exceeds the
+/// exact value and is non-decreasing in `x`.
+fn curve_fraction(x: u128, exponent_bps: u32) -> u128 {
+    let mul = |a: u128, b: u128| a * b / CURVE_SCALE;
+
+    let mut result = CURVE_SCALE;
+    let mut base = x;
+    let mut whole = exponent_bps / 10_000;
+    while whole > 0 {
+        if whole & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        whole >>= 1;
+    }
+
+    let mut root = x;
+    let mut remainder = exponent_bps % 10_000;
+    for _ in 0..CURVE_FRACTION_BITS {
+        if remainder == 0 {
+            break;
+        }
+        root = (root * CURVE_SCALE).isqrt();
+        remainder *= 2;
+        if remainder >= 10_000 {
+            remainder -= 10_000;
+            result = mul(result, root);
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
-    use super::calculate_accrued_amount;
+    use super::{
+        calculate_accrued_amount, calculate_cliff_unlock_accrued, calculate_curved_amount,
+        calculate_stepped_amount, step_unlock_time,
+    };
 
     #[test]
     fn returns_zero_before_cliff() {
-        let accrued = calculate_accrued_amount(0, 500, 1000, 1, 1000, 499);
+        let accrued = calculate_accrued_amount(0, 500, 1000, 1, 1, 1000, 499);
         assert_eq!(accrued, 0);
     }
 
     #[test]
     fn accrues_from_start_at_cliff() {
-        let accrued = calculate_accrued_amount(0, 500, 1000, 1, 1000, 500);
+        let accrued = calculate_accrued_amount(0, 500, 1000, 1, 1, 1000, 500);
         assert_eq!(accrued, 500);
     }
 
     #[test]
     fn caps_at_end_time_and_deposit() {
-        let accrued = calculate_accrued_amount(0, 0, 1000, 2, 1000, 9_999);
+        let accrued = calculate_accrued_amount(0, 0, 1000, 2, 1, 1000, 9_999);
         assert_eq!(accrued, 1000);
     }
 
     #[test]
     fn returns_zero_for_invalid_schedule() {
-        let accrued = calculate_accrued_amount(10, 10, 10, 1, 1000, 10);
+        let accrued = calculate_accrued_amount(10, 10, 10, 1, 1, 1000, 10);
         assert_eq!(accrued, 0);
     }
 
     #[test]
     fn returns_zero_for_negative_rate() {
-        let accrued = calculate_accrued_amount(0, 0, 1000, -1, 1000, 100);
+        let accrued = calculate_accrued_amount(0, 0, 1000, -1, 1, 1000, 100);
         assert_eq!(accrued, 0);
     }
 
     #[test]
     fn multiplication_overflow_returns_capped_deposit() {
-        let accrued = calculate_accrued_amount(0, 0, u64::MAX, i128::MAX, 10_000, u64::MAX);
+        let accrued = calculate_accrued_amount(0, 0, u64::MAX, i128::MAX, 1, 10_000, u64::MAX);
         assert_eq!(accrued, 10_000);
     }
+
+    #[test]
+    fn max_rate_overflows_after_two_seconds_and_caps_at_deposit() {
+        // i128::MAX × 2 is the smallest elapsed time that overflows at the max rate.
+        let accrued = calculate_accrued_amount(0, 0, 1000, i128::MAX, 1, 10_000, 2);
+        assert_eq!(accrued, 10_000);
+        assert_eq!(
+            calculate_accrued_amount(0, 0, 1000, i128::MAX, 1, 10_000, 1),
+            10_000
+        );
+    }
+
+    #[test]
+    fn stepped_unlocks_whole_tranches_only() {
+        assert_eq!(calculate_stepped_amount(0, 1000, 4, 1000, 0), 0);
+        assert_eq!(calculate_stepped_amount(0, 1000, 4, 1000, 249), 0);
+        assert_eq!(calculate_stepped_amount(0, 1000, 4, 1000, 250), 250);
+        assert_eq!(calculate_stepped_amount(0, 1000, 4, 1000, 999), 750);
+        assert_eq!(calculate_stepped_amount(0, 1000, 4, 1000, 1000), 1000);
+    }
+
+    #[test]
+    fn stepped_remainder_goes_to_last_tranche() {
+        assert_eq!(calculate_stepped_amount(0, 900, 3, 1000, 300), 333);
+        assert_eq!(calculate_stepped_amount(0, 900, 3, 1000, 600), 666);
+        assert_eq!(calculate_stepped_amount(0, 900, 3, 1000, 900), 1000);
+    }
+
+    #[test]
+    fn stepped_invalid_schedule_returns_zero() {
+        assert_eq!(calculate_stepped_amount(10, 10, 4, 1000, 20), 0);
+        assert_eq!(calculate_stepped_amount(0, 1000, 0, 1000, 500), 0);
+    }
+
+    #[test]
+    fn step_unlock_times_are_evenly_spaced() {
+        assert_eq!(step_unlock_time(100, 1100, 4, 1), 350);
+        assert_eq!(step_unlock_time(100, 1100, 4, 2), 600);
+        assert_eq!(step_unlock_time(100, 1100, 4, 4), 1100);
+        // Uneven split rounds each boundary up so a step never unlocks early.
+        assert_eq!(step_unlock_time(0, 10, 3, 1), 4);
+        assert_eq!(calculate_stepped_amount(0, 10, 3, 9, 3), 0);
+        assert_eq!(calculate_stepped_amount(0, 10, 3, 9, 4), 3);
+    }
+
+    #[test]
+    fn cliff_unlock_releases_lump_sum_at_cliff() {
+        assert_eq!(
+            calculate_cliff_unlock_accrued(500, 1000, 1, 1, 300, 800, 499),
+            0
+        );
+        assert_eq!(
+            calculate_cliff_unlock_accrued(500, 1000, 1, 1, 300, 800, 500),
+            300
+        );
+        assert_eq!(
+            calculate_cliff_unlock_accrued(500, 1000, 1, 1, 300, 800, 600),
+            400
+        );
+        assert_eq!(
+            calculate_cliff_unlock_accrued(500, 1000, 1, 1, 300, 800, 5000),
+            800
+        );
+    }
+
+    #[test]
+    fn cliff_unlock_overflow_caps_at_deposit() {
+        let accrued =
+            calculate_cliff_unlock_accrued(0, u64::MAX, i128::MAX, 1, 10, 1_000, u64::MAX);
+        assert_eq!(accrued, 1_000);
+    }
+
+    #[test]
+    fn rate_denominator_accrues_sub_second_rates_rounding_down() {
+        // 1 token per 10 seconds over 100 seconds.
+        assert_eq!(calculate_accrued_amount(0, 0, 100, 1, 10, 10, 9), 0);
+        assert_eq!(calculate_accrued_amount(0, 0, 100, 1, 10, 10, 10), 1);
+        assert_eq!(calculate_accrued_amount(0, 0, 100, 1, 10, 10, 55), 5);
+        assert_eq!(calculate_accrued_amount(0, 0, 100, 1, 10, 10, 100), 10);
+        assert_eq!(
+            calculate_cliff_unlock_accrued(50, 100, 3, 10, 5, 20, 80),
+            14
+        );
+    }
+
+    #[test]
+    fn zero_rate_denominator_returns_zero() {
+        assert_eq!(calculate_accrued_amount(0, 0, 100, 1, 0, 10, 50), 0);
+        assert_eq!(calculate_cliff_unlock_accrued(0, 100, 1, 0, 5, 10, 50), 0);
+    }
+
+    #[test]
+    fn curve_with_unit_exponent_matches_linear() {
+        for t in [0, 1, 250, 333, 500, 750, 999, 1000] {
+            assert_eq!(
+                calculate_curved_amount(0, 0, 1000, 10_000, 1000, t),
+                calculate_accrued_amount(0, 0, 1000, 1, 1, 1000, t)
+            );
+        }
+    }
+
+    #[test]
+    fn quadratic_curve_back_loads_release() {
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 20_000, 1000, 250), 62);
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 20_000, 1000, 500), 250);
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 20_000, 1000, 750), 562);
+        assert_eq!(
+            calculate_curved_amount(0, 0, 1000, 20_000, 1000, 1000),
+            1000
+        );
+    }
+
+    #[test]
+    fn fractional_exponents_use_roots() {
+        // 0.25^1.5 = 0.125 and 0.25^0.5 = 0.5.
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 15_000, 1000, 250), 125);
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 5_000, 1000, 250), 500);
+        // 0.5^1.25 = 0.42044...
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 12_500, 1000, 500), 420);
+    }
+
+    #[test]
+    fn curve_respects_cliff_and_is_monotonic() {
+        assert_eq!(calculate_curved_amount(0, 500, 1000, 20_000, 1000, 499), 0);
+        assert_eq!(
+            calculate_curved_amount(0, 500, 1000, 20_000, 1000, 500),
+            250
+        );
+
+        let mut previous = 0;
+        for t in (0..=1000).step_by(7) {
+            let accrued = calculate_curved_amount(0, 0, 1000, 17_321, 1_000_000, t);
+            assert!(accrued >= previous);
+            previous = accrued;
+        }
+    }
+
+    #[test]
+    fn curve_handles_extreme_deposits_and_invalid_schedules() {
+        assert_eq!(
+            calculate_curved_amount(0, 0, 1000, 20_000, i128::MAX, 500),
+            i128::MAX / 4
+        );
+        assert_eq!(calculate_curved_amount(10, 10, 10, 20_000, 1000, 10), 0);
+        assert_eq!(calculate_curved_amount(0, 0, 1000, 0, 1000, 500), 0);
+    }
 }
 
 #[cfg(test)]
@@ -110,7 +422,7 @@ mod invariants {
             ];
 
             for &t in &times {
-                let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, t);
+                let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, t);
 
                 assert!(
                     accrued >= 0,
@@ -156,10 +468,11 @@ mod invariants {
             times_buf[len] = end;
             len += 1;
 
-            let mut prev = calculate_accrued_amount(start, cliff, end, rate, deposit, times_buf[0]);
+            let mut prev =
+                calculate_accrued_amount(start, cliff, end, rate, 1, deposit, times_buf[0]);
 
             for &t in times_buf.iter().take(len).skip(1) {
-                let now = calculate_accrued_amount(start, cliff, end, rate, deposit, t);
+                let now = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, t);
 
                 assert!(
                     now >= prev,
@@ -213,7 +526,7 @@ mod accrued_after_end_time {
     #[test]
     fn exactly_at_end_time_equals_deposit() {
         let (start, cliff, end, rate, deposit) = standard_stream();
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, end);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, end);
         assert_eq!(
             accrued, deposit,
             "at end_time, accrued should equal deposit_amount"
@@ -224,7 +537,7 @@ mod accrued_after_end_time {
     #[test]
     fn one_second_after_end_time_still_capped() {
         let (start, cliff, end, rate, deposit) = standard_stream();
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, end + 1);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, end + 1);
         assert_eq!(
             accrued, deposit,
             "one second past end_time should not accrue more than deposit_amount"
@@ -236,7 +549,7 @@ mod accrued_after_end_time {
     fn long_after_end_time_still_capped() {
         let (start, cliff, end, rate, deposit) = standard_stream();
         let far_future = end + 10_000;
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, far_future);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, far_future);
         assert_eq!(
             accrued, deposit,
             "long after end_time, accrued must be capped at deposit_amount"
@@ -247,7 +560,7 @@ mod accrued_after_end_time {
     #[test]
     fn max_time_does_not_overflow() {
         let (start, cliff, end, rate, deposit) = standard_stream();
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, u64::MAX);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, u64::MAX);
         assert_eq!(
             accrued, deposit,
             "u64::MAX current_time should cap safely at deposit_amount"
@@ -262,7 +575,7 @@ mod accrued_after_end_time {
     #[test]
     fn one_second_before_end_time_less_than_deposit() {
         let (start, cliff, end, rate, deposit) = standard_stream();
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, end - 1);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, end - 1);
         assert!(
             accrued < deposit,
             "one second before end_time, accrued ({accrued}) should be less than deposit ({deposit})"
@@ -274,7 +587,7 @@ mod accrued_after_end_time {
     #[test]
     fn at_start_time_accrues_zero() {
         let (start, cliff, end, rate, deposit) = standard_stream();
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, start);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, start);
         assert_eq!(accrued, 0, "at start_time, nothing should have accrued yet");
     }
 
@@ -283,7 +596,7 @@ mod accrued_after_end_time {
     fn midway_accrues_half_deposit() {
         let (start, cliff, end, rate, deposit) = standard_stream();
         let midpoint = (start + end) / 2; // 1500
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, midpoint);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, midpoint);
         assert_eq!(
             accrued, 500,
             "halfway through, should accrue half the deposit"
@@ -303,6 +616,7 @@ mod accrued_after_end_time {
             0,     // cliff
             1_000, // end
             10,    // rate_per_second
+            1,     // rate_denominator
             5_000, // deposit (lower than rate * duration)
             1_000, // current_time == end_time
         );
@@ -316,7 +630,7 @@ mod accrued_after_end_time {
     #[test]
     fn high_rate_long_after_end_still_caps_at_deposit() {
         let accrued = calculate_accrued_amount(
-            0, 0, 1_000, 10, 5_000, 999_999, // far future
+            0, 0, 1_000, 10, 1, 5_000, 999_999, // far future
         );
         assert_eq!(accrued, 5_000);
     }
@@ -336,6 +650,7 @@ mod accrued_after_end_time {
             5_000, // cliff (way after end)
             1_000, // end
             1,     // rate
+            1,     // rate_denominator
             1_000, // deposit
             2_000, // current_time > end but < cliff
         );
@@ -354,8 +669,8 @@ mod accrued_after_end_time {
     fn pure_function_same_result_on_repeat_calls() {
         let (start, cliff, end, rate, deposit) = standard_stream();
         let t = end + 500;
-        let first = calculate_accrued_amount(start, cliff, end, rate, deposit, t);
-        let second = calculate_accrued_amount(start, cliff, end, rate, deposit, t);
+        let first = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, t);
+        let second = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, t);
         assert_eq!(first, second, "pure function must be deterministic");
         assert_eq!(first, deposit);
     }
@@ -378,7 +693,7 @@ mod accrued_after_end_time {
         // so expected = min(3000, 2000) = 2000
         let expected = (rate * (end - start) as i128).min(deposit);
 
-        let accrued = calculate_accrued_amount(start, cliff, end, rate, deposit, end + 9_999);
+        let accrued = calculate_accrued_amount(start, cliff, end, rate, 1, deposit, end + 9_999);
         assert_eq!(
             accrued, expected,
             "result must match the documented cap formula: min(rate*(end-start), deposit)"
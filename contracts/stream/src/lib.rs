@@ -1,9 +1,13 @@
 #![no_std]
 
 mod accrual;
+pub mod version;
+
+use core::cmp::Ordering;
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env,
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, token, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, Val,
 };
 
 // ---------------------------------------------------------------------------
@@ -23,9 +27,53 @@ const PERSISTENT_BUMP_AMOUNT: u32 = 120_960;
 
 /// Compile-time contract version number.
 ///
-/// Increment this constant whenever a breaking change is deployed so that
+/// Increment `version::MAJOR` whenever a breaking change is deployed so that
 /// frontends and scripts can detect which version is running on-chain.
-pub const CONTRACT_VERSION: u32 = 1;
+pub const CONTRACT_VERSION: u32 = version::MAJOR;
+
+/// Denominator for basis-point values (`10_000` bps = 100%).
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Highest protocol fee accepted by `set_fee_bps` and `update_config` (`1_000` bps = 10%).
+pub const MAX_CONFIG_FEE_BPS: u32 = 1_000;
+
+/// Largest page `list_streams_paginated` returns; bigger `limit`s are clamped.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Largest page `get_stream_ids_for_recipient` returns.
+pub const MAX_RECIPIENT_PAGE_SIZE: u32 = 100;
+
+/// Largest page `get_stream_ids_by_status` returns.
+pub const MAX_STATUS_PAGE_SIZE: u32 = 100;
+
+/// Longest memo, in bytes, accepted by `create_stream_with_memo`.
+pub const MAX_MEMO_LEN: u32 = 64;
+
+/// Longest description, in bytes, accepted by `set_stream_description`.
+pub const MAX_DESCRIPTION_LEN: u32 = 256;
+
+/// Most streams `bump_sender_streams_ttl` / `bump_recipient_streams_ttl` extend per call.
+pub const MAX_TTL_BUMP_BATCH: u32 = 50;
+
+/// Most stream IDs `get_streams_bulk` accepts per call.
+pub const MAX_BULK_READ: u32 = 50;
+
+/// Most `WithdrawalRecord`s kept per stream; older ones are dropped.
+pub const MAX_WITHDRAWAL_HISTORY: u32 = 100;
+
+/// Most `(timestamp, status)` entries kept per stream; older ones are dropped.
+pub const MAX_STATUS_HISTORY: u32 = 20;
+
+/// Largest `CurveType::Exponential` exponent, in basis points (an exponent of 10).
+pub const MAX_CURVE_EXPONENT_BPS: u32 = 100_000;
+
+/// Initial `Config.expiry_grace_period`: 30 days after a stream's end before
+/// anyone may call `expire_stream`.
+pub const DEFAULT_EXPIRY_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// Initial `Config.recovery_grace_period`: 365 days after a stream's end before
+/// the recovery address may call `recover_orphaned_stream`.
+pub const DEFAULT_RECOVERY_GRACE_PERIOD: u64 = 365 * 24 * 60 * 60;
 
 // ---------------------------------------------------------------------------
 // Data types
@@ -37,6 +85,53 @@ pub const CONTRACT_VERSION: u32 = 1;
 pub struct Config {
     pub token: Address,
     pub admin: Address,
+    /// Address proposed via `propose_admin`, waiting to call `accept_admin`.
+    pub pending_admin: Option<Address>,
+    /// Whether recipients may renounce streams via `cancel_stream_as_recipient`.
+    pub recipient_cancel_allowed: bool,
+    /// Protocol fee taken from each new stream's deposit, in basis points.
+    pub fee_bps: u32,
+    /// Smallest deposit accepted for a new stream (0 = no minimum).
+    pub min_deposit: i128,
+    /// Smallest `rate_per_second` accepted for a new linear stream (0 = no minimum).
+    pub min_rate: i128,
+    /// Shortest `end_time - start_time` accepted for a new stream (0 = no minimum).
+    pub min_duration: u64,
+    /// TTL (in ledgers) every stream write extends its entry to; `0` uses the
+    /// built-in `PERSISTENT_BUMP_AMOUNT`, and smaller values never shorten it.
+    pub min_ttl_ledgers: u32,
+    /// Minimum seconds between two paying withdrawals from the same live stream
+    /// (0 = no cooldown).
+    pub withdrawal_cooldown: u64,
+    /// Smallest amount a single withdrawal may pay out, unless it is the stream's
+    /// last (0 = no threshold).
+    pub min_withdraw_amount: i128,
+    /// Seconds after a stream is fully accrued before `expire_stream` may sweep it.
+    pub expiry_grace_period: u64,
+    /// Last-resort recipient of `recover_orphaned_stream`; `None` (the default)
+    /// disables recovery.
+    pub recovery_address: Option<Address>,
+    /// Seconds after a stream's end before `recover_orphaned_stream` may sweep it.
+    pub recovery_grace_period: u64,
+    /// When `true`, the withdrawal that drains a fully elapsed stream also refunds
+    /// the sender any deposit beyond `rate × duration` and completes the stream.
+    pub allow_excess_deposit: bool,
+    /// When `true`, new rate-based streams must be funded with exactly
+    /// `rate × duration`; any surplus fails with `ExcessDeposit`.
+    pub strict_deposit_mode: bool,
+    /// Protocol fee taken from each withdrawal, in basis points (0 = none).
+    /// Applies on top of `fee_bps`.
+    pub fee_on_withdrawal_bps: u32,
+    /// Maximum number of streams a single sender may create (0 = unlimited).
+    pub max_streams_per_sender: u32,
+    /// When `true`, only addresses on the sender whitelist may create streams.
+    pub whitelist_enabled: bool,
+    /// Stream creation stops once this many streams have been created by any path,
+    /// nonce-derived IDs included (`u64::MAX` = effectively unlimited).
+    pub global_stream_cap: u64,
+    /// Emergency stop set by `admin_pause_protocol`: while `true`, every
+    /// user-facing mutating function fails with `ProtocolPaused`.
+    pub protocol_paused: bool,
 }
 
 #[contracttype]
@@ -46,24 +141,100 @@ pub enum StreamStatus {
     Paused = 1,
     Completed = 2,
     Cancelled = 3,
+    /// Created via `create_pending_stream`; waiting for the recipient to accept.
+    /// No time accrues in this state.
+    Pending = 4,
+    /// Created with `start_time` in the future. Becomes `Active` once the start
+    /// time passes, lazily on the next `withdraw` / `calculate_accrued` or
+    /// explicitly via `activate_stream`.
+    Scheduled = 5,
+    /// Fully accrued but never drained; `expire_stream` paid the remainder out
+    /// after the grace period. Terminal, like `Completed`.
+    Expired = 6,
 }
 
+/// Typed error codes returned by the contract.
+///
+/// Every failure surfaces as one of these variants, so calling contracts and the
+/// generated `try_*` client methods can match on the exact cause. Codes are part of
+/// the public API: append new variants, never renumber existing ones.
 #[soroban_sdk::contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum ContractError {
+    /// No stream exists for the given ID.
     StreamNotFound = 1,
+    /// The stream's status does not allow the operation.
     InvalidState = 2,
+    /// `init` was called on an already initialised contract.
+    AlreadyInitialized = 3,
+    /// The contract has not been initialised yet.
+    NotInitialized = 4,
+    /// Stream parameters are invalid (amounts, addresses or time window).
+    InvalidParams = 5,
+    /// `deposit_amount` does not cover `rate_per_second × duration`.
+    UnfundedDeposit = 6,
+    /// An intermediate amount overflowed `i128`.
+    ArithmeticOverflow = 7,
+    /// The operation is not allowed while the stream is paused.
+    StreamPaused = 8,
+    /// The operation is not allowed on a cancelled stream.
+    StreamCancelled = 9,
+    /// The operation is not allowed on a completed stream.
+    StreamCompleted = 10,
+    /// The authorizing address may not act on this stream.
+    Unauthorized = 11,
+    /// `accept_admin` was called with no admin transfer pending.
+    NoPendingAdmin = 12,
+    /// Recipient cancellation is disabled by `Config.recipient_cancel_allowed`.
+    RecipientCancelDisabled = 13,
+    /// The operation is not allowed while the stream awaits acceptance.
+    StreamPending = 14,
+    /// The pending stream's acceptance deadline has passed.
+    PendingExpired = 15,
+    /// The operation is not allowed before a scheduled stream starts.
+    StreamScheduled = 16,
+    /// The admin has paused the whole protocol (`Config.protocol_paused`).
+    ProtocolPaused = 17,
+    /// The stream was created with `cancellable = false`.
+    StreamNotCancellable = 18,
+    /// The sender already has `Config.max_streams_per_sender` streams.
+    StreamLimitExceeded = 19,
+    /// Less than `Config.withdrawal_cooldown` (or, for `trigger_auto_withdraw`,
+    /// the stream's `auto_withdraw_interval`) seconds have passed since the
+    /// stream's last withdrawal.
+    WithdrawalCooldown = 20,
+    /// The withdrawal is smaller than `Config.min_withdraw_amount` and does not
+    /// drain the stream.
+    BelowDustThreshold = 21,
+    /// The operation is not allowed on an expired stream.
+    StreamExpired = 22,
+    /// The sender's cancellation right ended at the stream's `cancel_after`.
+    CancelDeadlinePassed = 23,
+    /// `freeze_contract` made the configuration permanently immutable.
+    ContractFrozen = 24,
+    /// The sender already created a stream with this `create_stream_with_nonce` nonce.
+    NonceAlreadyUsed = 25,
+    /// The sender whitelist is enabled and the sender is not on it.
+    SenderNotWhitelisted = 26,
+    /// The recipient was blacklisted by `blacklist_recipient`.
+    RecipientBlacklisted = 27,
+    /// The number of streams ever created has reached `Config.global_stream_cap`.
+    GlobalCapReached = 28,
+    /// `Config.strict_deposit_mode` is on and `deposit_amount` exceeds
+    /// `rate_per_second × duration`.
+    ExcessDeposit = 29,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum StreamEvent {
-    Paused(u64),
-    Resumed(u64),
-    Cancelled(u64),
-}
+// ---------------------------------------------------------------------------
+// Events
+//
+// Every stream lifecycle event is published under the topic
+// `("STREAM", <event_name>, stream_id)` so indexers can subscribe to all stream
+// activity with a single topic filter.
+// ---------------------------------------------------------------------------
 
+/// Payload of `("STREAM", "created", stream_id)`.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct StreamCreated {
@@ -77,12 +248,304 @@ pub struct StreamCreated {
     pub end_time: u64,
 }
 
+/// Payload of `("STREAM", "withdrawn", stream_id)`.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct Withdrawal {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamWithdrawn {
+    pub stream_id: u64,
+    /// Amount transferred by this withdrawal.
+    pub amount: i128,
+    /// Withdrawal fee kept from this withdrawal; `amount + fee` is what it added
+    /// to `withdrawn_total`.
+    pub fee: i128,
+    /// `withdrawn_amount` of the stream after this withdrawal.
+    pub withdrawn_total: i128,
+}
+
+/// Payload of `("STREAM", "cancelled", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamCancelled {
+    pub stream_id: u64,
+    /// Unstreamed tokens returned to the sender.
+    pub sender_refund: i128,
+    /// Tokens accrued to the recipient at cancellation (withdrawn or not).
+    pub recipient_accrued: i128,
+}
+
+/// Payload of `("STREAM", "admin_cancelled", stream_id)`, published by
+/// `admin_cancel_stream` right after the regular `cancelled` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamAdminCancelled {
+    pub stream_id: u64,
+    /// Admin-defined code recording why the stream was cancelled.
+    pub reason_code: u32,
+    pub sender_refund: i128,
+    pub recipient_accrued: i128,
+}
+
+/// Payload of `("STREAM", "force_completed", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamForceCompleted {
+    pub stream_id: u64,
+    /// Admin-defined code recording why the stream was force-completed.
+    pub reason_code: u32,
+    /// Tokens accrued at the time of the call (withdrawn or not).
+    pub accrued: i128,
+    /// Tokens paid to the recipient by the call: the whole unwithdrawn deposit.
+    pub recipient_amount: i128,
+}
+
+/// Payload of `("STREAM", "accepted", stream_id)`: the schedule after shifting it to
+/// the acceptance time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamAccepted {
+    pub stream_id: u64,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+/// Payload of `("STREAM", "rejected", stream_id)` and `("STREAM", "expired", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamPendingRefunded {
+    pub stream_id: u64,
+    pub sender_refund: i128,
+}
+
+/// Payload of `("STREAM", "abandoned", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamExpired {
+    pub stream_id: u64,
+    /// Unclaimed accrued tokens sent to the recipient.
+    pub recipient_amount: i128,
+    /// Unaccrued tokens returned to the sender (normally 0).
+    pub sender_refund: i128,
+}
+
+/// Payload of `("STREAM", "recovered", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrphanedStreamRecovered {
+    pub stream_id: u64,
+    pub recovery_address: Address,
+    /// Tokens still held for the stream, sent to `recovery_address`.
+    pub amount: i128,
+}
+
+/// Payload of `("config", "token")`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMigrated {
+    pub old_token: Address,
+    pub new_token: Address,
+    /// `get_config_version` after the migration.
+    pub version: u32,
+}
+
+/// Payload of `("STREAM", "excess", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamExcessRefunded {
+    pub stream_id: u64,
+    /// Deposit beyond the streamed schedule, returned to the sender.
+    pub sender_refund: i128,
+}
+
+/// Payload of `("STREAM", "paused", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamPaused {
+    pub stream_id: u64,
+    pub paused_at: u64,
+    /// `true` when paused through an admin entry point.
+    pub admin: bool,
+}
+
+/// Payload of `("STREAM", "resumed", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamResumed {
+    pub stream_id: u64,
+    pub resumed_at: u64,
+    /// `true` when resumed through an admin entry point.
+    pub admin: bool,
+}
+
+/// Payload of `("STREAM", "activated", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamActivated {
+    pub stream_id: u64,
+    pub activated_at: u64,
+}
+
+/// Payload of `("STREAM", "completed", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamCompleted {
+    pub stream_id: u64,
+}
+
+/// Payload of `("STREAM", "operator_approved", stream_id)` and
+/// `("STREAM", "operator_revoked", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamOperatorUpdated {
+    pub stream_id: u64,
+    pub operator: Address,
+}
+
+/// Payload of `("STREAM", "recipient_updated", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecipientUpdated {
+    pub stream_id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+}
+
+/// Payload of `("STREAM", "description_updated", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamDescriptionUpdated {
+    pub stream_id: u64,
+    pub description: Bytes,
+}
+
+/// Payload of `("STREAM", "sender_transferred", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SenderTransferred {
+    pub stream_id: u64,
+    pub old_sender: Address,
+    pub new_sender: Address,
+}
+
+/// Payload of `("STREAM", "topped_up", stream_id)` and `("STREAM", "extended", stream_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamToppedUp {
     pub stream_id: u64,
-    pub recipient: Address,
     pub amount: i128,
+    pub deposit_amount: i128,
+    pub end_time: u64,
+}
+
+/// What pausing a stream does to its schedule.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PauseMode {
+    /// Pausing only blocks withdrawals; time keeps accruing in the background.
+    #[default]
+    WithdrawalBlock = 0,
+    /// Pausing stops the clock: paused time is excluded from accrual and the
+    /// stream ends later by the same amount.
+    AccrualFreeze = 1,
+}
+
+/// Shape of a stream's release schedule between `start_time` and `end_time`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CurveType {
+    /// Tokens accrue at `rate_per_second`.
+    #[default]
+    Linear,
+    /// `accrued = deposit × (elapsed / duration)^(exponent_bps / 10_000)`, where
+    /// the value is `exponent_bps` in `[1, MAX_CURVE_EXPONENT_BPS]`. `10_000` is
+    /// linear over the whole deposit; `20_000` is quadratic, releasing a quarter of
+    /// the deposit at the halfway point.
+    Exponential(u32),
+}
+
+/// Who paused a stream, recorded in `Stream.paused_by`.
+///
+/// Stored directly rather than as `Option<PauserKind>`: the SDK cannot convert
+/// optional `contracttype` enums inside a struct, so `NotPaused` plays `None`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PauserKind {
+    NotPaused,
+    Sender,
+    /// Only the admin can resume an admin pause.
+    Admin,
+    /// Via `pause_stream_as_recipient`, on a stream with `recipient_can_pause`.
+    Recipient,
+}
+
+/// Optional behaviour chosen at creation via `create_stream_with_options`.
+/// `StreamOptions::default()` matches plain `create_stream`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamOptions {
+    pub pause_mode: PauseMode,
+    /// Lump sum released at `cliff_time`; the rest streams linearly from the cliff.
+    /// 0 (the default) means a plain linear stream.
+    pub cliff_unlock_amount: i128,
+    /// `false` makes the stream irrevocable: no party, including the admin, can
+    /// cancel it. Defaults to `true`.
+    pub cancellable: bool,
+    /// Divides `rate_per_second`, so the stream pays `rate_per_second /
+    /// rate_denominator` tokens per second (e.g. `1 / 10` for one token every ten
+    /// seconds). Must be positive; defaults to 1.
+    pub rate_denominator: u64,
+    /// `true` starts the stream in `Pending`, as `create_pending_stream`
+    /// does: the recipient must `accept_stream` before `offer_expiry`. Defaults
+    /// to `false`.
+    pub recipient_must_accept: bool,
+    /// Acceptance deadline when `recipient_must_accept` is set (must be in the
+    /// future); ignored otherwise.
+    pub offer_expiry: u64,
+    /// Last ledger time at which the sender may cancel; 0 (the default) means no
+    /// deadline. The admin and recipient cancel paths are not restricted.
+    pub cancel_after: u64,
+    /// `false` refunds any deposit beyond `rate × duration` to the sender when
+    /// the schedule completes, whatever `Config.allow_excess_deposit` says.
+    /// Defaults to `true`: the surplus is only refunded if the config allows it.
+    pub strict_deposit: bool,
+    /// Early-cancel penalty in basis points of the unstreamed amount: on
+    /// cancellation that share goes to the recipient instead of back to the
+    /// sender. At most `BPS_DENOMINATOR`; 0 (the default) disables it.
+    pub cancel_penalty_bps: u32,
+    /// `true` lets the recipient pause and resume the stream too, via
+    /// `pause_stream_as_recipient` / `resume_stream_as_recipient`. Defaults to
+    /// `false`.
+    pub recipient_can_pause: bool,
+    /// Release curve; `Exponential` cannot be combined with `cliff_unlock_amount`.
+    /// Defaults to `Linear`.
+    pub curve_type: CurveType,
+    /// `true` lets anyone call `trigger_auto_withdraw` to sweep accrued tokens to
+    /// the recipient once `auto_withdraw_interval` has passed. Defaults to `false`.
+    pub auto_withdraw: bool,
+    /// Minimum seconds between `trigger_auto_withdraw` payouts, counted from the
+    /// last withdrawal (or `start_time` before the first). Ignored unless
+    /// `auto_withdraw` is set.
+    pub auto_withdraw_interval: u64,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            pause_mode: PauseMode::default(),
+            cliff_unlock_amount: 0,
+            cancellable: true,
+            rate_denominator: 1,
+            recipient_must_accept: false,
+            offer_expiry: 0,
+            cancel_after: 0,
+            strict_deposit: true,
+            cancel_penalty_bps: 0,
+            recipient_can_pause: false,
+            curve_type: CurveType::Linear,
+            auto_withdraw: false,
+            auto_withdraw_interval: 0,
+        }
+    }
 }
 
 #[contracttype]
@@ -93,12 +556,137 @@ pub struct Stream {
     pub recipient: Address,
     pub deposit_amount: i128,
     pub rate_per_second: i128,
+    /// Divisor of `rate_per_second` for sub-second rates (see `StreamOptions`);
+    /// 1 for streams created without options.
+    pub rate_denominator: u64,
     pub start_time: u64,
     pub cliff_time: u64,
     pub end_time: u64,
     pub withdrawn_amount: i128,
+    /// Number of withdrawals that paid out a non-zero amount, through any of the
+    /// withdraw entry points.
+    pub withdraw_count: u32,
+    /// Ledger time of the latest withdrawal counted in `withdraw_count`; `None`
+    /// until the first one.
+    pub last_withdrawal_time: Option<u64>,
     pub status: StreamStatus,
     pub cancelled_at: Option<u64>,
+    /// Ledger time of the withdrawal that moved the stream to `Completed`;
+    /// `None` until then.
+    pub completed_at: Option<u64>,
+    /// Acceptance deadline of a stream created via `create_pending_stream`.
+    /// Cleared on `accept_stream`, so it stays `Some` only for streams that were
+    /// never accepted.
+    pub pending_expiry: Option<u64>,
+    pub pause_mode: PauseMode,
+    /// When the current pause began; `None` unless the stream is `Paused`.
+    pub paused_at: Option<u64>,
+    /// Who started the current pause; `NotPaused` unless the stream is `Paused`.
+    pub paused_by: PauserKind,
+    /// Seconds spent in completed pauses. Only `AccrualFreeze` streams subtract
+    /// it from accrual; for `WithdrawalBlock` streams it is informational.
+    pub total_paused_duration: u64,
+    /// Address the recipient allowed to withdraw on its behalf via
+    /// `withdraw_as_operator`. Cleared when the stream completes or is cancelled.
+    pub approved_operator: Option<Address>,
+    /// Number of tranches of a `create_step_stream` schedule; 0 for linear streams.
+    pub intervals: u32,
+    /// Lump sum unlocked at `cliff_time` (see `StreamOptions`); 0 if none.
+    pub cliff_unlock_amount: i128,
+    /// Whether the stream can be cancelled at all (see `StreamOptions`).
+    pub cancellable: bool,
+    /// Free-form reference set by `create_stream_with_memo` (at most
+    /// `MAX_MEMO_LEN` bytes); empty for streams created any other way.
+    pub memo: Bytes,
+    /// Sender cancellation deadline from `StreamOptions`; 0 for none.
+    pub cancel_after: u64,
+    /// Ledger timestamp at which the stream was created.
+    pub created_at: u64,
+    /// `StreamOptions.strict_deposit`; `false` always refunds surplus deposit at
+    /// completion.
+    pub strict_deposit: bool,
+    /// `StreamOptions.cancel_penalty_bps`; reset to 0 when the recipient cancels.
+    pub cancel_penalty_bps: u32,
+    /// Whether the recipient may pause and resume the stream (see `StreamOptions`).
+    pub recipient_can_pause: bool,
+    /// Release curve (see `StreamOptions`); `Linear` unless created with options.
+    pub curve_type: CurveType,
+    /// Whether keepers may `trigger_auto_withdraw` (see `StreamOptions`).
+    pub auto_withdraw: bool,
+    /// Seconds between `trigger_auto_withdraw` payouts (see `StreamOptions`).
+    pub auto_withdraw_interval: u64,
+    /// Token the stream was funded in: `Config.token` at creation. Every payout,
+    /// refund and top-up of the stream uses it, even after `admin_set_token`.
+    pub token: Address,
+}
+
+/// One paying withdrawal, as listed by `get_withdrawal_history`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalRecord {
+    pub timestamp: u64,
+    /// Amount transferred to `recipient`.
+    pub amount: i128,
+    /// Withdrawal fee kept from this withdrawal, as in the `withdrawn` event.
+    pub fee: i128,
+    /// Address the tokens were sent to (the `destination` of `withdraw_to`).
+    pub recipient: Address,
+    /// The stream's `withdraw_count` after this withdrawal.
+    pub withdraw_count: u32,
+}
+
+/// Semantic version of the deployed contract, returned by `get_version`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+/// Dashboard figures for one stream, returned by `get_stream_analytics`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamAnalytics {
+    pub accrued: i128,
+    pub withdrawable: i128,
+    pub refundable: i128,
+    /// `accrued / deposit_amount` in basis points, in `[0, 10_000]`.
+    pub completion_bps: u32,
+    /// Seconds until `end_time`; 0 once it has passed or the stream is terminal.
+    pub time_remaining: u64,
+}
+
+/// Per-stream outcome of `cancel_stream_batch`, mirroring the `cancelled` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchCancelResult {
+    pub stream_id: u64,
+    /// Unstreamed tokens returned to the sender.
+    pub refund: i128,
+    /// Tokens accrued to the recipient at cancellation (withdrawn or not).
+    pub recipient_accrued: i128,
+}
+
+/// Protocol-wide figures returned by `get_protocol_stats`.
+///
+/// Every stream is in exactly one status bucket, so
+/// `active_count + paused_count + completed_count + cancelled_count == total_streams`.
+/// `Pending` and `Scheduled` streams are counted as active, `Expired` ones as
+/// completed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolStats {
+    /// Streams ever created, including those with nonce-derived IDs.
+    pub total_streams: u64,
+    pub active_count: u64,
+    pub paused_count: u64,
+    pub completed_count: u64,
+    pub cancelled_count: u64,
+    /// Net deposits plus top-ups and extensions, never reduced.
+    pub total_deposited: i128,
+    /// Tokens ever withdrawn by recipients (`get_total_streamed`).
+    pub total_withdrawn: i128,
 }
 
 #[contracttype]
@@ -115,9 +703,28 @@ pub struct CreateStreamParams {
 /// Namespace for all contract storage keys.
 #[contracttype]
 pub enum DataKey {
-    Config,       // Instance storage for global settings (admin/token).
-    NextStreamId, // Instance storage for the auto-incrementing ID counter.
-    Stream(u64),  // Persistent storage for individual stream data (O(1) lookup).
+    Config,                          // Instance storage for global settings (admin/token).
+    NextStreamId,                    // Instance storage for the auto-incrementing ID counter.
+    Stream(u64),                     // Persistent storage for individual stream data (O(1) lookup).
+    SenderStreams(Address),          // Persistent storage for stream IDs created by a sender.
+    RecipientStreams(Address),       // Persistent storage for stream IDs paying a recipient.
+    AccumulatedFees,                 // Instance storage for protocol fees not yet withdrawn.
+    TotalLocked,                     // Instance storage for tokens held on behalf of streams.
+    TotalStreamed,                   // Instance storage for tokens ever withdrawn by recipients.
+    TotalDeposited,                  // Instance storage for net deposits, top-ups and extensions.
+    StatusCount(StreamStatus), // Instance storage for the number of streams in a stats bucket.
+    ConfigVersion,             // Instance storage for the number of `admin_set_token` migrations.
+    WithdrawalHistory(u64),    // Persistent storage for a stream's latest withdrawal records.
+    WithdrawalHistoryTruncated(u64), // Persistent flag: older withdrawal records were dropped.
+    LinkedStream(u64), // Persistent storage for the partner of a `create_swap_stream` stream.
+    ConfigFrozen,      // Instance flag: `freeze_contract` made the config immutable.
+    StatusHistory(u64), // Persistent storage for a stream's `(timestamp, status)` transitions.
+    UsedNonce(Address, u64), // Persistent flag: the sender consumed this creation nonce.
+    SenderWhitelist,   // Instance storage for addresses allowed to create streams.
+    BlacklistedRecipient(Address), // Persistent flag: the address may not receive new streams.
+    AgreementHash(u64), // Persistent storage for the hash anchored by `create_stream_with_agreement`.
+    StreamDescription(u64), // Persistent storage for the sender-editable stream description.
+    StreamsCreated,     // Instance storage for the number of streams ever created.
 }
 
 // ---------------------------------------------------------------------------
@@ -137,17 +744,119 @@ fn get_config(env: &Env) -> Config {
     env.storage()
         .instance()
         .get(&DataKey::Config)
-        .expect("contract not initialised: missing config")
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::NotInitialized))
+}
+
+/// Add `amount` to the running total for `token`, for batches that may span tokens.
+fn add_token_total(
+    totals: &mut Map<Address, i128>,
+    token: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let total = totals
+        .get(token.clone())
+        .unwrap_or(0)
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    totals.set(token.clone(), total);
+    Ok(())
+}
+
+/// Transfer every positive total in `totals` from the contract to `to`, one
+/// transfer per token.
+fn transfer_token_totals(env: &Env, totals: &Map<Address, i128>, to: &Address) {
+    for (token, amount) in totals.iter() {
+        if amount > 0 {
+            token::Client::new(env, &token).transfer(&env.current_contract_address(), to, &amount);
+        }
+    }
+}
+
+fn get_config_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ConfigVersion)
+        .unwrap_or(0)
 }
 
-fn get_token(env: &Env) -> Address {
-    get_config(env).token
+fn is_config_frozen(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::ConfigFrozen)
 }
 
 fn get_admin(env: &Env) -> Address {
     get_config(env).admin
 }
 
+fn get_accumulated_fees(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AccumulatedFees)
+        .unwrap_or(0)
+}
+
+fn set_accumulated_fees(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AccumulatedFees, &amount);
+    bump_instance_ttl(env);
+}
+
+/// Read a protocol-wide `i128` accumulator (`TotalLocked` / `TotalStreamed` /
+/// `TotalDeposited`).
+fn get_protocol_total(env: &Env, key: &DataKey) -> i128 {
+    env.storage().instance().get(key).unwrap_or(0)
+}
+
+/// Add `delta` (possibly negative) to a protocol-wide accumulator.
+fn adjust_protocol_total(env: &Env, key: &DataKey, delta: i128) -> Result<(), ContractError> {
+    if delta == 0 {
+        return Ok(());
+    }
+    let total = get_protocol_total(env, key)
+        .checked_add(delta)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    env.storage().instance().set(key, &total);
+    bump_instance_ttl(env);
+    Ok(())
+}
+
+/// Bucket `status` for `ProtocolStats`: `Pending` and `Scheduled` count as active,
+/// `Expired` as completed.
+fn stats_bucket(status: StreamStatus) -> StreamStatus {
+    match status {
+        StreamStatus::Pending | StreamStatus::Scheduled => StreamStatus::Active,
+        StreamStatus::Expired => StreamStatus::Completed,
+        other => other,
+    }
+}
+
+fn get_status_count(env: &Env, status: StreamStatus) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::StatusCount(stats_bucket(status)))
+        .unwrap_or(0)
+}
+
+/// Move one stream from the `from` stats bucket (`None` for a new stream) to the
+/// `to` bucket. Transitions within a bucket leave the counters untouched.
+fn move_status_count(env: &Env, from: Option<StreamStatus>, to: StreamStatus) {
+    let to = stats_bucket(to);
+    if let Some(from) = from.map(stats_bucket) {
+        if from == to {
+            return;
+        }
+        let count = get_status_count(env, from).saturating_sub(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::StatusCount(from), &count);
+    }
+    let count = get_status_count(env, to).saturating_add(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::StatusCount(to), &count);
+    bump_instance_ttl(env);
+}
+
 fn get_stream_count(env: &Env) -> u64 {
     bump_instance_ttl(env);
     env.storage()
@@ -161,6 +870,23 @@ fn set_stream_count(env: &Env, count: u64) {
     bump_instance_ttl(env);
 }
 
+/// Streams ever created by any path, including nonce-derived IDs that do not
+/// advance `NextStreamId`. `Config.global_stream_cap` is checked against it.
+fn get_streams_created(env: &Env) -> u64 {
+    bump_instance_ttl(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::StreamsCreated)
+        .unwrap_or(0u64)
+}
+
+fn increment_streams_created(env: &Env) {
+    let count = get_streams_created(env).saturating_add(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::StreamsCreated, &count);
+}
+
 fn load_stream(env: &Env, stream_id: u64) -> Result<Stream, ContractError> {
     let key = DataKey::Stream(stream_id);
     let stream: Stream = env
@@ -183,741 +909,4959 @@ fn save_stream(env: &Env, stream: &Stream) {
     let key = DataKey::Stream(stream.stream_id);
     env.storage().persistent().set(&key, stream);
 
-    // Extend TTL on stream save to ensure persistence
+    // Extend TTL on stream save to ensure persistence. A configured
+    // `min_ttl_ledgers` is topped up on every write, not only near expiry.
+    let min_ttl = get_config(env).min_ttl_ledgers.min(env.storage().max_ttl());
+    if min_ttl > PERSISTENT_BUMP_AMOUNT {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, min_ttl, min_ttl);
+    } else {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+}
+
+/// Load the stream-ID index stored under `key`, or an empty list if none exists.
+fn load_stream_index(env: &Env, key: &DataKey) -> soroban_sdk::Vec<u64> {
+    let storage = env.storage().persistent();
+    match storage.get(key) {
+        Some(ids) => {
+            storage.extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+            ids
+        }
+        None => soroban_sdk::Vec::new(env),
+    }
+}
+
+/// Append `stream_id` to the stream-ID index stored under `key`.
+fn push_stream_index(env: &Env, key: &DataKey, stream_id: u64) {
+    let mut ids = load_stream_index(env, key);
+    ids.push_back(stream_id);
+    env.storage().persistent().set(key, &ids);
     env.storage().persistent().extend_ttl(
-        &key,
+        key,
         PERSISTENT_LIFETIME_THRESHOLD,
         PERSISTENT_BUMP_AMOUNT,
     );
 }
 
-// ---------------------------------------------------------------------------
-// Internal Helpers
-// ---------------------------------------------------------------------------
-
-impl FluxoraStream {
-    fn validate_stream_params(
-        sender: &Address,
-        recipient: &Address,
-        deposit_amount: i128,
-        rate_per_second: i128,
-        start_time: u64,
-        cliff_time: u64,
-        end_time: u64,
-    ) {
-        // Validate positive amounts (#35)
-        assert!(deposit_amount > 0, "deposit_amount must be positive");
-        assert!(rate_per_second > 0, "rate_per_second must be positive");
-
-        // Validate sender != recipient (#35)
-        assert!(
-            sender != recipient,
-            "sender and recipient must be different"
+/// Append `record` to the withdrawal history of `stream_id`, dropping the oldest
+/// record (and setting the truncation flag) once `MAX_WITHDRAWAL_HISTORY` is reached.
+fn push_withdrawal_record(env: &Env, stream_id: u64, record: WithdrawalRecord) {
+    let storage = env.storage().persistent();
+    let key = DataKey::WithdrawalHistory(stream_id);
+    let mut history: soroban_sdk::Vec<WithdrawalRecord> =
+        storage.get(&key).unwrap_or(soroban_sdk::Vec::new(env));
+    if history.len() >= MAX_WITHDRAWAL_HISTORY {
+        history.pop_front();
+        let flag_key = DataKey::WithdrawalHistoryTruncated(stream_id);
+        if !storage.has(&flag_key) {
+            storage.set(&flag_key, &true);
+        }
+        storage.extend_ttl(
+            &flag_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
+    }
+    history.push_back(record);
+    storage.set(&key, &history);
+    storage.extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
 
-        // Validate time constraints
-        assert!(start_time < end_time, "start_time must be before end_time");
-        assert!(
-            cliff_time >= start_time && cliff_time <= end_time,
-            "cliff_time must be within [start_time, end_time]"
-        );
+/// Initial status history of a stream with no recorded transitions: the status
+/// it was created in, at `created_at`.
+fn initial_status_history(env: &Env, stream: &Stream) -> soroban_sdk::Vec<(u64, StreamStatus)> {
+    soroban_sdk::vec![env, (stream.created_at, stream.status)]
+}
 
-        // Validate deposit covers total streamable amount (#34)
-        let duration = (end_time - start_time) as i128;
-        let total_streamable = rate_per_second
-            .checked_mul(duration)
-            .expect("overflow calculating total streamable amount");
-        assert!(
-            deposit_amount >= total_streamable,
-            "deposit_amount must cover total streamable amount (rate * duration)"
-        );
+/// Append `(now, status)` to the status history of `stream`, dropping the oldest
+/// entry once `MAX_STATUS_HISTORY` is reached. Call before `stream.status` is
+/// updated: the creation entry is only written with the first transition, which
+/// keeps stream creation free of an extra storage write.
+fn push_status_record(env: &Env, stream: &Stream, status: StreamStatus) {
+    let storage = env.storage().persistent();
+    let key = DataKey::StatusHistory(stream.stream_id);
+    let mut history: soroban_sdk::Vec<(u64, StreamStatus)> = storage
+        .get(&key)
+        .unwrap_or_else(|| initial_status_history(env, stream));
+    if history.len() >= MAX_STATUS_HISTORY {
+        history.pop_front();
     }
+    history.push_back((env.ledger().timestamp(), status));
+    storage.set(&key, &history);
+    storage.extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
 
-    #[allow(clippy::too_many_arguments)]
-    fn persist_new_stream(
-        env: &Env,
-        sender: Address,
-        recipient: Address,
-        deposit_amount: i128,
-        rate_per_second: i128,
-        start_time: u64,
-        cliff_time: u64,
-        end_time: u64,
-    ) -> u64 {
-        let stream_id = get_stream_count(env);
-        set_stream_count(env, stream_id + 1);
+/// Stream ID reserved by `sender`'s creation `nonce`: the first 8 bytes of
+/// `sha256(sender XDR || nonce big-endian)`, read as a big-endian `u64`.
+fn nonce_stream_id(env: &Env, sender: &Address, nonce: u64) -> u64 {
+    let mut preimage = sender.clone().to_xdr(env);
+    preimage.extend_from_array(&nonce.to_be_bytes());
+    let digest = env.crypto().sha256(&preimage).to_array();
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(id)
+}
 
-        let stream = Stream {
-            stream_id,
-            sender: sender.clone(),
-            recipient: recipient.clone(),
+fn is_nonce_used(env: &Env, sender: &Address, nonce: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::UsedNonce(sender.clone(), nonce))
+}
+
+/// Record that `sender` consumed `nonce`; the entry shares the stream's TTL policy.
+fn mark_nonce_used(env: &Env, sender: &Address, nonce: u64) {
+    let key = DataKey::UsedNonce(sender.clone(), nonce);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+fn is_recipient_blacklisted(env: &Env, recipient: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::BlacklistedRecipient(recipient.clone()))
+}
+
+fn load_sender_whitelist(env: &Env) -> soroban_sdk::Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::SenderWhitelist)
+        .unwrap_or_else(|| soroban_sdk::Vec::new(env))
+}
+
+/// Return the `create_swap_stream` partner of `stream_id`, if any.
+fn load_linked_stream(env: &Env, stream_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LinkedStream(stream_id))
+}
+
+/// Return the agreement hash anchored to `stream_id`, if any.
+fn load_agreement_hash(env: &Env, stream_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgreementHash(stream_id))
+}
+
+/// Anchor `hash` to `stream_id`; the entry shares the stream's TTL policy.
+fn save_agreement_hash(env: &Env, stream_id: u64, hash: &BytesN<32>) {
+    let key = DataKey::AgreementHash(stream_id);
+    env.storage().persistent().set(&key, hash);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+/// Return the description of `stream_id` (empty if none was set).
+fn load_stream_description(env: &Env, stream_id: u64) -> Bytes {
+    env.storage()
+        .persistent()
+        .get(&DataKey::StreamDescription(stream_id))
+        .unwrap_or_else(|| Bytes::new(env))
+}
+
+/// Store `description` for `stream_id`, removing the entry when it is empty.
+fn save_stream_description(env: &Env, stream_id: u64, description: &Bytes) {
+    let key = DataKey::StreamDescription(stream_id);
+    if description.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, description);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+/// Link `stream_id` to `linked_id`; the entry shares the stream's TTL policy.
+fn save_linked_stream(env: &Env, stream_id: u64, linked_id: u64) {
+    let key = DataKey::LinkedStream(stream_id);
+    env.storage().persistent().set(&key, &linked_id);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+/// Remove `stream_id` from the stream-ID index stored under `key`, keeping the
+/// remaining IDs in order.
+fn remove_stream_index(env: &Env, key: &DataKey, stream_id: u64) {
+    let mut ids = load_stream_index(env, key);
+    if let Some(index) = ids.first_index_of(stream_id) {
+        ids.remove(index);
+        env.storage().persistent().set(key, &ids);
+    }
+}
+
+/// Publish a stream lifecycle event under the `("STREAM", name, stream_id)` topic.
+fn publish_stream_event<D: IntoVal<Env, Val>>(env: &Env, name: Symbol, stream_id: u64, data: D) {
+    env.events()
+        .publish((symbol_short!("STREAM"), name, stream_id), data);
+}
+
+// ---------------------------------------------------------------------------
+// Internal Helpers
+// ---------------------------------------------------------------------------
+
+impl FluxoraStream {
+    #[allow(clippy::too_many_arguments)]
+    fn validate_linear_params(
+        sender: &Address,
+        recipient: &Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        rate_denominator: u64,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> Result<(), ContractError> {
+        Self::validate_stream_shape(
+            sender,
+            recipient,
             deposit_amount,
             rate_per_second,
             start_time,
             cliff_time,
             end_time,
+        )?;
+
+        // Validate deposit covers total streamable amount (#34)
+        let total_streamable =
+            Self::streamable_amount(rate_per_second, rate_denominator, end_time - start_time)?;
+        if deposit_amount < total_streamable {
+            return Err(ContractError::UnfundedDeposit);
+        }
+
+        Ok(())
+    }
+
+    /// Tokens a rate of `rate_per_second / rate_denominator` streams over `duration`
+    /// seconds, rounded up so a deposit of this size always covers the schedule.
+    fn streamable_amount(
+        rate_per_second: i128,
+        rate_denominator: u64,
+        duration: u64,
+    ) -> Result<i128, ContractError> {
+        if rate_denominator == 0 {
+            return Err(ContractError::InvalidParams);
+        }
+        let scaled = rate_per_second
+            .checked_mul(duration as i128)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        let denominator = rate_denominator as i128;
+        Ok(scaled / denominator + i128::from(scaled % denominator != 0))
+    }
+
+    /// The rate `rate_per_second / rate_denominator`, in lowest terms, that streams
+    /// exactly `amount` over `duration` seconds.
+    fn exact_rate(amount: i128, duration: u64) -> (i128, u64) {
+        let (mut a, mut b) = (amount.unsigned_abs(), u128::from(duration));
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        (amount / a as i128, duration / a as u64)
+    }
+
+    /// `cliff_time` as stored on a new stream: `0` means "no cliff", i.e. the cliff
+    /// is at `start_time` whatever the start.
+    fn effective_cliff(start_time: u64, cliff_time: u64) -> u64 {
+        if cliff_time == 0 {
+            start_time
+        } else {
+            cliff_time
+        }
+    }
+
+    /// Checks shared by every linear schedule: positive amounts, distinct parties and
+    /// a well-ordered `start <= cliff <= end` window with `start < end`. A
+    /// `cliff_time` of 0 always passes as "no cliff".
+    fn validate_stream_shape(
+        sender: &Address,
+        recipient: &Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> Result<(), ContractError> {
+        // A self-stream only accrues the sender's own deposit back to itself and is
+        // almost certainly a caller bug (#35)
+        if sender == recipient {
+            return Err(ContractError::InvalidParams);
+        }
+
+        // Validate positive amounts (#35)
+        if deposit_amount <= 0 {
+            return Err(ContractError::InvalidParams);
+        }
+        if rate_per_second <= 0 {
+            return Err(ContractError::InvalidParams);
+        }
+
+        // Validate time constraints: a non-empty window with the cliff inside it
+        if start_time >= end_time {
+            return Err(ContractError::InvalidParams);
+        }
+        let cliff_time = Self::effective_cliff(start_time, cliff_time);
+        if cliff_time < start_time || cliff_time > end_time {
+            return Err(ContractError::InvalidParams);
+        }
+
+        Ok(())
+    }
+
+    /// Split a gross deposit into `(net_deposit, fee)` using `config.fee_bps`.
+    fn split_protocol_fee(
+        config: &Config,
+        deposit_amount: i128,
+    ) -> Result<(i128, i128), ContractError> {
+        if config.fee_bps == 0 || deposit_amount <= 0 {
+            return Ok((deposit_amount, 0));
+        }
+        let fee = deposit_amount
+            .checked_mul(config.fee_bps as i128)
+            .ok_or(ContractError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as i128;
+        Ok((deposit_amount - fee, fee))
+    }
+
+    /// Protocol fee on a withdrawal of `amount` from `stream`, using
+    /// `Config.fee_on_withdrawal_bps`. Streams funded in a token other than the
+    /// current `Config.token` (see `admin_set_token`) pay no withdrawal fee, since
+    /// accumulated fees are held in the configured token.
+    fn withdrawal_fee(env: &Env, stream: &Stream, amount: i128) -> Result<i128, ContractError> {
+        let config = get_config(env);
+        if config.fee_on_withdrawal_bps == 0 || stream.token != config.token {
+            return Ok(0);
+        }
+        Ok(amount
+            .checked_mul(config.fee_on_withdrawal_bps as i128)
+            .ok_or(ContractError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as i128)
+    }
+
+    /// Add `fee` to the protocol fee balance (no-op for a zero fee).
+    fn accrue_protocol_fees(env: &Env, fee: i128) -> Result<(), ContractError> {
+        if fee == 0 {
+            return Ok(());
+        }
+        let total = get_accumulated_fees(env)
+            .checked_add(fee)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        set_accumulated_fees(env, total);
+        Ok(())
+    }
+
+    /// Build an unsaved `Active` linear stream with default options. The ID is
+    /// assigned by `persist_new_stream`.
+    #[allow(clippy::too_many_arguments)]
+    fn new_stream(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> Stream {
+        Stream {
+            stream_id: 0,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            rate_denominator: 1,
+            start_time,
+            cliff_time: Self::effective_cliff(start_time, cliff_time),
+            end_time,
             withdrawn_amount: 0,
+            withdraw_count: 0,
+            last_withdrawal_time: None,
             status: StreamStatus::Active,
             cancelled_at: None,
+            completed_at: None,
+            pending_expiry: None,
+            pause_mode: PauseMode::WithdrawalBlock,
+            paused_at: None,
+            paused_by: PauserKind::NotPaused,
+            total_paused_duration: 0,
+            approved_operator: None,
+            intervals: 0,
+            cliff_unlock_amount: 0,
+            cancellable: true,
+            memo: Bytes::new(env),
+            cancel_after: 0,
+            created_at: env.ledger().timestamp(),
+            strict_deposit: true,
+            cancel_penalty_bps: 0,
+            recipient_can_pause: false,
+            curve_type: CurveType::Linear,
+            auto_withdraw: false,
+            auto_withdraw_interval: 0,
+            // Placeholder; the create path sets `Config.token` once it has loaded the config.
+            token: env.current_contract_address(),
+        }
+    }
+
+    /// Validate the schedule of an unsaved stream (linear or stepped).
+    fn validate_new_stream(stream: &Stream) -> Result<(), ContractError> {
+        if stream.memo.len() > MAX_MEMO_LEN || stream.cancel_penalty_bps > BPS_DENOMINATOR {
+            return Err(ContractError::InvalidParams);
+        }
+        if let CurveType::Exponential(exponent_bps) = stream.curve_type {
+            if exponent_bps == 0
+                || exponent_bps > MAX_CURVE_EXPONENT_BPS
+                || stream.intervals > 0
+                || stream.cliff_unlock_amount != 0
+            {
+                return Err(ContractError::InvalidParams);
+            }
+        }
+        if stream.intervals > 0 {
+            return Self::validate_step_params(
+                &stream.sender,
+                &stream.recipient,
+                stream.deposit_amount,
+                stream.intervals,
+                stream.start_time,
+                stream.end_time,
+            );
+        }
+        if stream.cliff_unlock_amount != 0 {
+            return Self::validate_cliff_unlock_params(stream);
+        }
+        Self::validate_linear_params(
+            &stream.sender,
+            &stream.recipient,
+            stream.deposit_amount,
+            stream.rate_per_second,
+            stream.rate_denominator,
+            stream.start_time,
+            stream.cliff_time,
+            stream.end_time,
+        )
+    }
+
+    /// A cliff-unlock stream must fund the lump sum plus the linear part that runs
+    /// from the cliff: `deposit >= cliff_unlock_amount + rate × (end - cliff)`.
+    fn validate_cliff_unlock_params(stream: &Stream) -> Result<(), ContractError> {
+        Self::validate_stream_shape(
+            &stream.sender,
+            &stream.recipient,
+            stream.deposit_amount,
+            stream.rate_per_second,
+            stream.start_time,
+            stream.cliff_time,
+            stream.end_time,
+        )?;
+        if stream.cliff_unlock_amount < 0 || stream.cliff_unlock_amount > stream.deposit_amount {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let required = Self::streamable_amount(
+            stream.rate_per_second,
+            stream.rate_denominator,
+            stream.end_time - stream.cliff_time,
+        )?
+        .checked_add(stream.cliff_unlock_amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+        if stream.deposit_amount < required {
+            return Err(ContractError::UnfundedDeposit);
+        }
+        Ok(())
+    }
+
+    fn validate_step_params(
+        sender: &Address,
+        recipient: &Address,
+        deposit_amount: i128,
+        intervals: u32,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<(), ContractError> {
+        if deposit_amount <= 0 || sender == recipient || start_time >= end_time {
+            return Err(ContractError::InvalidParams);
+        }
+        // Every step needs at least one second of its own.
+        if intervals == 0 || intervals as u64 > end_time - start_time {
+            return Err(ContractError::InvalidParams);
+        }
+        Ok(())
+    }
+
+    /// Shared body of the single-stream `create_*` entry points: authorize, take
+    /// the protocol fee, validate, pull the deposit and persist the stream.
+    ///
+    /// `stream.deposit_amount` is the gross amount pulled from the sender; the
+    /// stream is stored with the net amount.
+    fn create_stream_internal(env: &Env, stream: Stream) -> Result<u64, ContractError> {
+        stream.sender.require_auth();
+        Self::create_stream_as(env, stream, None)
+    }
+
+    /// `create_stream_internal` under a caller-chosen `stream_id` (`None` takes
+    /// the next sequential ID). Authorization is the caller's responsibility.
+    fn create_stream_as(
+        env: &Env,
+        mut stream: Stream,
+        stream_id: Option<u64>,
+    ) -> Result<u64, ContractError> {
+        Self::require_protocol_active(env)?;
+
+        let config = get_config(env);
+        Self::require_whitelisted_sender(env, &config, &stream.sender)?;
+        Self::require_recipient_allowed(env, &stream.recipient)?;
+        Self::require_global_capacity(env, &config, 1)?;
+        Self::require_sender_capacity(env, &config, &stream.sender, 1)?;
+        Self::require_minimums(
+            &config,
+            stream.deposit_amount,
+            stream.rate_per_second,
+            stream.rate_denominator,
+            stream.intervals > 0,
+        )?;
+        Self::require_min_duration(&config, stream.start_time, stream.end_time)?;
+        let gross_deposit = stream.deposit_amount;
+        let (net_deposit, fee) = Self::split_protocol_fee(&config, gross_deposit)?;
+        stream.deposit_amount = net_deposit;
+        stream.token = config.token.clone();
+        Self::validate_new_stream(&stream)?;
+        Self::require_exact_deposit(&config, &stream)?;
+
+        // Transfer tokens from sender to this contract (#36)
+        // If transfer fails (insufficient balance/allowance), this will panic
+        // and no state will be persisted (atomic transaction)
+        let token_client = token::Client::new(env, &config.token);
+        token_client.transfer(
+            &stream.sender,
+            &env.current_contract_address(),
+            &gross_deposit,
+        );
+        Self::accrue_protocol_fees(env, fee)?;
+
+        // Only allocate stream id and persist state AFTER successful transfer
+        Self::persist_new_stream(env, stream, stream_id)
+    }
+
+    /// Assign `stream_id` (or, if `None`, the next sequential ID) to `stream`,
+    /// save it, index it under its sender and recipient, add its deposit to
+    /// `TotalLocked` and `TotalDeposited`, count it in the protocol stats and
+    /// publish `created`.
+    fn persist_new_stream(
+        env: &Env,
+        mut stream: Stream,
+        stream_id: Option<u64>,
+    ) -> Result<u64, ContractError> {
+        adjust_protocol_total(env, &DataKey::TotalLocked, stream.deposit_amount)?;
+        adjust_protocol_total(env, &DataKey::TotalDeposited, stream.deposit_amount)?;
+        let stream_id = match stream_id {
+            Some(stream_id) => stream_id,
+            None => {
+                // Skip any ID a nonce-derived stream already occupies.
+                let mut stream_id = get_stream_count(env);
+                while env.storage().persistent().has(&DataKey::Stream(stream_id)) {
+                    stream_id += 1;
+                }
+                set_stream_count(env, stream_id + 1);
+                stream_id
+            }
         };
+        increment_streams_created(env);
+        stream.stream_id = stream_id;
+        if stream.status == StreamStatus::Active && stream.start_time > env.ledger().timestamp() {
+            stream.status = StreamStatus::Scheduled;
+        }
 
         save_stream(env, &stream);
+        move_status_count(env, None, stream.status);
+        push_stream_index(
+            env,
+            &DataKey::SenderStreams(stream.sender.clone()),
+            stream_id,
+        );
+        push_stream_index(
+            env,
+            &DataKey::RecipientStreams(stream.recipient.clone()),
+            stream_id,
+        );
+
+        publish_stream_event(
+            env,
+            symbol_short!("created"),
+            stream_id,
+            StreamCreated {
+                stream_id,
+                sender: stream.sender,
+                recipient: stream.recipient,
+                deposit_amount: stream.deposit_amount,
+                rate_per_second: stream.rate_per_second,
+                start_time: stream.start_time,
+                cliff_time: stream.cliff_time,
+                end_time: stream.end_time,
+            },
+        );
+
+        Ok(stream_id)
+    }
+
+    /// Map a ledger `timestamp` onto the stream's own schedule clock.
+    ///
+    /// Identity for `WithdrawalBlock` streams. For `AccrualFreeze` streams the
+    /// clock stands still during the current pause and every completed pause is
+    /// subtracted, which pushes the effective cliff and end back by the time spent
+    /// frozen.
+    fn schedule_time(stream: &Stream, timestamp: u64) -> u64 {
+        if stream.pause_mode != PauseMode::AccrualFreeze {
+            return timestamp;
+        }
+        let at = match stream.paused_at {
+            Some(paused_at) => timestamp.min(paused_at),
+            None => timestamp,
+        };
+        at.saturating_sub(stream.total_paused_duration)
+    }
+
+    /// Accrued amount of `stream` evaluated at `timestamp`.
+    ///
+    /// Completed streams are fully accrued from their completion time on (and
+    /// follow their schedule before it); cancelled streams are frozen at their
+    /// cancellation time and expired ones at what `expire_stream` paid out.
+    /// Streams that were never accepted accrue nothing.
+    fn accrued_at(stream: &Stream, timestamp: u64) -> Result<i128, ContractError> {
+        match stream.status {
+            StreamStatus::Completed => {
+                if stream.completed_at.is_none_or(|at| timestamp >= at) {
+                    return Ok(stream.deposit_amount);
+                }
+                let accrued =
+                    Self::scheduled_amount(stream, Self::schedule_time(stream, timestamp));
+                return Ok(accrued.min(stream.deposit_amount));
+            }
+            StreamStatus::Expired => return Ok(stream.withdrawn_amount),
+            _ => {}
+        }
+        if stream.pending_expiry.is_some() {
+            return Ok(0);
+        }
+
+        if stream.status == StreamStatus::Cancelled {
+            let cancelled_at = stream.cancelled_at.ok_or(ContractError::InvalidState)?;
+            let accrued = Self::scheduled_amount(
+                stream,
+                Self::schedule_time(stream, timestamp.min(cancelled_at)),
+            );
+            if timestamp < cancelled_at {
+                return Ok(accrued);
+            }
+            let penalty = Self::cancel_penalty(stream, stream.deposit_amount - accrued)?;
+            return Ok(accrued + penalty);
+        }
+
+        Ok(Self::scheduled_amount(
+            stream,
+            Self::schedule_time(stream, timestamp),
+        ))
+    }
+
+    /// Early-cancel penalty owed to the recipient when `unstreamed` tokens are
+    /// left at cancellation: `unstreamed × cancel_penalty_bps / 10_000`. Never
+    /// charged on an offer that was not accepted.
+    fn cancel_penalty(stream: &Stream, unstreamed: i128) -> Result<i128, ContractError> {
+        if stream.cancel_penalty_bps == 0 || stream.pending_expiry.is_some() {
+            return Ok(0);
+        }
+        let scaled = unstreamed
+            .checked_mul(i128::from(stream.cancel_penalty_bps))
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        Ok(scaled / i128::from(BPS_DENOMINATOR))
+    }
+
+    /// Ledger time from which `expire_stream` may sweep `stream`: its end time,
+    /// pushed back by any `AccrualFreeze` pauses, plus `Config.expiry_grace_period`.
+    fn expiry_time(env: &Env, stream: &Stream) -> u64 {
+        let mut end = stream.end_time;
+        if stream.pause_mode == PauseMode::AccrualFreeze {
+            end = end.saturating_add(stream.total_paused_duration);
+        }
+        end.saturating_add(get_config(env).expiry_grace_period)
+    }
+
+    /// Ledger time from which `recover_orphaned_stream` may sweep `stream`: its
+    /// end time (pushed back as in `expiry_time`) plus `recovery_grace_period`.
+    fn recovery_time(stream: &Stream, config: &Config) -> u64 {
+        let mut end = stream.end_time;
+        if stream.pause_mode == PauseMode::AccrualFreeze {
+            end = end.saturating_add(stream.total_paused_duration);
+        }
+        end.saturating_add(config.recovery_grace_period)
+    }
+
+    /// Amount unlocked by the stream's schedule at schedule time `at`, ignoring status.
+    fn scheduled_amount(stream: &Stream, at: u64) -> i128 {
+        if stream.intervals > 0 {
+            return accrual::calculate_stepped_amount(
+                stream.start_time,
+                stream.end_time,
+                stream.intervals,
+                stream.deposit_amount,
+                at,
+            );
+        }
+        if let CurveType::Exponential(exponent_bps) = stream.curve_type {
+            return accrual::calculate_curved_amount(
+                stream.start_time,
+                stream.cliff_time,
+                stream.end_time,
+                exponent_bps,
+                stream.deposit_amount,
+                at,
+            );
+        }
+        if stream.cliff_unlock_amount > 0 {
+            return accrual::calculate_cliff_unlock_accrued(
+                stream.cliff_time,
+                stream.end_time,
+                stream.rate_per_second,
+                stream.rate_denominator,
+                stream.cliff_unlock_amount,
+                stream.deposit_amount,
+                at,
+            );
+        }
+        accrual::calculate_accrued_amount(
+            stream.start_time,
+            stream.cliff_time,
+            stream.end_time,
+            stream.rate_per_second,
+            stream.rate_denominator,
+            stream.deposit_amount,
+            at,
+        )
+    }
+
+    /// Amount `withdraw` would transfer for `stream` at the current ledger time.
+    fn withdrawable_now(env: &Env, stream: &Stream) -> Result<i128, ContractError> {
+        match stream.status {
+            StreamStatus::Paused
+            | StreamStatus::Completed
+            | StreamStatus::Pending
+            | StreamStatus::Expired => Ok(0),
+            StreamStatus::Active | StreamStatus::Cancelled | StreamStatus::Scheduled => {
+                let accrued = Self::accrued_at(stream, env.ledger().timestamp())?;
+                Ok(accrued - stream.withdrawn_amount)
+            }
+        }
+    }
+
+    /// Part of the deposit not yet accrued at the current ledger time; 0 once the
+    /// stream is terminal.
+    fn unstreamed_now(env: &Env, stream: &Stream) -> Result<i128, ContractError> {
+        match stream.status {
+            StreamStatus::Completed | StreamStatus::Cancelled | StreamStatus::Expired => Ok(0),
+            StreamStatus::Active
+            | StreamStatus::Paused
+            | StreamStatus::Pending
+            | StreamStatus::Scheduled => {
+                let accrued = Self::accrued_at(stream, env.ledger().timestamp())?;
+                Ok((stream.deposit_amount - accrued).max(0))
+            }
+        }
+    }
+
+    /// Amount `cancel_stream` would refund to the sender at the current ledger time:
+    /// the unstreamed deposit less the stream's cancel penalty.
+    fn refundable_now(env: &Env, stream: &Stream) -> Result<i128, ContractError> {
+        let unstreamed = Self::unstreamed_now(env, stream)?;
+        Ok(unstreamed - Self::cancel_penalty(stream, unstreamed)?)
+    }
+
+    /// `accrued` as basis points of `deposit`, clamped to `[0, 10_000]`.
+    fn completion_bps(accrued: i128, deposit: i128) -> u32 {
+        if deposit <= 0 {
+            return 0;
+        }
+        let denominator = BPS_DENOMINATOR as i128;
+        let bps = match accrued.checked_mul(denominator) {
+            Some(scaled) => scaled / deposit,
+            // Only reachable for deposits above i128::MAX / 10_000, so the divisor is non-zero.
+            None => accrued / (deposit / denominator),
+        };
+        bps.clamp(0, denominator) as u32
+    }
+
+    /// Completion ratio of `stream` given its `accrued` amount: `10_000` for
+    /// terminal streams, otherwise `completion_bps(accrued, deposit_amount)`.
+    fn stream_completion_bps(stream: &Stream, accrued: i128) -> u32 {
+        match stream.status {
+            StreamStatus::Completed | StreamStatus::Cancelled | StreamStatus::Expired => {
+                BPS_DENOMINATOR
+            }
+            _ => Self::completion_bps(accrued, stream.deposit_amount),
+        }
+    }
+
+    /// Validate a withdrawal against `stream`, record it and persist the stream.
+    ///
+    /// Returns the withdrawable amount (possibly 0, in which case nothing is
+    /// written). The caller is responsible for authorization, the token transfer
+    /// and publishing the event.
+    fn apply_withdrawal(env: &Env, stream: &mut Stream) -> Result<i128, ContractError> {
+        Self::activate_if_started(env, stream);
+        match stream.status {
+            StreamStatus::Completed => return Err(ContractError::StreamCompleted),
+            StreamStatus::Paused => return Err(ContractError::StreamPaused),
+            StreamStatus::Pending => return Err(ContractError::StreamPending),
+            StreamStatus::Scheduled => return Err(ContractError::StreamScheduled),
+            StreamStatus::Expired => return Err(ContractError::StreamExpired),
+            StreamStatus::Active | StreamStatus::Cancelled => {}
+        }
+
+        let accrued = Self::accrued_at(stream, env.ledger().timestamp())?;
+        let withdrawable = accrued - stream.withdrawn_amount;
+        if withdrawable == 0 {
+            return Ok(0);
+        }
+        let excess = Self::excess_deposit(env, stream, accrued);
+        Self::require_cooldown_elapsed(env, stream)?;
+        // Counting the excess makes a refunding withdrawal drain the deposit, so it
+        // is exempt like any final withdrawal.
+        Self::require_above_dust(env, stream, withdrawable + excess)?;
+
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        stream.deposit_amount -= excess;
+        stream.withdrawn_amount += withdrawable;
+        stream.withdraw_count = stream.withdraw_count.saturating_add(1);
+        stream.last_withdrawal_time = Some(env.ledger().timestamp());
+        if stream.withdrawn_amount == stream.deposit_amount {
+            move_status_count(env, Some(stream.status), StreamStatus::Completed);
+            push_status_record(env, stream, StreamStatus::Completed);
+            stream.status = StreamStatus::Completed;
+            stream.completed_at = Some(env.ledger().timestamp());
+            stream.approved_operator = None;
+        }
+        save_stream(env, stream);
+        adjust_protocol_total(env, &DataKey::TotalLocked, -(withdrawable + excess))?;
+        adjust_protocol_total(env, &DataKey::TotalStreamed, withdrawable)?;
+
+        if excess > 0 {
+            let token_client = token::Client::new(env, &stream.token);
+            token_client.transfer(&env.current_contract_address(), &stream.sender, &excess);
+            publish_stream_event(
+                env,
+                symbol_short!("excess"),
+                stream.stream_id,
+                StreamExcessRefunded {
+                    stream_id: stream.stream_id,
+                    sender_refund: excess,
+                },
+            );
+        }
+
+        Ok(withdrawable)
+    }
+
+    /// Deposit left over once an over-funded `Active` stream's schedule has fully
+    /// elapsed, given its `accrued` amount; 0 unless the stream opted out of
+    /// `strict_deposit` or `Config.allow_excess_deposit` is set.
+    fn excess_deposit(env: &Env, stream: &Stream, accrued: i128) -> i128 {
+        if stream.status != StreamStatus::Active
+            || accrued >= stream.deposit_amount
+            || Self::schedule_time(stream, env.ledger().timestamp()) < stream.end_time
+            || (stream.strict_deposit && !get_config(env).allow_excess_deposit)
+        {
+            return 0;
+        }
+        stream.deposit_amount - accrued
+    }
+
+    /// Enforce `Config.withdrawal_cooldown` since the stream's last withdrawal.
+    ///
+    /// The first withdrawal is never limited, and neither are cancelled streams:
+    /// their accrual is frozen, so there is nothing left to drip out.
+    fn require_cooldown_elapsed(env: &Env, stream: &Stream) -> Result<(), ContractError> {
+        let Some(last) = stream.last_withdrawal_time else {
+            return Ok(());
+        };
+        if stream.status == StreamStatus::Cancelled {
+            return Ok(());
+        }
+        let cooldown = get_config(env).withdrawal_cooldown;
+        if env.ledger().timestamp().saturating_sub(last) < cooldown {
+            return Err(ContractError::WithdrawalCooldown);
+        }
+        Ok(())
+    }
+
+    /// Enforce `Config.min_withdraw_amount` on a withdrawal of `amount`.
+    ///
+    /// The stream's last possible withdrawal is exempt so a remainder below the
+    /// threshold never gets stuck: one that drains the deposit, or any withdrawal
+    /// from a cancelled stream, whose accrual is frozen.
+    fn require_above_dust(env: &Env, stream: &Stream, amount: i128) -> Result<(), ContractError> {
+        if stream.status == StreamStatus::Cancelled
+            || stream.withdrawn_amount + amount == stream.deposit_amount
+        {
+            return Ok(());
+        }
+        if amount < get_config(env).min_withdraw_amount {
+            return Err(ContractError::BelowDustThreshold);
+        }
+        Ok(())
+    }
+
+    /// Append a paying withdrawal of `amount` to `to` to the stream's history.
+    fn record_withdrawal(env: &Env, stream: &Stream, to: &Address, amount: i128, fee: i128) {
+        push_withdrawal_record(
+            env,
+            stream.stream_id,
+            WithdrawalRecord {
+                timestamp: env.ledger().timestamp(),
+                amount,
+                fee,
+                recipient: to.clone(),
+                withdraw_count: stream.withdraw_count,
+            },
+        );
+    }
+
+    /// Publish the events for a withdrawal recorded by `apply_withdrawal`:
+    /// `withdrawn`, plus `completed` when it drained the stream.
+    fn publish_withdrawal(env: &Env, stream: &Stream, amount: i128, fee: i128) {
+        publish_stream_event(
+            env,
+            symbol_short!("withdrawn"),
+            stream.stream_id,
+            StreamWithdrawn {
+                stream_id: stream.stream_id,
+                amount,
+                fee,
+                withdrawn_total: stream.withdrawn_amount,
+            },
+        );
+        if stream.status == StreamStatus::Completed {
+            publish_stream_event(
+                env,
+                symbol_short!("completed"),
+                stream.stream_id,
+                StreamCompleted {
+                    stream_id: stream.stream_id,
+                },
+            );
+        }
+    }
+
+    /// Record a withdrawal on `stream`, transfer it to `to` and publish its events.
+    ///
+    /// The caller is responsible for authorization. Returns the amount transferred
+    /// (0 when nothing is withdrawable, in which case nothing is written).
+    fn withdraw_and_transfer(
+        env: &Env,
+        stream: &mut Stream,
+        to: &Address,
+    ) -> Result<i128, ContractError> {
+        let withdrawable = Self::apply_withdrawal(env, stream)?;
+        if withdrawable == 0 {
+            return Ok(0);
+        }
+        let fee = Self::withdrawal_fee(env, stream, withdrawable)?;
+        Self::accrue_protocol_fees(env, fee)?;
+        let payout = withdrawable - fee;
+
+        if payout > 0 {
+            let token_client = token::Client::new(env, &stream.token);
+            token_client.transfer(&env.current_contract_address(), to, &payout);
+        }
+
+        Self::record_withdrawal(env, stream, to, payout, fee);
+        Self::publish_withdrawal(env, stream, payout, fee);
+        Ok(payout)
+    }
+
+    fn load_pending_stream(env: &Env, stream_id: u64) -> Result<Stream, ContractError> {
+        let stream = load_stream(env, stream_id)?;
+        if stream.status != StreamStatus::Pending {
+            return Err(ContractError::InvalidState);
+        }
+        Ok(stream)
+    }
+
+    /// Cancel a never-accepted stream and return its whole deposit to the sender.
+    fn refund_pending(
+        env: &Env,
+        stream: &mut Stream,
+        event_name: Symbol,
+    ) -> Result<(), ContractError> {
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        move_status_count(env, Some(stream.status), StreamStatus::Cancelled);
+        push_status_record(env, stream, StreamStatus::Cancelled);
+        stream.status = StreamStatus::Cancelled;
+        stream.cancelled_at = Some(env.ledger().timestamp());
+        stream.approved_operator = None;
+        save_stream(env, stream);
+        adjust_protocol_total(env, &DataKey::TotalLocked, -stream.deposit_amount)?;
+
+        let token_client = token::Client::new(env, &stream.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &stream.sender,
+            &stream.deposit_amount,
+        );
+
+        publish_stream_event(
+            env,
+            event_name,
+            stream.stream_id,
+            StreamPendingRefunded {
+                stream_id: stream.stream_id,
+                sender_refund: stream.deposit_amount,
+            },
+        );
+        Ok(())
+    }
+
+    /// Pull `amount` from the sender into `stream`, move its end time to
+    /// `new_end_time`, persist it and publish `event_name` with a `StreamToppedUp`
+    /// payload. Authorization and status checks are the caller's responsibility.
+    fn apply_extension(
+        env: &Env,
+        stream: &mut Stream,
+        new_end_time: u64,
+        amount: i128,
+        event_name: Symbol,
+    ) -> Result<(), ContractError> {
+        stream.deposit_amount = stream
+            .deposit_amount
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        stream.end_time = new_end_time;
+
+        let token_client = token::Client::new(env, &stream.token);
+        token_client.transfer(&stream.sender, &env.current_contract_address(), &amount);
+
+        save_stream(env, stream);
+        adjust_protocol_total(env, &DataKey::TotalLocked, amount)?;
+        adjust_protocol_total(env, &DataKey::TotalDeposited, amount)?;
+
+        publish_stream_event(
+            env,
+            event_name,
+            stream.stream_id,
+            StreamToppedUp {
+                stream_id: stream.stream_id,
+                amount,
+                deposit_amount: stream.deposit_amount,
+                end_time: stream.end_time,
+            },
+        );
+        Ok(())
+    }
+
+    /// Move a `Scheduled` stream whose start time has arrived to `Active`, persist
+    /// it and publish `activated`. Returns whether the stream was activated.
+    fn activate_if_started(env: &Env, stream: &mut Stream) -> bool {
+        let now = env.ledger().timestamp();
+        if stream.status != StreamStatus::Scheduled || now < stream.start_time {
+            return false;
+        }
+
+        push_status_record(env, stream, StreamStatus::Active);
+        stream.status = StreamStatus::Active;
+        save_stream(env, stream);
+
+        publish_stream_event(
+            env,
+            symbol_short!("activated"),
+            stream.stream_id,
+            StreamActivated {
+                stream_id: stream.stream_id,
+                activated_at: now,
+            },
+        );
+        true
+    }
+
+    /// Move an `Active` stream to `Paused` on behalf of `by`, persist it and
+    /// publish `paused`. Authorization is the caller's responsibility.
+    fn apply_pause(env: &Env, stream: &mut Stream, by: PauserKind) -> Result<(), ContractError> {
+        Self::activate_if_started(env, stream);
+        Self::require_status(stream.status, StreamStatus::Active)?;
+
+        move_status_count(env, Some(stream.status), StreamStatus::Paused);
+        push_status_record(env, stream, StreamStatus::Paused);
+        stream.status = StreamStatus::Paused;
+        stream.paused_at = Some(env.ledger().timestamp());
+        stream.paused_by = by;
+        save_stream(env, stream);
+
+        publish_stream_event(
+            env,
+            symbol_short!("paused"),
+            stream.stream_id,
+            StreamPaused {
+                stream_id: stream.stream_id,
+                paused_at: env.ledger().timestamp(),
+                admin: by == PauserKind::Admin,
+            },
+        );
+        Ok(())
+    }
+
+    /// Move a `Paused` stream back to `Active` on behalf of `by`, persist it and
+    /// publish `resumed`. Only the admin can lift an admin pause.
+    /// Authorization is the caller's responsibility.
+    fn apply_resume(env: &Env, stream: &mut Stream, by: PauserKind) -> Result<(), ContractError> {
+        Self::require_status(stream.status, StreamStatus::Paused)?;
+        if by != PauserKind::Admin && stream.paused_by == PauserKind::Admin {
+            return Err(ContractError::Unauthorized);
+        }
+
+        move_status_count(env, Some(stream.status), StreamStatus::Active);
+        push_status_record(env, stream, StreamStatus::Active);
+        stream.status = StreamStatus::Active;
+        stream.paused_by = PauserKind::NotPaused;
+        if let Some(paused_at) = stream.paused_at.take() {
+            stream.total_paused_duration = stream
+                .total_paused_duration
+                .checked_add(env.ledger().timestamp() - paused_at)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+        save_stream(env, stream);
+
+        publish_stream_event(
+            env,
+            symbol_short!("resumed"),
+            stream.stream_id,
+            StreamResumed {
+                stream_id: stream.stream_id,
+                resumed_at: env.ledger().timestamp(),
+                admin: by == PauserKind::Admin,
+            },
+        );
+        Ok(())
+    }
+
+    /// Cancel an `Active`, `Paused` or `Scheduled` stream: freeze accrual, refund the unstreamed
+    /// amount to the sender and publish `cancelled`.
+    /// Authorization is the caller's responsibility.
+    ///
+    /// Returns `(sender_refund, recipient_accrued)`.
+    fn apply_cancel(env: &Env, stream: &mut Stream) -> Result<(i128, i128), ContractError> {
+        let (unstreamed, accrued) = Self::record_cancel(env, stream)?;
+
+        if unstreamed > 0 {
+            let token_client = token::Client::new(env, &stream.token);
+            token_client.transfer(&env.current_contract_address(), &stream.sender, &unstreamed);
+        }
+
+        Self::publish_cancel(env, stream, unstreamed, accrued);
+        Self::cancel_linked(env, stream)?;
+        Ok((unstreamed, accrued))
+    }
+
+    /// Cancel the `create_swap_stream` partner of `stream`, if it is still live
+    /// and cancellable. The partner's own link points back at `stream`, which is
+    /// already cancelled, so the cascade stops there.
+    fn cancel_linked(env: &Env, stream: &Stream) -> Result<(), ContractError> {
+        let Some(linked_id) = load_linked_stream(env, stream.stream_id) else {
+            return Ok(());
+        };
+        let mut linked = load_stream(env, linked_id)?;
+        if Self::require_cancellable_status(linked.status).is_err() || !linked.cancellable {
+            return Ok(());
+        }
+        Self::apply_cancel(env, &mut linked)?;
+        Ok(())
+    }
+
+    /// State half of `apply_cancel`: mark the stream `Cancelled` and release the
+    /// unstreamed amount from `TotalLocked`, without transferring the refund.
+    /// Any `cancel_penalty_bps` share of it stays locked for the recipient.
+    ///
+    /// Returns `(sender_refund, recipient_accrued)`.
+    fn record_cancel(env: &Env, stream: &mut Stream) -> Result<(i128, i128), ContractError> {
+        Self::require_cancellable_status(stream.status)?;
+        if !stream.cancellable {
+            return Err(ContractError::StreamNotCancellable);
+        }
+
+        let now = env.ledger().timestamp();
+        let streamed = Self::accrued_at(stream, now)?;
+        let accrued = streamed + Self::cancel_penalty(stream, stream.deposit_amount - streamed)?;
+        let unstreamed = stream.deposit_amount - accrued;
+
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        move_status_count(env, Some(stream.status), StreamStatus::Cancelled);
+        push_status_record(env, stream, StreamStatus::Cancelled);
+        stream.status = StreamStatus::Cancelled;
+        stream.cancelled_at = Some(now);
+        stream.approved_operator = None;
+        save_stream(env, stream);
+        adjust_protocol_total(env, &DataKey::TotalLocked, -unstreamed)?;
+        Ok((unstreamed, accrued))
+    }
+
+    /// Publish the `cancelled` event for a cancellation recorded by `record_cancel`.
+    fn publish_cancel(env: &Env, stream: &Stream, sender_refund: i128, recipient_accrued: i128) {
+        publish_stream_event(
+            env,
+            symbol_short!("cancelled"),
+            stream.stream_id,
+            StreamCancelled {
+                stream_id: stream.stream_id,
+                sender_refund,
+                recipient_accrued,
+            },
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Contract Implementation
+// ---------------------------------------------------------------------------
+
+#[contract]
+pub struct FluxoraStream;
+
+#[contractimpl]
+impl FluxoraStream {
+    /// Initialise the contract with the streaming token and admin address.
+    ///
+    /// This function must be called exactly once before any other contract operations.
+    /// It persists the token address (used for all stream transfers) and admin address
+    /// (authorized for administrative operations) in instance storage.
+    ///
+    /// # Parameters
+    /// - `token`: Address of the token contract used for all payment streams
+    /// - `admin`: Address authorized to perform administrative operations (pause, cancel, etc.)
+    ///
+    /// # Storage
+    /// - Stores `Config { token, admin, .. }` in instance storage under `DataKey::Config`,
+    ///   with no pending admin, recipient cancellation allowed and a zero protocol fee
+    /// - Initializes `NextStreamId` counter to 0 for stream ID generation
+    /// - Extends TTL to prevent premature expiration (17280 ledgers threshold, 120960 max)
+    ///
+    /// # Errors
+    /// - `AlreadyInitialized` if called more than once
+    ///
+    /// # Security
+    /// - Re-initialization is prevented to ensure immutable token and admin configuration
+    /// - No authorization required for initial setup (deployer calls this once)
+    pub fn init(env: Env, token: Address, admin: Address) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Config) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        let config = Config {
+            token,
+            admin,
+            pending_admin: None,
+            recipient_cancel_allowed: true,
+            fee_bps: 0,
+            min_deposit: 0,
+            min_rate: 0,
+            min_duration: 0,
+            min_ttl_ledgers: 0,
+            withdrawal_cooldown: 0,
+            min_withdraw_amount: 0,
+            expiry_grace_period: DEFAULT_EXPIRY_GRACE_PERIOD,
+            recovery_address: None,
+            recovery_grace_period: DEFAULT_RECOVERY_GRACE_PERIOD,
+            allow_excess_deposit: false,
+            strict_deposit_mode: false,
+            fee_on_withdrawal_bps: 0,
+            max_streams_per_sender: 0,
+            whitelist_enabled: false,
+            global_stream_cap: u64::MAX,
+            protocol_paused: false,
+        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::NextStreamId, &0u64);
+
+        // Ensure instance storage (Config / NextStreamId) doesn't expire quickly
+        bump_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Create a new payment stream with specified parameters.
+    ///
+    /// Establishes a new token stream from sender to recipient with defined rate and duration.
+    /// Transfers the deposit amount from sender to the contract immediately. Returns a unique
+    /// stream ID that can be used to interact with the stream.
+    ///
+    /// # Parameters
+    /// - `sender`: Address funding the stream (must authorize the transaction)
+    /// - `recipient`: Address receiving the streamed tokens
+    /// - `deposit_amount`: Total tokens to deposit (must be > 0)
+    /// - `rate_per_second`: Streaming rate in tokens per second (must be > 0)
+    /// - `start_time`: When streaming begins (ledger timestamp)
+    /// - `cliff_time`: When tokens first become available (vesting cliff, must be in [start_time, end_time])
+    /// - `end_time`: When streaming completes (must be > start_time)
+    ///
+    /// # Returns
+    /// - `u64`: Unique stream identifier for the newly created stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address
+    ///
+    /// # Validation
+    /// The function validates all parameters before creating the stream:
+    /// - `deposit_amount > 0` and `rate_per_second > 0`
+    /// - `sender != recipient` (cannot stream to yourself)
+    /// - `start_time < end_time` (valid time range)
+    /// - `cliff_time` in `[start_time, end_time]` (cliff within stream duration); `0`
+    ///   means no cliff and is stored as `start_time`
+    /// - `deposit_amount >= rate_per_second × (end_time - start_time)` (sufficient deposit)
+    /// - `deposit_amount >= Config.min_deposit` and `rate_per_second >= Config.min_rate`
+    /// - `end_time - start_time >= Config.min_duration`
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `deposit_amount` or `rate_per_second` is not positive
+    /// - `InvalidParams` if `deposit_amount` or `rate_per_second` is below the configured minimum
+    /// - `InvalidParams` if `end_time - start_time` is shorter than `Config.min_duration`
+    /// - `InvalidParams` if `sender` and `recipient` are the same address
+    /// - `InvalidParams` if `start_time >= end_time` or `cliff_time` is not in `[start_time, end_time]`
+    /// - `UnfundedDeposit` if `deposit_amount < rate_per_second × (end_time - start_time)`
+    /// - `ExcessDeposit` if `Config.strict_deposit_mode` is on and `deposit_amount`
+    ///   is above `rate_per_second × (end_time - start_time)`
+    /// - `ArithmeticOverflow` if `rate_per_second × (end_time - start_time)` overflows
+    /// - `StreamLimitExceeded` if `sender` already has `max_streams_per_sender` streams
+    ///
+    /// # Panics
+    /// - If token transfer fails (insufficient balance or allowance)
+    ///
+    /// # State Changes
+    /// - Transfers `deposit_amount` tokens from sender to contract
+    /// - Creates new stream with status `Active`
+    /// - Increments global stream counter
+    /// - Stores stream data in persistent storage with extended TTL
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "created", stream_id)` with a `StreamCreated` payload
+    ///
+    /// # Usage Notes
+    /// - Transaction is atomic: if token transfer fails, no stream is created
+    /// - Stream IDs are sequential starting from 0
+    /// - Cliff time enables vesting schedules (no withdrawals before cliff)
+    /// - Setting `cliff_time = start_time` means no cliff (immediate vesting)
+    /// - Deposit can exceed minimum required (excess remains in contract)
+    /// - Sender must have sufficient token balance and approve contract
+    /// ## Stream Limits Policy
+    /// No hard upper bounds are enforced on `deposit_amount` or stream duration.
+    /// Rationale:
+    /// - Overflow in accrual math is already prevented via `checked_mul` and clamping.
+    /// - A fixed cap would require a contract upgrade to change and conflicts with
+    ///   the overflow test suite, which exercises values up to `i128::MAX`.
+    /// - Protocol-specific limits (e.g. "max 10 M USDC per stream") belong at the
+    ///   application layer (UI or an admin-gated factory contract), where business
+    ///   context is available.
+    ///
+    /// Senders are responsible for the correctness of the values they supply.
+    /// The validations above (`deposit > 0`, `rate > 0`, `deposit >= rate × duration`,
+    /// valid time window, admin-set minimums) are the contract's complete set of
+    /// creation constraints.
+    ///
+    /// # Examples
+    /// - Linear stream: 1000 tokens over 1000 seconds, no cliff
+    ///   - `deposit_amount = 1000`, `rate = 1`, `start = 0`, `cliff = 0`, `end = 1000`
+    /// - Vesting stream: 12000 tokens over 12 months, 6-month cliff
+    ///   - `deposit_amount = 12000`, `rate = 1`, `start = 0`, `cliff = 15552000`, `end = 31104000`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> Result<u64, ContractError> {
+        let stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+        );
+        Self::create_stream_internal(&env, stream)
+    }
+
+    /// Create a payment stream whose ID cannot be predicted by front-runners.
+    ///
+    /// Identical to `create_stream` except for `nonce`. With `Some(nonce)` the
+    /// stream ID is the first 8 bytes of `sha256(sender XDR || nonce)` (big-endian)
+    /// instead of the next `NextStreamId`, and the nonce is consumed so the same
+    /// sender cannot replay it. Different senders may use the same nonce. `None`
+    /// behaves exactly like `create_stream`.
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns
+    /// - `NonceAlreadyUsed` if `sender` already used `nonce`, or (astronomically
+    ///   unlikely) its derived ID is already taken
+    ///
+    /// # Usage Notes
+    /// - Streams with hashed IDs are not reached by functions that walk the
+    ///   sequential ID range (`list_streams_paginated`, `get_stream_ids_by_status`,
+    ///   `get_streams_near_completion`);
+    ///   use `get_streams_by_sender` / `get_streams_by_recipient` to find them
+    /// - `is_nonce_used` tells whether a nonce is still available
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_with_nonce(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        nonce: Option<u64>,
+    ) -> Result<u64, ContractError> {
+        let Some(nonce) = nonce else {
+            return Self::create_stream(
+                env,
+                sender,
+                recipient,
+                deposit_amount,
+                rate_per_second,
+                start_time,
+                cliff_time,
+                end_time,
+            );
+        };
+
+        let stream_id = nonce_stream_id(&env, &sender, nonce);
+        if is_nonce_used(&env, &sender, nonce) || load_stream(&env, stream_id).is_ok() {
+            return Err(ContractError::NonceAlreadyUsed);
+        }
+        sender.require_auth();
+        mark_nonce_used(&env, &sender, nonce);
+        let stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+        );
+        Self::create_stream_as(&env, stream, Some(stream_id))
+    }
+
+    /// Create a payment stream with non-default behaviour.
+    ///
+    /// Identical to `create_stream` except for the extra `options`, which are
+    /// stored on the stream and fixed for its lifetime.
+    ///
+    /// # Parameters
+    /// - Same as `create_stream`, plus:
+    /// - `options`: See `StreamOptions`; `pause_mode = AccrualFreeze` makes pauses
+    ///   stop the accrual clock instead of only blocking withdrawals, and a positive
+    ///   `cliff_unlock_amount` releases that lump sum at `cliff_time` before streaming
+    ///   the rest linearly from the cliff; `cancellable = false` makes the stream
+    ///   irrevocable; `recipient_must_accept = true` starts it in `Pending` like
+    ///   `create_pending_stream`, with `offer_expiry` as the acceptance deadline;
+    ///   a non-zero `cancel_after` ends the sender's right to cancel at that time;
+    ///   `curve_type = Exponential(exponent_bps)` releases the deposit along
+    ///   `(elapsed / duration)^(exponent_bps / 10_000)` instead of at the rate;
+    ///   `auto_withdraw` opens `trigger_auto_withdraw` to keepers
+    ///
+    /// # Returns
+    /// - `u64`: Unique identifier of the new stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns
+    /// - `InvalidParams` if `cliff_unlock_amount` is negative or exceeds the deposit
+    /// - `UnfundedDeposit` if `deposit < cliff_unlock_amount + rate × (end - cliff)`
+    ///   (replaces the usual `rate × (end - start)` rule when a cliff unlock is set)
+    /// - `InvalidParams` if `recipient_must_accept` is set and `offer_expiry <= now`
+    /// - `InvalidParams` if `cancel_penalty_bps > 10_000`
+    /// - `InvalidParams` if an `Exponential` curve has an exponent outside
+    ///   `[1, MAX_CURVE_EXPONENT_BPS]` or a non-zero `cliff_unlock_amount`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_with_options(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+    ) -> Result<u64, ContractError> {
+        let mut stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+        );
+        stream.pause_mode = options.pause_mode;
+        stream.cliff_unlock_amount = options.cliff_unlock_amount;
+        stream.cancellable = options.cancellable;
+        stream.rate_denominator = options.rate_denominator;
+        stream.cancel_after = options.cancel_after;
+        stream.strict_deposit = options.strict_deposit;
+        stream.cancel_penalty_bps = options.cancel_penalty_bps;
+        stream.recipient_can_pause = options.recipient_can_pause;
+        stream.curve_type = options.curve_type;
+        stream.auto_withdraw = options.auto_withdraw;
+        stream.auto_withdraw_interval = options.auto_withdraw_interval;
+        if options.recipient_must_accept {
+            if options.offer_expiry <= env.ledger().timestamp() {
+                return Err(ContractError::InvalidParams);
+            }
+            stream.status = StreamStatus::Pending;
+            stream.pending_expiry = Some(options.offer_expiry);
+        }
+        Self::create_stream_internal(&env, stream)
+    }
+
+    /// Create a payment stream carrying a free-form reference.
+    ///
+    /// Identical to `create_stream` except that `memo` (an invoice reference,
+    /// employee ID, grant number, …) is stored on the stream and readable via
+    /// `get_stream_memo`. `create_stream` is equivalent to passing an empty memo.
+    ///
+    /// # Parameters
+    /// - Same as `create_stream`, plus:
+    /// - `memo`: At most `MAX_MEMO_LEN` (64) bytes; never interpreted by the contract
+    ///
+    /// # Returns
+    /// - `u64`: Unique identifier of the new stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns
+    /// - `InvalidParams` if `memo` is longer than `MAX_MEMO_LEN` bytes
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_with_memo(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        memo: Bytes,
+    ) -> Result<u64, ContractError> {
+        let mut stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+        );
+        stream.memo = memo;
+        Self::create_stream_internal(&env, stream)
+    }
+
+    /// Create a payment stream anchored to an off-chain legal agreement.
+    ///
+    /// Identical to `create_stream` except that `agreement_hash` (e.g. the
+    /// SHA-256 of a signed payroll or grant contract) is stored alongside the
+    /// stream and readable via `get_agreement_hash`. The hash cannot be changed
+    /// afterwards.
+    ///
+    /// # Parameters
+    /// - Same as `create_stream`, plus:
+    /// - `agreement_hash`: 32-byte hash of the agreement; never interpreted by the
+    ///   contract
+    ///
+    /// # Returns
+    /// - `u64`: Unique identifier of the new stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_with_agreement(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        agreement_hash: BytesN<32>,
+    ) -> Result<u64, ContractError> {
+        let stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+        );
+        let stream_id = Self::create_stream_internal(&env, stream)?;
+        save_agreement_hash(&env, stream_id, &agreement_hash);
+        Ok(stream_id)
+    }
+
+    /// Atomically create two streams paying each other, e.g. salary one way and
+    /// consulting fees the other.
+    ///
+    /// `party_a` funds `params_a` to `party_b` and `party_b` funds `params_b` to
+    /// `party_a`; each entry is created exactly like `create_stream`. The two
+    /// streams are linked (see `get_linked_stream_id`): cancelling either one, through any
+    /// cancel path, also cancels the other.
+    ///
+    /// # Returns
+    /// - `(u64, u64)`: IDs of the `party_a` and `party_b` streams
+    ///
+    /// # Authorization
+    /// - Requires authorization from both `party_a` and `party_b`
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns for either stream (neither is created)
+    /// - `InvalidParams` if `params_a.recipient != party_b` or
+    ///   `params_b.recipient != party_a`
+    pub fn create_swap_stream(
+        env: Env,
+        party_a: Address,
+        party_b: Address,
+        params_a: CreateStreamParams,
+        params_b: CreateStreamParams,
+    ) -> Result<(u64, u64), ContractError> {
+        if params_a.recipient != party_b || params_b.recipient != party_a {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let stream_a = Self::new_stream(
+            &env,
+            party_a,
+            params_a.recipient,
+            params_a.deposit_amount,
+            params_a.rate_per_second,
+            params_a.start_time,
+            params_a.cliff_time,
+            params_a.end_time,
+        );
+        let stream_b = Self::new_stream(
+            &env,
+            party_b,
+            params_b.recipient,
+            params_b.deposit_amount,
+            params_b.rate_per_second,
+            params_b.start_time,
+            params_b.cliff_time,
+            params_b.end_time,
+        );
+
+        let id_a = Self::create_stream_internal(&env, stream_a)?;
+        let id_b = Self::create_stream_internal(&env, stream_b)?;
+        save_linked_stream(&env, id_a, id_b);
+        save_linked_stream(&env, id_b, id_a);
+        Ok((id_a, id_b))
+    }
+
+    /// Create a stream the recipient must opt into before it starts.
+    ///
+    /// Same parameters, validation and deposit transfer as `create_stream`, but the
+    /// stream starts in `Pending` and accrues nothing until the recipient calls
+    /// `accept_stream`. The recipient may instead `reject_stream`, and once
+    /// `pending_expiry` passes anyone may call `claim_expired_pending` to refund the
+    /// sender.
+    ///
+    /// # Parameters
+    /// - Same as `create_stream`, plus:
+    /// - `pending_expiry`: Ledger timestamp after which the offer can no longer be
+    ///   accepted (must be in the future)
+    ///
+    /// # Returns
+    /// - `u64`: Unique identifier of the pending stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns
+    /// - `InvalidParams` if `pending_expiry <= now`
+    ///
+    /// # Usage Notes
+    /// - `start_time`, `cliff_time` and `end_time` describe the schedule's shape; on
+    ///   acceptance the whole schedule is shifted so that it starts at the acceptance time
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_pending_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        pending_expiry: u64,
+    ) -> Result<u64, ContractError> {
+        if pending_expiry <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidParams);
+        }
+        let mut stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+        );
+        stream.status = StreamStatus::Pending;
+        stream.pending_expiry = Some(pending_expiry);
+        Self::create_stream_internal(&env, stream)
+    }
+
+    /// Create a step-function (tranche) vesting stream.
+    ///
+    /// Splits `deposit_amount` into `intervals` equal tranches that unlock at evenly
+    /// spaced times between `start_time` and `end_time`; the last tranche, at
+    /// `end_time`, also carries any remainder of `deposit_amount / intervals`.
+    /// Nothing unlocks between steps. Quarterly vesting over a year is
+    /// `intervals = 4`.
+    ///
+    /// # Parameters
+    /// - `sender`: Address funding the stream (must authorize the transaction)
+    /// - `recipient`: Address receiving the unlocked tokens
+    /// - `deposit_amount`: Total tokens to deposit (must be > 0)
+    /// - `intervals`: Number of tranches (must be in `[1, end_time - start_time]`)
+    /// - `start_time`: Schedule start; the first tranche unlocks one interval later
+    /// - `end_time`: When the final tranche unlocks (must be > start_time)
+    ///
+    /// # Returns
+    /// - `u64`: Unique identifier of the new stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `deposit_amount <= 0`, `sender == recipient`,
+    ///   `start_time >= end_time` or `intervals` is out of range
+    ///
+    /// # Usage Notes
+    /// - The stream stores `intervals` and `rate_per_second = 0`; `topup_stream` and
+    ///   `extend_stream_end_time` are not available for stepped streams
+    /// - Pause, cancel and withdraw behave as for linear streams
+    pub fn create_step_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        intervals: u32,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, ContractError> {
+        let mut stream = Self::new_stream(
+            &env,
+            sender,
+            recipient,
+            deposit_amount,
+            0,
+            start_time,
+            start_time,
+            end_time,
+        );
+        stream.intervals = intervals;
+        Self::create_stream_internal(&env, stream)
+    }
+
+    /// Create multiple payment streams in a single transaction.
+    ///
+    /// Optimizes gas usage by verifying authorization once and doing a single bulk
+    /// token transfer for all streams, executing the creations atomically.
+    ///
+    /// # Parameters
+    /// - `sender`: Address funding all streams in the batch
+    /// - `streams`: Vector of stream configuration parameters
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Vector of unique stream identifiers for the newly created streams
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address exactly once for the entire batch.
+    ///
+    /// # Errors
+    /// - Any error `create_stream` returns for an individual entry (the whole batch fails)
+    /// - `ArithmeticOverflow` if the summed deposits overflow
+    /// - `StreamLimitExceeded` if the batch would take `sender` past
+    ///   `max_streams_per_sender`
+    pub fn create_streams(
+        env: Env,
+        sender: Address,
+        streams: soroban_sdk::Vec<CreateStreamParams>,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        let rate_denominators = soroban_sdk::Vec::new(&env);
+        Self::create_stream_batch(env, sender, streams, rate_denominators)
+    }
+
+    /// `create_streams` with a `rate_denominator` for each entry:
+    /// `rate_denominators[i]` applies to `streams[i]`, and missing entries mean 1.
+    fn create_stream_batch(
+        env: Env,
+        sender: Address,
+        streams: soroban_sdk::Vec<CreateStreamParams>,
+        rate_denominators: soroban_sdk::Vec<u64>,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        Self::require_protocol_active(&env)?;
+        sender.require_auth();
+
+        let config = get_config(&env);
+        Self::require_whitelisted_sender(&env, &config, &sender)?;
+        Self::require_global_capacity(&env, &config, streams.len())?;
+        Self::require_sender_capacity(&env, &config, &sender, streams.len())?;
+        let mut total_deposit: i128 = 0;
+        let mut total_fee: i128 = 0;
+
+        // First pass: validate all streams and calculate total deposit required
+        for (i, params) in streams.iter().enumerate() {
+            let rate_denominator = rate_denominators.get(i as u32).unwrap_or(1);
+            Self::require_recipient_allowed(&env, &params.recipient)?;
+            Self::require_minimums(
+                &config,
+                params.deposit_amount,
+                params.rate_per_second,
+                rate_denominator,
+                false,
+            )?;
+            Self::require_min_duration(&config, params.start_time, params.end_time)?;
+            let (net_deposit, fee) = Self::split_protocol_fee(&config, params.deposit_amount)?;
+            Self::validate_linear_params(
+                &sender,
+                &params.recipient,
+                net_deposit,
+                params.rate_per_second,
+                rate_denominator,
+                params.start_time,
+                params.cliff_time,
+                params.end_time,
+            )?;
+            if config.strict_deposit_mode
+                && net_deposit
+                    > Self::streamable_amount(
+                        params.rate_per_second,
+                        rate_denominator,
+                        params.end_time - params.start_time,
+                    )?
+            {
+                return Err(ContractError::ExcessDeposit);
+            }
+            total_deposit = total_deposit
+                .checked_add(params.deposit_amount)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            total_fee += fee;
+        }
+
+        // Bulk transfer tokens from sender to this contract atomically to save gas
+        if total_deposit > 0 {
+            let token_client = token::Client::new(&env, &config.token);
+            token_client.transfer(&sender, &env.current_contract_address(), &total_deposit);
+        }
+        Self::accrue_protocol_fees(&env, total_fee)?;
+
+        // Second pass: generate IDs, persist state, and emit events iteratively
+        let mut created_ids = soroban_sdk::Vec::new(&env);
+        for (i, params) in streams.iter().enumerate() {
+            let (net_deposit, _) = Self::split_protocol_fee(&config, params.deposit_amount)?;
+            let mut stream = Self::new_stream(
+                &env,
+                sender.clone(),
+                params.recipient,
+                net_deposit,
+                params.rate_per_second,
+                params.start_time,
+                params.cliff_time,
+                params.end_time,
+            );
+            stream.token = config.token.clone();
+            stream.rate_denominator = rate_denominators.get(i as u32).unwrap_or(1);
+            let stream_id = Self::persist_new_stream(&env, stream, None)?;
+            created_ids.push_back(stream_id);
+        }
+
+        Ok(created_ids)
+    }
+
+    /// Split one deposit into independent streams to several recipients.
+    ///
+    /// `recipients` pairs each address with its share in basis points; the shares
+    /// must add up to exactly `10_000`. Recipient `i` gets a stream of
+    /// `deposit_amount × weight_i / 10_000` (the last recipient also takes the
+    /// rounding remainder) with no cliff. Its rate streams exactly that deposit,
+    /// net of the protocol fee, over `end_time - start_time`: a fractional
+    /// `rate_per_second / rate_denominator` in lowest terms, so shares below one
+    /// token per second work and nothing is left over. The streams are created as
+    /// by `create_streams`: one transfer of the whole deposit, then each stream is
+    /// withdrawn, paused or cancelled on its own.
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: IDs of the new streams, in `recipients` order
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address once
+    ///
+    /// # Errors
+    /// - `InvalidParams` if the weights do not sum to `10_000` (including an empty
+    ///   `recipients`) or `start_time >= end_time`
+    /// - `ArithmeticOverflow` if `deposit_amount × weight` overflows
+    /// - Any error `create_streams` returns, e.g. `InvalidParams` if a share rounds
+    ///   down to a zero deposit
+    pub fn create_split_stream(
+        env: Env,
+        sender: Address,
+        recipients: soroban_sdk::Vec<(Address, u32)>,
+        deposit_amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        let mut total_weight: u32 = 0;
+        for (_, weight) in recipients.iter() {
+            total_weight = total_weight.saturating_add(weight);
+        }
+        if total_weight != BPS_DENOMINATOR || start_time >= end_time {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let config = get_config(&env);
+        let duration = end_time - start_time;
+        let mut remaining = deposit_amount;
+        let mut streams = soroban_sdk::Vec::new(&env);
+        let mut rate_denominators = soroban_sdk::Vec::new(&env);
+        for (i, (recipient, weight)) in recipients.iter().enumerate() {
+            let deposit = if i as u32 + 1 == recipients.len() {
+                remaining
+            } else {
+                deposit_amount
+                    .checked_mul(i128::from(weight))
+                    .ok_or(ContractError::ArithmeticOverflow)?
+                    / i128::from(BPS_DENOMINATOR)
+            };
+            remaining -= deposit;
+            let (net_deposit, _) = Self::split_protocol_fee(&config, deposit)?;
+            let (rate_per_second, rate_denominator) = Self::exact_rate(net_deposit, duration);
+            streams.push_back(CreateStreamParams {
+                recipient,
+                deposit_amount: deposit,
+                rate_per_second,
+                start_time,
+                cliff_time: start_time,
+                end_time,
+            });
+            rate_denominators.push_back(rate_denominator);
+        }
+        Self::create_stream_batch(env, sender, streams, rate_denominators)
+    }
+
+    /// Set up `recurrence_count` back-to-back copies of one stream, e.g. twelve
+    /// monthly payroll streams in one transaction.
+    ///
+    /// The first stream uses `params` as given. Each later one starts
+    /// `recurrence_gap` seconds after the previous one ends and keeps the same
+    /// duration, deposit, rate and cliff offset (a `cliff_time` of 0 stays "no
+    /// cliff"). The streams are created as by `create_streams`: one transfer of
+    /// `deposit_amount × recurrence_count`, then each stream lives on its own.
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: IDs of the new streams, earliest first
+    ///
+    /// # Authorization
+    /// - Requires authorization from the sender address once
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `recurrence_count` is 0 or `params.start_time >=
+    ///   params.end_time`
+    /// - `ArithmeticOverflow` if a later stream's times overflow `u64`
+    /// - Any error `create_streams` returns
+    pub fn create_recurring_stream(
+        env: Env,
+        sender: Address,
+        params: CreateStreamParams,
+        recurrence_count: u32,
+        recurrence_gap: u64,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        if recurrence_count == 0 || params.start_time >= params.end_time {
+            return Err(ContractError::InvalidParams);
+        }
+        let duration = params.end_time - params.start_time;
+        let cliff_offset = params.cliff_time.saturating_sub(params.start_time);
+        let has_cliff = params.cliff_time != 0;
+
+        let mut streams = soroban_sdk::Vec::new(&env);
+        let mut next = params;
+        streams.push_back(next.clone());
+        for _ in 1..recurrence_count {
+            let start_time = next
+                .end_time
+                .checked_add(recurrence_gap)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            next.start_time = start_time;
+            next.end_time = start_time
+                .checked_add(duration)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            if has_cliff {
+                next.cliff_time = start_time + cliff_offset;
+            }
+            streams.push_back(next.clone());
+        }
+        Self::create_streams(env, sender, streams)
+    }
+
+    /// Create many streams atomically; alias of `create_streams`.
+    ///
+    /// Provided under the `batch_*` name used by `batch_withdraw` so payroll and
+    /// airdrop tooling can discover both batch entry points together. Every entry is
+    /// validated before the summed deposit is pulled from `sender` in a single
+    /// transfer, and the IDs are returned in input order.
+    ///
+    /// # Errors
+    /// - Same as `create_streams`; an invalid entry fails the whole batch and no
+    ///   stream is created
+    pub fn batch_create_streams(
+        env: Env,
+        sender: Address,
+        params: soroban_sdk::Vec<CreateStreamParams>,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        Self::create_streams(env, sender, params)
+    }
+
+    /// Pause an active payment stream.
+    ///
+    /// Temporarily halts withdrawals from the stream while preserving accrual calculations.
+    /// The stream can be resumed later by the sender or admin. Accrual continues based on
+    /// time elapsed, but the recipient cannot withdraw while paused.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to pause
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender (original creator)
+    /// - Admin can use `pause_stream_as_admin` for administrative override
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    /// - `StreamCompleted` / `StreamCancelled` if the stream is in a terminal state
+    ///
+    /// # Panics
+    /// - If caller is not authorized (not the sender)
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "paused", stream_id)` with a `StreamPaused` payload
+    ///
+    /// # Usage Notes
+    /// - Pausing does not affect accrual calculations (time-based)
+    /// - Recipient cannot withdraw while stream is paused
+    /// - Stream can be cancelled while paused
+    /// - Use `resume_stream` to reactivate withdrawals
+    /// - Idempotent: pausing an already `Paused` stream (by anyone) returns `Ok`
+    ///   without changes or events, so racing pauses do not fail
+    pub fn pause_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+
+        Self::require_sender_or_admin(&env, &stream.sender);
+        if stream.status == StreamStatus::Paused {
+            return Ok(());
+        }
+        Self::apply_pause(&env, &mut stream, PauserKind::Sender)
+    }
+
+    /// Resume a paused payment stream.
+    ///
+    /// Reactivates a paused stream, allowing the recipient to withdraw accrued funds again.
+    /// Only streams in `Paused` state can be resumed. Terminal states (Completed, Cancelled)
+    /// cannot be resumed.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to resume
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender (original creator)
+    /// - Admin can use `resume_stream_as_admin` for administrative override
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    /// - `StreamCompleted` if the stream is `Completed` (terminal state)
+    /// - `StreamCancelled` if the stream is `Cancelled` (terminal state)
+    /// - `Unauthorized` if the admin paused the stream (`paused_by = Admin`)
+    ///
+    /// # Panics
+    /// - If caller is not authorized (not the sender)
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "resumed", stream_id)` with a `StreamResumed` payload
+    ///
+    /// # Usage Notes
+    /// - Idempotent: resuming an `Active` stream returns `Ok` without changes or events
+    /// - Accrual calculations are time-based and unaffected by pause/resume
+    /// - After resume, recipient can immediately withdraw accrued funds
+    pub fn resume_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::require_sender_or_admin(&env, &stream.sender);
+        if stream.status == StreamStatus::Active {
+            return Ok(());
+        }
+        Self::apply_resume(&env, &mut stream, PauserKind::Sender)
+    }
+
+    /// Pause a stream as its recipient.
+    ///
+    /// Same as `pause_stream`, for streams created with
+    /// `StreamOptions.recipient_can_pause` (e.g. vesting agreements that let the
+    /// recipient halt payments while terms are renegotiated).
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `Unauthorized` if the stream does not allow recipient pauses
+    /// - Otherwise as `pause_stream`
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "paused", stream_id)` with a `StreamPaused` payload
+    pub fn pause_stream_as_recipient(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+        if !stream.recipient_can_pause {
+            return Err(ContractError::Unauthorized);
+        }
+        if stream.status == StreamStatus::Paused {
+            return Ok(());
+        }
+        Self::apply_pause(&env, &mut stream, PauserKind::Recipient)
+    }
+
+    /// Resume a stream as its recipient.
+    ///
+    /// Same as `resume_stream`, for streams created with
+    /// `StreamOptions.recipient_can_pause`. Either party may lift the other's
+    /// pause; neither can lift an admin pause.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `Unauthorized` if the stream does not allow recipient pauses or the admin
+    ///   paused it
+    /// - Otherwise as `resume_stream`
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "resumed", stream_id)` with a `StreamResumed` payload
+    pub fn resume_stream_as_recipient(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+        if !stream.recipient_can_pause {
+            return Err(ContractError::Unauthorized);
+        }
+        if stream.status == StreamStatus::Active {
+            return Ok(());
+        }
+        Self::apply_resume(&env, &mut stream, PauserKind::Recipient)
+    }
+
+    /// Cancel a payment stream and refund unstreamed funds to the sender.
+    ///
+    /// Terminates an active or paused stream, immediately refunding any unstreamed tokens
+    /// to the sender. The accrued amount (based on time elapsed) remains in the contract
+    /// for the recipient to withdraw. This is a terminal operation - cancelled streams
+    /// cannot be resumed.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to cancel
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender (original creator)
+    /// - Admin can use `cancel_stream_as_admin` for administrative override
+    ///
+    /// # Behavior
+    /// 1. Validates stream is in `Active` or `Paused` state
+    /// 2. Calculates accrued amount: `min((now - start_time) × rate, deposit_amount)`
+    /// 3. Calculates refund: `deposit_amount - accrued`, less the stream's
+    ///    `cancel_penalty_bps` share of it, which is added to the recipient's accrual
+    /// 4. Transfers refund to sender (if > 0)
+    /// 5. Sets stream status to `Cancelled`
+    /// 6. Accrued but not withdrawn amount remains for recipient
+    ///
+    /// # Returns
+    /// - `(sender_refund, recipient_accrued)`: Tokens refunded to the sender by this
+    ///   call and the total accrued to the recipient at cancellation (withdrawn or
+    ///   not), matching the `StreamCancelled` event
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    /// - `InvalidState` if the stream is not `Active` or `Paused` (already completed or cancelled)
+    /// - `StreamNotCancellable` if the stream was created with `cancellable = false`
+    /// - `CancelDeadlinePassed` if the ledger time is past the stream's `cancel_after`
+    ///
+    /// # Panics
+    /// - If caller is not authorized (not the sender)
+    /// - If token transfer fails (should not happen with valid contract state)
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "cancelled", stream_id)` with a `StreamCancelled` payload
+    ///
+    /// # Usage Notes
+    /// - Cancellation is irreversible (terminal state)
+    /// - Recipient can still withdraw accrued amount after cancellation
+    /// - If fully accrued (time >= end_time), sender receives no refund
+    /// - Accrual is time-based, not affected by pause state
+    /// - Can be called on paused streams
+    ///
+    /// # Examples
+    /// - Cancel at 30% completion → sender gets 70% refund, recipient can withdraw 30%
+    /// - Cancel at 100% completion → sender gets 0% refund, recipient can withdraw 100%
+    /// - Cancel before cliff → sender gets 100% refund (no accrual before cliff)
+    pub fn cancel_stream(env: Env, stream_id: u64) -> Result<(i128, i128), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::require_sender_or_admin(&env, &stream.sender);
+        Self::require_before_cancel_deadline(&env, &stream)?;
+        Self::apply_cancel(&env, &mut stream)
+    }
+
+    /// Cancel several streams owned by the same sender in one transaction.
+    ///
+    /// Runs the same cancellation as `cancel_stream` for every stream, then returns
+    /// the aggregate refund to the sender with a single token transfer. Accrued
+    /// amounts stay in the contract for each recipient to withdraw.
+    ///
+    /// # Parameters
+    /// - `stream_ids`: Streams to cancel; all must share the same sender
+    ///
+    /// # Returns
+    /// - `Vec<BatchCancelResult>`: Refund and accrued amount per stream, in the
+    ///   same order as `stream_ids`
+    ///
+    /// # Authorization
+    /// - Requires authorization from the common sender exactly once for the batch
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if any stream does not exist
+    /// - `Unauthorized` if the streams do not all share the same sender
+    /// - `InvalidState` / `StreamNotCancellable` / `CancelDeadlinePassed` for any
+    ///   stream `cancel_stream` would reject (including an ID listed twice)
+    /// - `ArithmeticOverflow` if the summed refunds overflow
+    ///
+    /// # Usage Notes
+    /// - The batch is atomic: a failure on any stream rolls back every stream
+    /// - An empty `stream_ids` returns an empty vector without any transfer
+    /// - Publishes the same `cancelled` event as `cancel_stream` per stream
+    pub fn cancel_stream_batch(
+        env: Env,
+        stream_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<soroban_sdk::Vec<BatchCancelResult>, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut results = soroban_sdk::Vec::new(&env);
+        if stream_ids.is_empty() {
+            return Ok(results);
+        }
+
+        let sender = load_stream(&env, stream_ids.get_unchecked(0))?.sender;
+        sender.require_auth();
+
+        let mut refunds = Map::new(&env);
+        for stream_id in stream_ids.iter() {
+            let mut stream = load_stream(&env, stream_id)?;
+            if stream.sender != sender {
+                return Err(ContractError::Unauthorized);
+            }
+            Self::require_before_cancel_deadline(&env, &stream)?;
+
+            let (refund, accrued) = Self::record_cancel(&env, &mut stream)?;
+            add_token_total(&mut refunds, &stream.token, refund)?;
+            Self::cancel_linked(&env, &stream)?;
+            Self::publish_cancel(&env, &stream, refund, accrued);
+            results.push_back(BatchCancelResult {
+                stream_id,
+                refund,
+                recipient_accrued: accrued,
+            });
+        }
+
+        transfer_token_totals(&env, &refunds, &sender);
+
+        Ok(results)
+    }
+
+    /// Cancel a payment stream as its recipient, renouncing the unvested remainder.
+    ///
+    /// Runs the same cancellation as `cancel_stream` (unstreamed tokens go back to the
+    /// sender) and then immediately pays out whatever the recipient has accrued but
+    /// not yet withdrawn.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to cancel
+    ///
+    /// # Returns
+    /// - `i128`: Amount transferred to the recipient (0 if nothing was outstanding)
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `RecipientCancelDisabled` if `Config.recipient_cancel_allowed` is `false`
+    /// - `InvalidState` if the stream is not `Active` or `Paused`
+    /// - `StreamNotCancellable` if the stream was created with `cancellable = false`
+    ///
+    /// # Panics
+    /// - If caller is not authorized (not the recipient)
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "cancelled", stream_id)` with a `StreamCancelled` payload
+    /// - Publishes `("STREAM", "withdrawn", stream_id)` (and `completed` if the stream was
+    ///   fully accrued) when an accrued amount is paid out
+    pub fn cancel_stream_as_recipient(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        if !get_config(&env).recipient_cancel_allowed {
+            return Err(ContractError::RecipientCancelDisabled);
+        }
+
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+        // The penalty protects the recipient from early termination by others;
+        // it is not owed when the recipient walks away.
+        stream.cancel_penalty_bps = 0;
+        Self::apply_cancel(&env, &mut stream)?;
+
+        let recipient = stream.recipient.clone();
+        Self::withdraw_and_transfer(&env, &mut stream, &recipient)
+    }
+
+    /// Accept a pending stream, starting it at the current ledger time.
+    ///
+    /// Transitions the stream from `Pending` to `Active` and shifts `start_time`,
+    /// `cliff_time` and `end_time` so that the stream starts now while keeping its
+    /// duration and cliff offset.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is not `Pending`
+    /// - `PendingExpired` if `pending_expiry` has passed
+    /// - `ArithmeticOverflow` if the shifted schedule overflows
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "accepted", stream_id)` with a `StreamAccepted` payload
+    pub fn accept_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = Self::load_pending_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        let now = env.ledger().timestamp();
+        if stream.pending_expiry.is_some_and(|expiry| now > expiry) {
+            return Err(ContractError::PendingExpired);
+        }
+
+        let cliff_offset = stream.cliff_time - stream.start_time;
+        let duration = stream.end_time - stream.start_time;
+        stream.start_time = now;
+        stream.cliff_time = now
+            .checked_add(cliff_offset)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        stream.end_time = now
+            .checked_add(duration)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        push_status_record(&env, &stream, StreamStatus::Active);
+        stream.status = StreamStatus::Active;
+        stream.pending_expiry = None;
+        save_stream(&env, &stream);
+
+        publish_stream_event(
+            &env,
+            symbol_short!("accepted"),
+            stream_id,
+            StreamAccepted {
+                stream_id,
+                start_time: stream.start_time,
+                cliff_time: stream.cliff_time,
+                end_time: stream.end_time,
+            },
+        );
+        Ok(())
+    }
+
+    /// Reject a pending stream, returning the full deposit to the sender.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is not `Pending`
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "rejected", stream_id)` with a `StreamPendingRefunded` payload
+    pub fn reject_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = Self::load_pending_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        Self::refund_pending(&env, &mut stream, symbol_short!("rejected"))
+    }
+
+    /// Refund a pending stream whose acceptance deadline has passed.
+    ///
+    /// Permissionless: the full deposit always goes back to the sender, so anyone
+    /// (typically the sender or a keeper) may trigger it.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is not `Pending` or `pending_expiry` has not passed
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "expired", stream_id)` with a `StreamPendingRefunded` payload
+    pub fn claim_expired_pending(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = Self::load_pending_stream(&env, stream_id)?;
+
+        let now = env.ledger().timestamp();
+        if !stream.pending_expiry.is_some_and(|expiry| now > expiry) {
+            return Err(ContractError::InvalidState);
+        }
+
+        Self::refund_pending(&env, &mut stream, symbol_short!("expired"))
+    }
+
+    /// Sweep an abandoned stream whose recipient never drained it.
+    ///
+    /// Permissionless: once `get_expiry_time` has passed, anyone may move the
+    /// stream to `Expired`, paying its unclaimed accrued tokens to the recipient
+    /// and any unaccrued remainder (normally 0) back to the sender.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `StreamPaused`, `StreamCompleted`, `StreamCancelled`, `StreamPending` or
+    ///   `StreamExpired` if the stream is not `Active`
+    /// - `InvalidState` if the expiry time has not passed
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "abandoned", stream_id)` with a `StreamExpired` payload
+    pub fn expire_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::activate_if_started(&env, &mut stream);
+        Self::require_status(stream.status, StreamStatus::Active)?;
+
+        let now = env.ledger().timestamp();
+        if now < Self::expiry_time(&env, &stream) {
+            return Err(ContractError::InvalidState);
+        }
+        let accrued = Self::accrued_at(&stream, now)?;
+        let recipient_amount = accrued - stream.withdrawn_amount;
+        let sender_refund = stream.deposit_amount - accrued;
+
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        move_status_count(&env, Some(stream.status), StreamStatus::Expired);
+        push_status_record(&env, &stream, StreamStatus::Expired);
+        stream.status = StreamStatus::Expired;
+        stream.withdrawn_amount = accrued;
+        stream.approved_operator = None;
+        save_stream(&env, &stream);
+        adjust_protocol_total(
+            &env,
+            &DataKey::TotalLocked,
+            -(recipient_amount + sender_refund),
+        )?;
+        adjust_protocol_total(&env, &DataKey::TotalStreamed, recipient_amount)?;
+
+        let token_client = token::Client::new(&env, &stream.token);
+        if recipient_amount > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &stream.recipient,
+                &recipient_amount,
+            );
+        }
+        if sender_refund > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &stream.sender,
+                &sender_refund,
+            );
+        }
+
+        publish_stream_event(
+            &env,
+            symbol_short!("abandoned"),
+            stream_id,
+            StreamExpired {
+                stream_id,
+                recipient_amount,
+                sender_refund,
+            },
+        );
+        Ok(())
+    }
+
+    /// Last-resort recovery of a stream whose sender and recipient both lost
+    /// their keys.
+    ///
+    /// Once `recovery_grace_period` has passed since the stream's end, the
+    /// configured `Config.recovery_address` may take every token still held for
+    /// it: `deposit_amount - withdrawn_amount` for a live stream, the unclaimed
+    /// accrual for a cancelled one. The stream moves to the terminal `Expired`
+    /// status and nothing remains claimable.
+    ///
+    /// # Returns
+    /// - `i128`: Amount transferred to the recovery address
+    ///
+    /// # Authorization
+    /// - Requires authorization from `Config.recovery_address`
+    ///
+    /// # Errors
+    /// - `Unauthorized` if no recovery address is configured
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the grace period has not passed, the stream is
+    ///   `Pending`, `Completed` or `Expired`, or a cancelled stream has nothing
+    ///   left to claim
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "recovered", stream_id)` with an
+    ///   `OrphanedStreamRecovered` payload
+    pub fn recover_orphaned_stream(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let config = get_config(&env);
+        let recovery_address = config
+            .recovery_address
+            .clone()
+            .ok_or(ContractError::Unauthorized)?;
+        recovery_address.require_auth();
+
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::activate_if_started(&env, &mut stream);
+        let now = env.ledger().timestamp();
+        if now < Self::recovery_time(&stream, &config) {
+            return Err(ContractError::InvalidState);
+        }
+        let amount = match stream.status {
+            StreamStatus::Active | StreamStatus::Paused => {
+                stream.deposit_amount - stream.withdrawn_amount
+            }
+            StreamStatus::Cancelled => Self::accrued_at(&stream, now)? - stream.withdrawn_amount,
+            _ => return Err(ContractError::InvalidState),
+        };
+        if amount <= 0 {
+            return Err(ContractError::InvalidState);
+        }
+
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        move_status_count(&env, Some(stream.status), StreamStatus::Expired);
+        push_status_record(&env, &stream, StreamStatus::Expired);
+        stream.status = StreamStatus::Expired;
+        stream.paused_at = None;
+        stream.paused_by = PauserKind::NotPaused;
+        stream.approved_operator = None;
+        save_stream(&env, &stream);
+        adjust_protocol_total(&env, &DataKey::TotalLocked, -amount)?;
+
+        token::Client::new(&env, &stream.token).transfer(
+            &env.current_contract_address(),
+            &recovery_address,
+            &amount,
+        );
+
+        publish_stream_event(
+            &env,
+            symbol_short!("recovered"),
+            stream_id,
+            OrphanedStreamRecovered {
+                stream_id,
+                recovery_address,
+                amount,
+            },
+        );
+        Ok(amount)
+    }
+
+    /// Return the ledger time from which `expire_stream` may sweep the stream.
+    ///
+    /// `end_time` (pushed back by any `AccrualFreeze` pauses so far) plus the
+    /// current `Config.expiry_grace_period`; saturates at `u64::MAX`.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_expiry_time(env: Env, stream_id: u64) -> Result<u64, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(Self::expiry_time(&env, &stream))
+    }
+
+    /// Withdraw accrued tokens from a payment stream to the recipient.
+    ///
+    /// Transfers all accrued-but-not-yet-withdrawn tokens to the stream's recipient.
+    /// The amount withdrawn is calculated as `accrued - withdrawn_amount`, where accrued
+    /// is based on time elapsed since stream start. If this withdrawal completes the
+    /// stream (all deposited tokens withdrawn), the stream status transitions to `Completed`.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to withdraw from
+    ///
+    /// # Returns
+    /// - `i128`: The amount of tokens transferred to the recipient, net of any
+    ///   `Config.fee_on_withdrawal_bps` fee (0 if nothing to withdraw)
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient (only recipient can withdraw)
+    /// - This prevents anyone from withdrawing on behalf of the recipient
+    ///
+    /// # Zero Withdrawable Behavior
+    /// - If `accrued == withdrawn_amount` (nothing to withdraw), returns 0 immediately
+    /// - No token transfer occurs, no state change, no event published
+    /// - This is idempotent: safe to call multiple times without side effects
+    /// - Occurs before cliff time or when all accrued funds already withdrawn
+    /// - Frontends can call withdraw without pre-checking balance
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    /// - `StreamCompleted` if the stream is `Completed` (all tokens already withdrawn)
+    /// - `StreamPaused` if the stream is `Paused` (withdrawals not allowed while paused)
+    ///
+    /// # Panics
+    /// - If caller is not authorized (not the recipient)
+    /// - If token transfer fails (insufficient contract balance, should not happen)
+    ///
+    /// # State Changes
+    /// - Updates `withdrawn_amount` by the amount transferred (only if withdrawable > 0)
+    /// - Sets status to `Completed` if all deposited tokens are withdrawn
+    /// - Extends stream storage TTL to prevent expiration
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "withdrawn", stream_id)` with a `StreamWithdrawn` payload
+    ///   (only if amount > 0), followed by `("STREAM", "completed", stream_id)` if the
+    ///   withdrawal drained the stream
+    ///
+    /// # Usage Notes
+    /// - Can be called multiple times to withdraw incrementally
+    /// - Accrual is time-based: `min((now - start_time) × rate, deposit_amount)`
+    /// - Before cliff time, accrued amount is 0 (returns 0, no transfer)
+    /// - After end_time, accrued amount is capped at deposit_amount
+    /// - Works on `Active` and `Cancelled` streams, not on `Paused` or `Completed`
+    /// - For cancelled streams, only the accrued amount (not refunded) can be withdrawn
+    ///
+    /// # Examples
+    /// - Stream: 1000 tokens over 1000 seconds (1 token/sec)
+    /// - At t=0 (before cliff): withdraw() returns 0 (no transfer)
+    /// - At t=300: withdraw() returns 300 tokens
+    /// - At t=300 (again): withdraw() returns 0 (already withdrawn)
+    /// - At t=800: withdraw() returns 500 tokens (800 - 300 already withdrawn)
+    /// - At t=1000: withdraw() returns 200 tokens, status → Completed
+    pub fn withdraw(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+
+        // Enforce recipient-only authorization: only the stream's recipient can withdraw
+        // This is equivalent to checking env.invoker() == stream.recipient
+        // require_auth() ensures only the recipient can authorize this call,
+        // preventing anyone from withdrawing on behalf of the recipient
+        stream.recipient.require_auth();
+
+        // Handle zero withdrawable: return 0 without transfer or state change (idempotent).
+        // This occurs before cliff or when all accrued funds have been withdrawn.
+        // Frontends can safely call withdraw without checking balance first.
+        let recipient = stream.recipient.clone();
+        Self::withdraw_and_transfer(&env, &mut stream, &recipient)
+    }
+
+    /// Withdraw from a stream and reinvest the proceeds in a new stream, atomically.
+    ///
+    /// Runs `withdraw` on `from_id`, then `create_stream` with the recipient of
+    /// `from_id` as sender and `new_stream_params` describing the new stream. The
+    /// new stream's `deposit_amount` is paid out of the withdrawn tokens; any
+    /// surplus stays with the caller.
+    ///
+    /// # Returns
+    /// - `(withdrawn_amount, new_stream_id)`: tokens withdrawn from `from_id`
+    ///   (after any withdrawal fee) and the ID of the new stream
+    ///
+    /// # Authorization
+    /// - Requires authorization from the recipient of `from_id`, who funds the
+    ///   new stream
+    ///
+    /// # Errors
+    /// - Any error `withdraw` or `create_stream` returns
+    /// - `UnfundedDeposit` if the withdrawn amount is below
+    ///   `new_stream_params.deposit_amount`; nothing is withdrawn or created
+    /// - `InvalidParams` if `from_id` is funded in a token other than
+    ///   `Config.token` (see `admin_set_token`)
+    pub fn withdraw_and_create_stream(
+        env: Env,
+        from_id: u64,
+        new_stream_params: CreateStreamParams,
+    ) -> Result<(i128, u64), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, from_id)?;
+        stream.recipient.require_auth();
+        if stream.token != get_config(&env).token {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let recipient = stream.recipient.clone();
+        let withdrawn = Self::withdraw_and_transfer(&env, &mut stream, &recipient)?;
+        if withdrawn < new_stream_params.deposit_amount {
+            return Err(ContractError::UnfundedDeposit);
+        }
+
+        let new_stream = Self::new_stream(
+            &env,
+            recipient,
+            new_stream_params.recipient,
+            new_stream_params.deposit_amount,
+            new_stream_params.rate_per_second,
+            new_stream_params.start_time,
+            new_stream_params.cliff_time,
+            new_stream_params.end_time,
+        );
+        // The recipient's authorization above also covers the new stream.
+        let new_stream_id = Self::create_stream_as(&env, new_stream, None)?;
+        Ok((withdrawn, new_stream_id))
+    }
+
+    /// Withdraw accrued tokens to `destination` instead of the recipient.
+    ///
+    /// Same accrual and state update as `withdraw`; only the transfer target
+    /// differs (e.g. a cold wallet or a DeFi protocol). With
+    /// `destination == recipient` this is equivalent to `withdraw`.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Stream to withdraw from
+    /// - `destination`: Address that receives the tokens
+    ///
+    /// # Returns
+    /// - `i128`: Amount transferred to `destination` (0 if nothing is withdrawable)
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///   (an approved operator uses `withdraw_to_as_operator`)
+    ///
+    /// # Errors
+    /// - Same as `withdraw`
+    ///
+    /// # Events
+    /// - Same as `withdraw`
+    pub fn withdraw_to(
+        env: Env,
+        stream_id: u64,
+        destination: Address,
+    ) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        Self::withdraw_and_transfer(&env, &mut stream, &destination)
+    }
+
+    /// Withdraw accrued tokens to the recipient, authorized by its approved operator.
+    ///
+    /// Lets a keeper bot or contract trigger `withdraw` without the recipient
+    /// signing every transaction. Tokens always go to `stream.recipient`.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Stream to withdraw from
+    /// - `operator`: Caller; must equal the stream's `approved_operator`
+    ///
+    /// # Returns
+    /// - `i128`: Amount transferred to the recipient (0 if nothing is withdrawable)
+    ///
+    /// # Authorization
+    /// - Requires authorization from `operator`
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `Unauthorized` if `operator` is not the stream's approved operator
+    /// - Any error `withdraw` returns for the stream's status
+    ///
+    /// # Events
+    /// - Same as `withdraw`
+    pub fn withdraw_as_operator(
+        env: Env,
+        stream_id: u64,
+        operator: Address,
+    ) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        if stream.approved_operator.as_ref() != Some(&operator) {
+            return Err(ContractError::Unauthorized);
+        }
+        operator.require_auth();
+
+        let recipient = stream.recipient.clone();
+        Self::withdraw_and_transfer(&env, &mut stream, &recipient)
+    }
+
+    /// Withdraw accrued tokens to the recipient on behalf of anyone, for streams
+    /// created with `StreamOptions.auto_withdraw`.
+    ///
+    /// Lets keeper bots sweep a recipient's tokens on a schedule without an
+    /// operator approval. The caller receives no fee; tokens always go to
+    /// `stream.recipient`, less any `Config.fee_on_withdrawal_bps`.
+    ///
+    /// # Returns
+    /// - `i128`: Amount transferred to the recipient (0 if nothing is withdrawable)
+    ///
+    /// # Authorization
+    /// - None; any address may trigger it
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `Unauthorized` if the stream was created without `auto_withdraw`
+    /// - `WithdrawalCooldown` if less than `auto_withdraw_interval` seconds have
+    ///   passed since the last withdrawal, or since `start_time` before the first
+    /// - Any error `withdraw` returns for the stream's status
+    ///
+    /// # Events
+    /// - Same as `withdraw`
+    pub fn trigger_auto_withdraw(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        if !stream.auto_withdraw {
+            return Err(ContractError::Unauthorized);
+        }
+        let last = stream.last_withdrawal_time.unwrap_or(stream.start_time);
+        if env.ledger().timestamp().saturating_sub(last) < stream.auto_withdraw_interval {
+            return Err(ContractError::WithdrawalCooldown);
+        }
+
+        let recipient = stream.recipient.clone();
+        Self::withdraw_and_transfer(&env, &mut stream, &recipient)
+    }
+
+    /// `withdraw_to`, authorized by the stream's approved operator.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `operator`
+    ///
+    /// # Errors
+    /// - `Unauthorized` if `operator` is not the stream's approved operator
+    /// - Same as `withdraw_to` otherwise
+    ///
+    /// # Events
+    /// - Same as `withdraw`
+    pub fn withdraw_to_as_operator(
+        env: Env,
+        stream_id: u64,
+        operator: Address,
+        destination: Address,
+    ) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        if stream.approved_operator.as_ref() != Some(&operator) {
+            return Err(ContractError::Unauthorized);
+        }
+        operator.require_auth();
+
+        Self::withdraw_and_transfer(&env, &mut stream, &destination)
+    }
+
+    /// Allow `operator` to call `withdraw_as_operator` and
+    /// `withdraw_to_as_operator` for this stream.
+    ///
+    /// Replaces any previously approved operator. The operator can only move
+    /// accrued tokens out of the stream; it cannot pause, cancel or modify it.
+    /// Note that `withdraw_to_as_operator` lets it choose the destination, so
+    /// only approve addresses trusted with the accrued balance.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidParams` if `operator` is the recipient itself
+    /// - `StreamCompleted` / `StreamCancelled` if the stream is terminal
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "operator_approved", stream_id)` with a
+    ///   `StreamOperatorUpdated` payload
+    pub fn approve_operator(
+        env: Env,
+        stream_id: u64,
+        operator: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        match stream.status {
+            StreamStatus::Completed => return Err(ContractError::StreamCompleted),
+            StreamStatus::Cancelled => return Err(ContractError::StreamCancelled),
+            StreamStatus::Expired => return Err(ContractError::StreamExpired),
+            StreamStatus::Active
+            | StreamStatus::Paused
+            | StreamStatus::Pending
+            | StreamStatus::Scheduled => {}
+        }
+        if operator == stream.recipient {
+            return Err(ContractError::InvalidParams);
+        }
+
+        stream.approved_operator = Some(operator.clone());
+        save_stream(&env, &stream);
+
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "operator_approved"),
+            stream_id,
+            StreamOperatorUpdated {
+                stream_id,
+                operator,
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove the stream's approved operator, if any.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's recipient
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "operator_revoked", stream_id)` with a
+    ///   `StreamOperatorUpdated` payload when an operator was removed
+    pub fn revoke_operator(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        let Some(operator) = stream.approved_operator.take() else {
+            return Ok(());
+        };
+        save_stream(&env, &stream);
+
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "operator_revoked"),
+            stream_id,
+            StreamOperatorUpdated {
+                stream_id,
+                operator,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return the operator approved to withdraw on the recipient's behalf, if any.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_operator(env: Env, stream_id: u64) -> Result<Option<Address>, ContractError> {
+        Ok(load_stream(&env, stream_id)?.approved_operator)
+    }
+
+    /// Redirect a stream to a new recipient, e.g. after a key rotation.
+    ///
+    /// Everything not yet withdrawn, including tokens already accrued, becomes
+    /// claimable by `new_recipient` only. The stream moves from the old to the new
+    /// recipient's `get_streams_by_recipient` index.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current recipient
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidParams` if `new_recipient` is the current recipient or the sender
+    /// - `RecipientBlacklisted` if `new_recipient` is blacklisted
+    /// - `StreamCompleted` / `StreamCancelled` if the stream is terminal
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "recipient_updated", stream_id)` with a
+    ///   `RecipientUpdated` payload
+    ///
+    /// # Usage Notes
+    /// - Clears the approved operator, which the old recipient chose
+    /// - A pause stays in place; only the sender or admin can pause, so the pause
+    ///   bookkeeping is unaffected
+    /// - A `Pending` stream must then be accepted by `new_recipient`
+    pub fn update_recipient(
+        env: Env,
+        stream_id: u64,
+        new_recipient: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.recipient.require_auth();
+
+        match stream.status {
+            StreamStatus::Completed => return Err(ContractError::StreamCompleted),
+            StreamStatus::Cancelled => return Err(ContractError::StreamCancelled),
+            StreamStatus::Expired => return Err(ContractError::StreamExpired),
+            StreamStatus::Active
+            | StreamStatus::Paused
+            | StreamStatus::Pending
+            | StreamStatus::Scheduled => {}
+        }
+        if new_recipient == stream.recipient || new_recipient == stream.sender {
+            return Err(ContractError::InvalidParams);
+        }
+        Self::require_recipient_allowed(&env, &new_recipient)?;
+
+        let old_recipient = stream.recipient.clone();
+        stream.recipient = new_recipient.clone();
+        stream.approved_operator = None;
+        save_stream(&env, &stream);
+        remove_stream_index(
+            &env,
+            &DataKey::RecipientStreams(old_recipient.clone()),
+            stream_id,
+        );
+        push_stream_index(
+            &env,
+            &DataKey::RecipientStreams(new_recipient.clone()),
+            stream_id,
+        );
+
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "recipient_updated"),
+            stream_id,
+            RecipientUpdated {
+                stream_id,
+                old_recipient,
+                new_recipient,
+            },
+        );
+        Ok(())
+    }
+
+    /// Set or replace the stream's free-form description, e.g. as project scope
+    /// changes.
+    ///
+    /// Unlike the memo and the agreement hash, which are fixed at creation, the
+    /// description can be rewritten as often as the sender likes while the stream
+    /// is live. An empty `description` clears it.
+    ///
+    /// # Parameters
+    /// - `description`: At most `MAX_DESCRIPTION_LEN` (256) bytes; never
+    ///   interpreted by the contract
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidParams` if `description` is longer than `MAX_DESCRIPTION_LEN` bytes
+    /// - `StreamCompleted` / `StreamCancelled` / `StreamExpired` if the stream is
+    ///   terminal
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "description_updated", stream_id)` with a
+    ///   `StreamDescriptionUpdated` payload
+    pub fn set_stream_description(
+        env: Env,
+        stream_id: u64,
+        description: Bytes,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let stream = load_stream(&env, stream_id)?;
+        stream.sender.require_auth();
+
+        match stream.status {
+            StreamStatus::Completed => return Err(ContractError::StreamCompleted),
+            StreamStatus::Cancelled => return Err(ContractError::StreamCancelled),
+            StreamStatus::Expired => return Err(ContractError::StreamExpired),
+            StreamStatus::Active
+            | StreamStatus::Paused
+            | StreamStatus::Pending
+            | StreamStatus::Scheduled => {}
+        }
+        if description.len() > MAX_DESCRIPTION_LEN {
+            return Err(ContractError::InvalidParams);
+        }
+
+        save_stream_description(&env, stream_id, &description);
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "description_updated"),
+            stream_id,
+            StreamDescriptionUpdated {
+                stream_id,
+                description,
+            },
+        );
+        Ok(())
+    }
+
+    /// Hand a stream over to a new sender, e.g. after a key rotation.
+    ///
+    /// `new_sender` takes over every sender right (pause, resume, cancel, top up,
+    /// extend) and receives any future cancellation refund. The stream moves from
+    /// the old to the new sender's `get_streams_by_sender` index.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current sender
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidParams` if `new_sender` is the current sender or the recipient
+    /// - `StreamCompleted` / `StreamCancelled` if the stream is terminal
+    /// - `StreamLimitExceeded` if `new_sender` already holds `max_streams_per_sender` streams
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "sender_transferred", stream_id)` with a
+    ///   `SenderTransferred` payload
+    pub fn transfer_stream_sender(
+        env: Env,
+        stream_id: u64,
+        new_sender: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.sender.require_auth();
+
+        match stream.status {
+            StreamStatus::Completed => return Err(ContractError::StreamCompleted),
+            StreamStatus::Cancelled => return Err(ContractError::StreamCancelled),
+            StreamStatus::Expired => return Err(ContractError::StreamExpired),
+            StreamStatus::Active
+            | StreamStatus::Paused
+            | StreamStatus::Pending
+            | StreamStatus::Scheduled => {}
+        }
+        if new_sender == stream.sender || new_sender == stream.recipient {
+            return Err(ContractError::InvalidParams);
+        }
+        Self::require_sender_capacity(&env, &get_config(&env), &new_sender, 1)?;
+
+        let old_sender = stream.sender.clone();
+        stream.sender = new_sender.clone();
+        save_stream(&env, &stream);
+        remove_stream_index(&env, &DataKey::SenderStreams(old_sender.clone()), stream_id);
+        push_stream_index(&env, &DataKey::SenderStreams(new_sender.clone()), stream_id);
+
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "sender_transferred"),
+            stream_id,
+            SenderTransferred {
+                stream_id,
+                old_sender,
+                new_sender,
+            },
+        );
+        Ok(())
+    }
+
+    /// Withdraw accrued tokens from several streams owned by the same recipient.
+    ///
+    /// Runs the same accrual and state-update logic as `withdraw` for every stream,
+    /// then transfers the aggregate amount to the recipient with a single token
+    /// transfer.
+    ///
+    /// # Parameters
+    /// - `stream_ids`: Streams to withdraw from; all must share the same recipient
+    ///
+    /// # Returns
+    /// - `Vec<i128>`: Amount paid out from each stream (net of any withdrawal fee),
+    ///   in the same order as `stream_ids`
+    ///
+    /// # Authorization
+    /// - Requires authorization from the common recipient exactly once for the batch
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if any stream does not exist
+    /// - `Unauthorized` if the streams do not all share the same recipient
+    /// - `StreamPaused` / `StreamCompleted` for any stream `withdraw` would reject
+    /// - `ArithmeticOverflow` if the summed amounts overflow
+    ///
+    /// # Usage Notes
+    /// - The batch is atomic: a failure on any stream rolls back every stream
+    /// - Streams with nothing to withdraw contribute `0` and are left untouched
+    /// - An empty `stream_ids` returns an empty vector without any transfer
+    /// - Publishes the same `withdrawn` / `completed` events as `withdraw` per stream
+    pub fn batch_withdraw(
+        env: Env,
+        stream_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<soroban_sdk::Vec<i128>, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut amounts = soroban_sdk::Vec::new(&env);
+        if stream_ids.is_empty() {
+            return Ok(amounts);
+        }
+
+        let recipient = load_stream(&env, stream_ids.get_unchecked(0))?.recipient;
+        recipient.require_auth();
+
+        let mut totals = Map::new(&env);
+        for stream_id in stream_ids.iter() {
+            let mut stream = load_stream(&env, stream_id)?;
+            if stream.recipient != recipient {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let withdrawable = Self::apply_withdrawal(&env, &mut stream)?;
+            let mut payout = withdrawable;
+            if withdrawable > 0 {
+                let fee = Self::withdrawal_fee(&env, &stream, withdrawable)?;
+                Self::accrue_protocol_fees(&env, fee)?;
+                payout -= fee;
+                add_token_total(&mut totals, &stream.token, payout)?;
+                Self::record_withdrawal(&env, &stream, &recipient, payout, fee);
+                Self::publish_withdrawal(&env, &stream, payout, fee);
+            }
+            amounts.push_back(payout);
+        }
+
+        transfer_token_totals(&env, &totals, &recipient);
+
+        Ok(amounts)
+    }
+
+    /// Add funds to a running stream and extend its end time accordingly.
+    ///
+    /// Transfers `additional_amount` tokens from the sender to the contract, adds
+    /// them to `deposit_amount` and pushes `end_time` out by
+    /// `additional_amount / rate_per_second` seconds, so the stream keeps paying at
+    /// the same rate for longer instead of being cancelled and recreated.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to top up
+    /// - `additional_amount`: Tokens to add to the stream (must be > 0)
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidParams` if `additional_amount <= 0`
+    /// - `StreamCancelled` / `StreamCompleted` if the stream is in a terminal state
+    /// - `InvalidState` for stepped and `Exponential` streams, whose schedule a
+    ///   new end time would reshape
+    /// - `ArithmeticOverflow` if the new deposit or end time overflows
+    ///
+    /// # Panics
+    /// - If token transfer fails (insufficient balance or allowance)
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "topped_up", stream_id)` with a `StreamToppedUp` payload
+    ///
+    /// # Usage Notes
+    /// - The new end time is `max(now, end_time) + additional_amount / rate_per_second`,
+    ///   but accrual still runs from `start_time`: when topping up after the original
+    ///   end, the part of `additional_amount` covering `now - end_time` accrues at once
+    ///   and is immediately withdrawable; only the rest streams from `now`
+    /// - Any remainder of `additional_amount` not divisible by the rate still counts
+    ///   towards `deposit_amount` (accrual is capped at the deposit)
+    /// - Paused streams can be topped up
+    pub fn topup_stream(
+        env: Env,
+        stream_id: u64,
+        additional_amount: i128,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.sender.require_auth();
+
+        Self::require_extendable_status(stream.status)?;
+        if stream.intervals > 0 || stream.curve_type != CurveType::Linear {
+            return Err(ContractError::InvalidState);
+        }
+        if additional_amount <= 0 {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let extra_duration = additional_amount
+            .checked_mul(stream.rate_denominator as i128)
+            .and_then(|scaled| u64::try_from(scaled / stream.rate_per_second).ok())
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        let base_time = stream
+            .end_time
+            .max(Self::schedule_time(&stream, env.ledger().timestamp()));
+        let new_end_time = base_time
+            .checked_add(extra_duration)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+
+        Self::apply_extension(
+            &env,
+            &mut stream,
+            new_end_time,
+            additional_amount,
+            symbol_short!("topped_up"),
+        )
+    }
+
+    /// Extend a running stream to an exact new end time.
+    ///
+    /// Unlike `topup_stream`, which derives the new end time from the amount added,
+    /// the sender picks `new_end_time` (e.g. to align with a calendar date) and the
+    /// contract charges `rate_per_second × (new_end_time - end_time)` for it.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to extend
+    /// - `new_end_time`: Requested end time; must be after both the current `end_time`
+    ///   and the current ledger time
+    /// - `extra_deposit`: Maximum amount the sender is willing to add; must cover the
+    ///   required additional deposit
+    ///
+    /// # Returns
+    /// - `i128`: The additional deposit actually charged
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `StreamCancelled` / `StreamCompleted` if the stream is in a terminal state
+    /// - `InvalidParams` if `new_end_time <= end_time` or `new_end_time <= now`
+    /// - `InvalidState` for stepped and `Exponential` streams
+    /// - `UnfundedDeposit` if `extra_deposit` is less than the required additional deposit
+    /// - `ArithmeticOverflow` if the required deposit or new total deposit overflows
+    ///
+    /// # Panics
+    /// - If token transfer fails (insufficient balance or allowance)
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "extended", stream_id)` with a `StreamToppedUp` payload
+    ///
+    /// # Usage Notes
+    /// - Only the required amount is transferred from the sender; any overpayment in
+    ///   `extra_deposit` never leaves the sender's balance
+    pub fn extend_stream_end_time(
+        env: Env,
+        stream_id: u64,
+        new_end_time: u64,
+        extra_deposit: i128,
+    ) -> Result<i128, ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        stream.sender.require_auth();
+
+        Self::require_extendable_status(stream.status)?;
+        if stream.intervals > 0 || stream.curve_type != CurveType::Linear {
+            return Err(ContractError::InvalidState);
+        }
+        if new_end_time <= stream.end_time
+            || new_end_time <= Self::schedule_time(&stream, env.ledger().timestamp())
+        {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let required = Self::streamable_amount(
+            stream.rate_per_second,
+            stream.rate_denominator,
+            new_end_time - stream.end_time,
+        )?;
+        if extra_deposit < required {
+            return Err(ContractError::UnfundedDeposit);
+        }
+
+        Self::apply_extension(
+            &env,
+            &mut stream,
+            new_end_time,
+            required,
+            symbol_short!("extended"),
+        )?;
+        Ok(required)
+    }
+
+    /// Calculate the total amount accrued to the recipient at the current time.
+    ///
+    /// # Behaviour by status
+    ///
+    /// | Status      | Return value                                         |
+    /// |-------------|------------------------------------------------------|
+    /// | `Active`    | `min((min(now,end)-start) × rate, deposit_amount)`   |
+    /// | `Paused`    | Same time-based formula (accrual is not paused)      |
+    /// | `Completed` | `deposit_amount` — all tokens were accrued/withdrawn |
+    /// | `Cancelled` | Final accrued at cancellation timestamp (frozen value) |
+    /// | `Scheduled` | 0 before `start_time`; afterwards the stream is activated first |
+    ///
+    /// ## Rationale for `Cancelled`
+    /// On cancellation, unstreamed tokens are refunded immediately to the sender.
+    /// The recipient can claim only what was already accrued at cancellation time.
+    /// Returning a frozen final accrued value keeps `calculate_accrued` consistent
+    /// with contract balances and prevents post-cancel time growth.
+    ///
+    /// # Calculation
+    /// - Before `cliff_time`: returns 0 (no accrual before cliff)
+    /// - After `cliff_time`: `min((now - start_time) × rate_per_second, deposit_amount)`
+    /// - After `end_time`: elapsed time is capped at `end_time` (no accrual beyond end)
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    ///
+    /// # Usage Notes
+    /// - Read-only, except that a `Scheduled` stream whose start time has passed is
+    ///   moved to `Active` (publishing `activated`)
+    /// - No authorization required (public information)
+    /// - Returns total accrued, not withdrawable amount
+    /// - To get withdrawable amount: `calculate_accrued() - stream.withdrawn_amount`
+    /// - Active/Paused streams accrue by current time; Completed/Cancelled are deterministic
+    /// - Useful for UIs to show real-time accrual without transactions
+    ///
+    /// # Examples
+    /// - Stream: 1000 tokens, 0-1000s, rate 1 token/sec, cliff at 500s
+    /// - At t=300: returns 0 (before cliff)
+    /// - At t=500: returns 500 (at cliff, accrual from start_time)
+    /// - At t=800: returns 800
+    /// - At t=1500: returns 1000 (elapsed time capped at end_time)
+    /// ## Rationale for `Completed`
+    /// When a stream reaches `Completed`, `withdrawn_amount == deposit_amount`.
+    /// There is no further accrual possible. Returning `deposit_amount` is the
+    /// deterministic, timestamp-independent answer for any UI or downstream caller.
+    pub fn calculate_accrued(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::activate_if_started(&env, &mut stream);
+        Self::accrued_at(&stream, env.ledger().timestamp())
+    }
+
+    /// Move a `Scheduled` stream to `Active` once its start time has arrived.
+    ///
+    /// The same transition happens lazily on `withdraw` and `calculate_accrued`;
+    /// this entry point lets keepers and indexers trigger it explicitly.
+    ///
+    /// # Authorization
+    /// - None; anyone may activate a stream whose start time has passed
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is not `Scheduled`
+    /// - `StreamScheduled` if `start_time` is still in the future
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "activated", stream_id)` with a `StreamActivated` payload
+    pub fn activate_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::require_protocol_active(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        if stream.status != StreamStatus::Scheduled {
+            return Err(ContractError::InvalidState);
+        }
+        if !Self::activate_if_started(&env, &mut stream) {
+            return Err(ContractError::StreamScheduled);
+        }
+        Ok(())
+    }
+
+    /// Calculate the total amount accrued to the recipient at an arbitrary timestamp.
+    ///
+    /// Applies the same cliff and cap rules as `calculate_accrued`, but evaluates them
+    /// at `timestamp` instead of the current ledger time. Useful for projections
+    /// ("how much will have streamed by Friday?") and for auditing historical accrual.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to query
+    /// - `timestamp`: Point in time (ledger timestamp, seconds) to evaluate accrual at
+    ///
+    /// # Returns
+    /// - `0` if `timestamp < start_time` or `timestamp < cliff_time`
+    /// - `deposit_amount` if `timestamp >= end_time` (or the stream `Completed` at or
+    ///   before `timestamp`)
+    /// - Otherwise `min(rate_per_second × (timestamp - start_time), deposit_amount)`
+    /// - For `Cancelled` streams, `timestamp` is clamped to the cancellation time
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - `calculate_accrued_at(id, now)` always equals `calculate_accrued(id)` at ledger time `now`
+    pub fn calculate_accrued_at(
+        env: Env,
+        stream_id: u64,
+        timestamp: u64,
+    ) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::accrued_at(&stream, timestamp)
+    }
+
+    /// Project the outcome of cancelling the stream at `timestamp`.
+    ///
+    /// Pure computation on top of `calculate_accrued_at`: nothing is transferred or
+    /// written, so senders can preview a cancellation before committing to it.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to query
+    /// - `timestamp`: Point in time (ledger timestamp, seconds) of the hypothetical cancel
+    ///
+    /// # Returns
+    /// - `(sender_refund, recipient_accrued)`, the amounts `cancel_stream` would
+    ///   report in its `cancelled` event at `timestamp`
+    /// - `sender_refund == deposit_amount` if `timestamp < start_time`, and `0` if
+    ///   `timestamp >= end_time`
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is `Cancelled`, `Completed` or `Pending`
+    ///   (there is nothing to simulate)
+    /// - `StreamNotCancellable` if the stream was created with `cancellable = false`
+    pub fn simulate_cancel(
+        env: Env,
+        stream_id: u64,
+        timestamp: u64,
+    ) -> Result<(i128, i128), ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::require_cancellable_status(stream.status)?;
+        if !stream.cancellable {
+            return Err(ContractError::StreamNotCancellable);
+        }
+
+        let streamed = Self::accrued_at(&stream, timestamp)?;
+        let recipient_accrued =
+            streamed + Self::cancel_penalty(&stream, stream.deposit_amount - streamed)?;
+        Ok((stream.deposit_amount - recipient_accrued, recipient_accrued))
+    }
+
+    /// Return the amount the recipient could withdraw right now.
+    ///
+    /// Equivalent to `calculate_accrued(stream_id) - withdrawn_amount`, adjusted for the
+    /// statuses in which `withdraw` would not transfer anything.
+    ///
+    /// # Behaviour by status
+    ///
+    /// | Status      | Return value                                     |
+    /// |-------------|--------------------------------------------------|
+    /// | `Active`    | `accrued - withdrawn_amount`                     |
+    /// | `Paused`    | `0` — withdrawals are blocked while paused       |
+    /// | `Completed` | `0` — everything has already been withdrawn      |
+    /// | `Cancelled` | Accrued at cancellation minus `withdrawn_amount` |
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Returns `0` before the cliff
+    pub fn get_withdrawable_amount(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::withdrawable_now(&env, &stream)
+    }
+
+    /// Return whether `withdraw` would transfer a positive amount right now.
+    ///
+    /// Complements `get_withdrawable_amount` (how much) with a yes/no answer that
+    /// also accounts for the protocol-wide pause, the withdrawal cooldown and the
+    /// dust threshold.
+    ///
+    /// # Behaviour by status
+    ///
+    /// | Status                   | Return value                                  |
+    /// |--------------------------|-----------------------------------------------|
+    /// | `Active` / `Scheduled`   | `true` once past the cliff with a balance     |
+    /// | `Paused` / `Pending`     | `false`                                       |
+    /// | `Completed`              | `false`                                       |
+    /// | `Cancelled`              | `true` while accrued tokens remain unclaimed  |
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Always `false` while the protocol is paused (`withdraw` fails with `ProtocolPaused`)
+    /// - `false` within `Config.withdrawal_cooldown` of the last withdrawal
+    ///   (`withdraw` fails with `WithdrawalCooldown`)
+    /// - `false` while the amount is below `Config.min_withdraw_amount`, unless
+    ///   `withdraw` would exempt it as the stream's last withdrawal
+    /// - A cancelled stream keeps its frozen accrual claimable, so it reports `true`
+    ///   until the recipient withdraws it
+    pub fn is_withdrawable(env: Env, stream_id: u64) -> Result<bool, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        if get_config(&env).protocol_paused {
+            return Ok(false);
+        }
+        let withdrawable = Self::withdrawable_now(&env, &stream)?;
+        if withdrawable <= 0 {
+            return Ok(false);
+        }
+        // Same amount `withdraw` checks: any excess refund counts towards it.
+        let excess = Self::excess_deposit(&env, &stream, stream.withdrawn_amount + withdrawable);
+        Ok(Self::require_cooldown_elapsed(&env, &stream).is_ok()
+            && Self::require_above_dust(&env, &stream, withdrawable + excess).is_ok())
+    }
+
+    /// Return the amount the sender would get back if the stream were cancelled now.
+    ///
+    /// Computes `deposit_amount - calculate_accrued(stream_id)` at the current ledger
+    /// time, less the stream's `cancel_penalty_bps` share of it, i.e. exactly the
+    /// refund `cancel_stream` would transfer.
+    ///
+    /// # Behaviour by status
+    ///
+    /// | Status                 | Return value                                 |
+    /// |------------------------|----------------------------------------------|
+    /// | `Active` / `Paused`    | `max(deposit_amount - accrued, 0)` - penalty |
+    /// | `Completed`            | `0` — nothing left to refund                 |
+    /// | `Cancelled`            | `0` — the refund has already been paid out   |
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid)
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Returns the full deposit before `start_time` (and before the cliff)
+    /// - Returns `0` once the stream is fully accrued (at or after `end_time`)
+    pub fn get_refundable_amount(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::refundable_now(&env, &stream)
+    }
+
+    /// Return how much the recipient can still receive from the stream, now or
+    /// later: `deposit_amount - withdrawn_amount` while the stream is live.
+    ///
+    /// For live streams this equals `get_amount_streaming` plus
+    /// `get_withdrawable_amount` (ignoring pauses), so `withdrawn + streaming +
+    /// accrued_not_withdrawn` adds up to `deposit_amount`.
+    ///
+    /// # Behaviour by status
+    ///
+    /// | Status                 | Return value                                     |
+    /// |------------------------|--------------------------------------------------|
+    /// | Live (incl. `Paused`)  | `deposit_amount - withdrawn_amount`              |
+    /// | `Completed`            | `0`                                              |
+    /// | `Expired`              | `0` — everything was paid out by `expire_stream` |
+    /// | `Cancelled`            | `0` — use `get_withdrawable_amount` for the      |
+    /// |                        | frozen accrual still unclaimed                   |
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_amount_remaining(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        if stream.status == StreamStatus::Cancelled {
+            return Ok(0);
+        }
+        let accrued = Self::accrued_at(&stream, env.ledger().timestamp())?;
+        let unclaimed = (accrued - stream.withdrawn_amount).max(0);
+        Ok(Self::unstreamed_now(&env, &stream)? + unclaimed)
+    }
+
+    /// Return how much of the deposit has not accrued yet:
+    /// `deposit_amount - calculate_accrued(stream_id)`.
+    ///
+    /// Same value as `get_refundable_amount` unless the stream has a
+    /// `cancel_penalty_bps`, which only reduces the refund: `0` for completed,
+    /// cancelled and expired streams, since nothing is left to stream.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_amount_streaming(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::unstreamed_now(&env, &stream)
+    }
+
+    /// Return a stream's dashboard figures in a single call.
+    ///
+    /// Combines `calculate_accrued`, `get_withdrawable_amount` and
+    /// `get_refundable_amount` with the completion ratio and time left, all
+    /// computed from one read of the stream.
+    ///
+    /// # Behaviour by status
+    ///
+    /// | Status      | Values                                                                  |
+    /// |-------------|-------------------------------------------------------------------------|
+    /// | `Completed` | `accrued = deposit`, `withdrawable = refundable = 0`, `completion_bps = 10_000`, `time_remaining = 0` |
+    /// | `Cancelled` | `refundable = 0`, `completion_bps = 10_000`, `time_remaining = 0`; `accrued` and `withdrawable` stay frozen at cancellation so unclaimed funds remain visible |
+    /// | Otherwise   | Live values at the current ledger time                                  |
+    ///
+    /// `completion_bps = accrued × 10_000 / deposit_amount` and
+    /// `time_remaining = max(0, end_time - now)`.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_stream_analytics(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<StreamAnalytics, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        let now = env.ledger().timestamp();
+        let accrued = Self::accrued_at(&stream, now)?;
+        let withdrawable = Self::withdrawable_now(&env, &stream)?;
+
+        if matches!(
+            stream.status,
+            StreamStatus::Completed | StreamStatus::Cancelled | StreamStatus::Expired
+        ) {
+            return Ok(StreamAnalytics {
+                accrued,
+                withdrawable,
+                refundable: 0,
+                completion_bps: Self::stream_completion_bps(&stream, accrued),
+                time_remaining: 0,
+            });
+        }
+
+        Ok(StreamAnalytics {
+            accrued,
+            withdrawable,
+            refundable: Self::refundable_now(&env, &stream)?,
+            completion_bps: Self::stream_completion_bps(&stream, accrued),
+            time_remaining: stream.end_time.saturating_sub(now),
+        })
+    }
+
+    /// Return how far a stream has progressed, in basis points of its deposit.
+    ///
+    /// `accrued × 10_000 / deposit_amount`, clamped to `[0, 10_000]`; the same
+    /// value as `get_stream_analytics(..).completion_bps`. Returns `0` before
+    /// `start_time` (including `Scheduled` and `Pending` streams) and exactly
+    /// `10_000` once the deposit has fully accrued or the stream is `Completed`
+    /// or `Cancelled`.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_stream_completion_bps(env: Env, stream_id: u64) -> Result<u32, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        let accrued = Self::accrued_at(&stream, env.ledger().timestamp())?;
+        Ok(Self::stream_completion_bps(&stream, accrued))
+    }
+
+    /// Return how much of the deposit the recipient has actually withdrawn, in
+    /// basis points.
+    ///
+    /// `withdrawn_amount × 10_000 / deposit_amount`, clamped to `[0, 10_000]`.
+    /// Unlike `get_stream_completion_bps`, which follows accrual over time, this
+    /// stays `0` until the first withdrawal and reaches `10_000` only once the
+    /// whole deposit has been paid out.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_stream_utilization_bps(env: Env, stream_id: u64) -> Result<u32, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(Self::completion_bps(
+            stream.withdrawn_amount,
+            stream.deposit_amount,
+        ))
+    }
+
+    /// Return the seconds left until the stream's schedule ends, for countdowns.
+    ///
+    /// `end_time - now`, or `Some(0)` once `end_time` has passed but the stream
+    /// has not been drained. For `AccrualFreeze` streams paused time pushes the
+    /// end back, and the countdown stands still while the stream is paused.
+    ///
+    /// # Returns
+    /// - `None` for terminal streams (`Completed`, `Cancelled`, `Expired`)
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_time_to_completion(env: Env, stream_id: u64) -> Result<Option<u64>, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        if matches!(
+            stream.status,
+            StreamStatus::Completed | StreamStatus::Cancelled | StreamStatus::Expired
+        ) {
+            return Ok(None);
+        }
+        let elapsed_to = Self::schedule_time(&stream, env.ledger().timestamp());
+        Ok(Some(stream.end_time.saturating_sub(elapsed_to)))
+    }
+
+    /// Return the lump sum the stream releases at its cliff (0 for plain streams).
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_cliff_unlock_amount(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        Ok(load_stream(&env, stream_id)?.cliff_unlock_amount)
+    }
+
+    /// Return the stream's status transitions as `(ledger timestamp, new status)`,
+    /// oldest first.
+    ///
+    /// The first entry is the status the stream was created in (`Active`,
+    /// `Scheduled` or `Pending`); every later transition appends one entry. At
+    /// most `MAX_STATUS_HISTORY` entries are kept, dropping the oldest.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_status_history(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<soroban_sdk::Vec<(u64, StreamStatus)>, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusHistory(stream_id))
+            .unwrap_or_else(|| initial_status_history(&env, &stream)))
+    }
+
+    /// Return the stream's most recent paying withdrawals, oldest first.
+    ///
+    /// Covers every withdraw entry point. At most `MAX_WITHDRAWAL_HISTORY` records
+    /// are kept; `is_withdrawal_history_truncated` tells whether older ones were
+    /// dropped.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_withdrawal_history(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<soroban_sdk::Vec<WithdrawalRecord>, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::WithdrawalHistory(stream_id))
+            .unwrap_or(soroban_sdk::Vec::new(&env)))
+    }
+
+    /// Whether `get_withdrawal_history` has dropped records to stay within
+    /// `MAX_WITHDRAWAL_HISTORY`.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn is_withdrawal_history_truncated(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<bool, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(env
+            .storage()
+            .persistent()
+            .has(&DataKey::WithdrawalHistoryTruncated(stream_id)))
+    }
+
+    /// Whether the stream has a cliff, i.e. `cliff_time > start_time`.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn has_cliff(env: Env, stream_id: u64) -> Result<bool, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(stream.cliff_time > stream.start_time)
+    }
+
+    /// Whether the ledger time has reached the stream's `cliff_time`.
+    ///
+    /// Compares against the stored `cliff_time`; `AccrualFreeze` pauses that push
+    /// the effective cliff back are not taken into account.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn is_past_cliff(env: Env, stream_id: u64) -> Result<bool, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(env.ledger().timestamp() >= stream.cliff_time)
+    }
+
+    /// Seconds until the stream's `cliff_time`, or 0 once it has passed, for
+    /// countdown displays.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn cliff_unlock_remaining(env: Env, stream_id: u64) -> Result<u64, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(stream.cliff_time.saturating_sub(env.ledger().timestamp()))
+    }
+
+    /// Whether the stream can be cancelled (`false` for irrevocable streams).
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_stream_cancellable(env: Env, stream_id: u64) -> Result<bool, ContractError> {
+        Ok(load_stream(&env, stream_id)?.cancellable)
+    }
+
+    /// Return the ID of the stream linked to this one by `create_swap_stream`, or
+    /// `None` for unlinked streams.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_linked_stream_id(env: Env, stream_id: u64) -> Result<Option<u64>, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(load_linked_stream(&env, stream_id))
+    }
+
+    /// Return the last ledger time at which the sender may cancel the stream, or
+    /// `None` if the stream has no `cancel_after` deadline.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_cancel_deadline(env: Env, stream_id: u64) -> Result<Option<u64>, ContractError> {
+        let cancel_after = load_stream(&env, stream_id)?.cancel_after;
+        Ok((cancel_after != 0).then_some(cancel_after))
+    }
+
+    /// Return how many withdrawals have paid out from the stream.
+    ///
+    /// Counts every non-zero payout (`withdraw`, `withdraw_to`, the operator
+    /// variants, `batch_withdraw` and the payout of `cancel_stream_as_recipient`);
+    /// calls that return 0 or fail leave it unchanged.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_withdrawal_count(env: Env, stream_id: u64) -> Result<u32, ContractError> {
+        Ok(load_stream(&env, stream_id)?.withdraw_count)
+    }
+
+    /// Seconds since the stream's latest withdrawal, or `None` if nothing has been
+    /// withdrawn yet (see `last_withdrawal_time`).
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn time_since_last_withdrawal(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<Option<u64>, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        let now = env.ledger().timestamp();
+        Ok(stream.last_withdrawal_time.map(|at| now.saturating_sub(at)))
+    }
+
+    /// Return the memo attached by `create_stream_with_memo` (empty if none).
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_stream_memo(env: Env, stream_id: u64) -> Result<Bytes, ContractError> {
+        Ok(load_stream(&env, stream_id)?.memo)
+    }
+
+    /// Return the agreement hash anchored by `create_stream_with_agreement`
+    /// (`None` if the stream was created any other way).
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_agreement_hash(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<Option<BytesN<32>>, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(load_agreement_hash(&env, stream_id))
+    }
+
+    /// Return the description set by `set_stream_description` (empty if none).
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_stream_description(env: Env, stream_id: u64) -> Result<Bytes, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(load_stream_description(&env, stream_id))
+    }
+
+    /// Ledger time at which the stream next unlocks tokens.
+    ///
+    /// For stepped streams this is the time of the next tranche; for linear streams
+    /// it is the cliff, or the next second once the cliff has passed.
+    ///
+    /// # Returns
+    /// - `u64`: Next unlock time, or 0 if nothing remains to unlock or the stream is
+    ///   not `Active` / `Paused`
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    ///
+    /// # Usage Notes
+    /// - For a frozen `AccrualFreeze` stream the time assumes it resumes now
+    pub fn get_next_unlock_time(env: Env, stream_id: u64) -> Result<u64, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(Self::next_unlock(&env, &stream).map_or(0, |(time, _)| time))
+    }
+
+    /// Amount the stream's next unlock (see `get_next_unlock_time`) releases.
+    ///
+    /// # Returns
+    /// - `i128`: Tranche size for stepped streams (including any remainder on the
+    ///   last tranche), the cliff amount or one second of rate for linear streams,
+    ///   or 0 if nothing remains to unlock
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_next_unlock_amount(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(Self::next_unlock(&env, &stream).map_or(0, |(_, amount)| amount))
+    }
+
+    /// Retrieve the global contract configuration.
+    ///
+    /// Returns the contract's configuration containing the token address used for all
+    /// streams and the admin address authorized for administrative operations.
+    ///
+    /// # Returns
+    /// - `Config`: Structure containing:
+    ///   - `token`: Address of the token contract used for all payment streams
+    ///   - `admin`: Address authorized to perform admin operations (pause, cancel, resume)
+    ///   - `pending_admin`: Address proposed via `propose_admin`, if any
+    ///   - `recipient_cancel_allowed`: Whether `cancel_stream_as_recipient` is enabled
+    ///   - `fee_bps`: Protocol fee on new deposits, in basis points
+    ///
+    /// # Errors
+    /// - `NotInitialized` if the contract has not been initialized (missing config)
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Config is set once during `init()` and can be updated via `set_admin()`
+    /// - Useful for integrators to verify token and admin addresses
+    pub fn get_config(env: Env) -> Config {
+        get_config(&env)
+    }
+
+    /// Return the ID the next created stream will receive.
+    ///
+    /// Reads the `NextStreamId` counter without incrementing it, so integrators
+    /// can pre-compute the ID of a `create_stream` before broadcasting it.
+    ///
+    /// # Returns
+    /// - `u64`: Next stream ID (equals the number of streams created so far);
+    ///   `0` before `init` or before any stream exists
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Never fails, including before `init`
+    pub fn get_next_stream_id(env: Env) -> u64 {
+        get_stream_count(&env)
+    }
+
+    /// Return `Config.global_stream_cap`: the number of created streams at which
+    /// stream creation stops with `GlobalCapReached`.
+    pub fn get_global_stream_cap(env: Env) -> u64 {
+        get_config(&env).global_stream_cap
+    }
+
+    /// Check `create_stream` parameters without creating anything.
+    ///
+    /// Runs the same parameter checks as `create_stream` (including the protocol
+    /// fee deduction and the per-sender stream cap) but collects every violated
+    /// constraint instead of stopping at the first one, so UIs can report them
+    /// together.
+    ///
+    /// # Parameters
+    /// - Same as `create_stream`
+    ///
+    /// # Returns
+    /// - `Vec<ContractError>`: Each violated constraint once, in check order;
+    ///   empty if `create_stream` would accept the parameters. Possible entries are
+    ///   `InvalidParams`, `UnfundedDeposit`, `ExcessDeposit`, `ArithmeticOverflow`,
+    ///   `SenderNotWhitelisted`, `RecipientBlacklisted` and `StreamLimitExceeded`
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Does not read the sender's token balance or allowance, so an empty result
+    ///   does not guarantee the transfer in `create_stream` succeeds
+    /// - Panics with `NotInitialized` before `init`
+    #[allow(clippy::too_many_arguments)]
+    pub fn validate_stream_params(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+    ) -> soroban_sdk::Vec<ContractError> {
+        let config = get_config(&env);
+        let mut errors = soroban_sdk::Vec::new(&env);
+        let mut report = |error: ContractError| {
+            if !errors.contains(error) {
+                errors.push_back(error);
+            }
+        };
+
+        if sender == recipient || deposit_amount <= 0 || rate_per_second <= 0 {
+            report(ContractError::InvalidParams);
+        }
+        if Self::require_minimums(&config, deposit_amount, rate_per_second, 1, false).is_err() {
+            report(ContractError::InvalidParams);
+        }
+        let cliff_time = Self::effective_cliff(start_time, cliff_time);
+        if start_time >= end_time || cliff_time < start_time || cliff_time > end_time {
+            report(ContractError::InvalidParams);
+        }
+        if Self::require_min_duration(&config, start_time, end_time).is_err() {
+            report(ContractError::InvalidParams);
+        }
+
+        // The funding rule only makes sense for a positive rate over a non-empty window.
+        if rate_per_second > 0 && start_time < end_time {
+            match Self::split_protocol_fee(&config, deposit_amount).and_then(|(net, _)| {
+                rate_per_second
+                    .checked_mul((end_time - start_time) as i128)
+                    .map(|required| net.cmp(&required))
+                    .ok_or(ContractError::ArithmeticOverflow)
+            }) {
+                Ok(Ordering::Less) => report(ContractError::UnfundedDeposit),
+                Ok(Ordering::Greater) if config.strict_deposit_mode => {
+                    report(ContractError::ExcessDeposit)
+                }
+                Ok(_) => {}
+                Err(error) => report(error),
+            }
+        }
+
+        if let Err(error) = Self::require_whitelisted_sender(&env, &config, &sender) {
+            report(error);
+        }
+        if let Err(error) = Self::require_recipient_allowed(&env, &recipient) {
+            report(error);
+        }
+        if let Err(error) = Self::require_sender_capacity(&env, &config, &sender, 1) {
+            report(error);
+        }
+        errors
+    }
+
+    /// Update the admin address for the contract.
+    ///
+    /// Allows the current admin to rotate the admin key by setting a new admin address.
+    /// This enables key rotation without redeploying the contract. Only the current admin
+    /// may call this function.
+    ///
+    /// # Parameters
+    /// - `new_admin`: The new admin address that will replace the current admin
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `NotInitialized` if the contract has not been initialized (missing config)
+    ///
+    /// # Panics
+    /// - If caller is not the current admin
+    ///
+    /// # State Changes
+    /// - Updates the admin address in the Config stored in instance storage
+    /// - Token address remains unchanged
+    ///
+    /// # Events
+    /// - Publishes `admin_updated(old_admin, new_admin)` event on success
+    ///
+    /// # Usage Notes
+    /// - This is a security-critical function for admin key rotation
+    /// - The new admin immediately gains all administrative privileges
+    /// - The old admin immediately loses all administrative privileges
+    /// - No restrictions on the new admin address (can be any valid address)
+    /// - Can be called multiple times to rotate keys as needed
+    ///
+    /// # Examples
+    /// - Rotate to a new admin key: `set_admin(env, new_admin_address)`
+    /// - Transfer admin to a multisig: `set_admin(env, multisig_address)`
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        let old_admin = config.admin.clone();
+
+        // Only current admin can update admin
+        old_admin.require_auth();
+
+        // Update admin in config; any pending two-step proposal is superseded
+        config.admin = new_admin.clone();
+        config.pending_admin = None;
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        // Bump TTL after instance write
+        bump_instance_ttl(&env);
+
+        // Emit event with old and new admin addresses
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("updated")),
+            (old_admin, new_admin),
+        );
+        Ok(())
+    }
+
+    /// Point the contract at a new token for streams created from now on.
+    ///
+    /// For token migrations (e.g. a rebasing or re-issued token). Existing streams
+    /// keep the token they were funded in (`Stream.token`): their withdrawals,
+    /// refunds and top-ups still move the old token. Only new streams use
+    /// `new_token`. Each call bumps `get_config_version`.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `new_token` is already the configured token
+    /// - `InvalidState` if protocol fees are still accrued in the old token;
+    ///   call `withdraw_protocol_fees` first
+    ///
+    /// # Events
+    /// - Publishes `("config", "token")` with a `TokenMigrated` payload
+    ///
+    /// # Usage Notes
+    /// - `get_total_value_locked`, `get_total_streamed` and `get_protocol_stats`
+    ///   keep summing raw amounts across tokens
+    pub fn admin_set_token(env: Env, new_token: Address) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
+
+        if new_token == config.token {
+            return Err(ContractError::InvalidParams);
+        }
+        if get_accumulated_fees(&env) != 0 {
+            return Err(ContractError::InvalidState);
+        }
+
+        let old_token = config.token.clone();
+        config.token = new_token.clone();
+        env.storage().instance().set(&DataKey::Config, &config);
+        let version = get_config_version(&env).saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::ConfigVersion, &version);
+        bump_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("token")),
+            TokenMigrated {
+                old_token,
+                new_token,
+                version,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return how many times `admin_set_token` has changed the token (0 until
+    /// the first migration).
+    pub fn get_config_version(env: Env) -> u32 {
+        get_config_version(&env)
+    }
+
+    /// Propose a new admin address (step 1 of a two-step admin transfer).
+    ///
+    /// Stores `new_admin` as `Config.pending_admin`. The current admin keeps all
+    /// privileges until the proposed address calls `accept_admin`, so a typo in
+    /// `new_admin` can never lock the contract out of administration.
+    ///
+    /// # Parameters
+    /// - `new_admin`: Address that will be allowed to accept the admin role
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    ///
+    /// # Events
+    /// - Publishes `("admin", "proposed")` with `(admin, new_admin)`
+    ///
+    /// # Usage Notes
+    /// - Proposing again replaces any earlier pending proposal
+    /// - Use `cancel_admin_proposal` to abort the transfer
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
+
+        config.pending_admin = Some(new_admin.clone());
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
+
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("proposed")),
+            (config.admin, new_admin),
+        );
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal (step 2 of a two-step admin transfer).
+    ///
+    /// Moves `Config.pending_admin` into `Config.admin` and clears the proposal.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the pending admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `NoPendingAdmin` if no proposal is pending
+    ///
+    /// # Events
+    /// - Publishes `("admin", "updated")` with `(old_admin, new_admin)`, as `set_admin` does
+    pub fn accept_admin(env: Env) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        let new_admin = config
+            .pending_admin
+            .clone()
+            .ok_or(ContractError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        let old_admin = config.admin.clone();
+        config.admin = new_admin.clone();
+        config.pending_admin = None;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
         env.events().publish(
-            (symbol_short!("created"), stream_id),
-            StreamCreated {
-                stream_id,
-                sender,
-                recipient,
-                deposit_amount,
-                rate_per_second,
-                start_time,
-                cliff_time,
-                end_time,
-            },
+            (symbol_short!("admin"), symbol_short!("updated")),
+            (old_admin, new_admin),
         );
-
-        stream_id
+        Ok(())
     }
-}
 
-// ---------------------------------------------------------------------------
-// Contract Implementation
-// ---------------------------------------------------------------------------
+    /// Enable or disable `cancel_stream_as_recipient` protocol-wide.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    ///
+    /// # Events
+    /// - Publishes `("config", "rcpt_cncl")` with the new value
+    pub fn set_recipient_cancel_allowed(env: Env, allowed: bool) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-#[contract]
-pub struct FluxoraStream;
+        config.recipient_cancel_allowed = allowed;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-#[contractimpl]
-impl FluxoraStream {
-    /// Initialise the contract with the streaming token and admin address.
-    ///
-    /// This function must be called exactly once before any other contract operations.
-    /// It persists the token address (used for all stream transfers) and admin address
-    /// (authorized for administrative operations) in instance storage.
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("rcpt_cncl")),
+            allowed,
+        );
+        Ok(())
+    }
+
+    /// Enable or disable refunding excess deposits when a stream's schedule ends.
     ///
-    /// # Parameters
-    /// - `token`: Address of the token contract used for all payment streams
-    /// - `admin`: Address authorized to perform administrative operations (pause, cancel, etc.)
+    /// A stream may be funded with more than `rate × duration`; without this flag
+    /// the surplus stays locked until the sender cancels (or `expire_stream`
+    /// sweeps it). With it, the withdrawal that drains an elapsed stream refunds
+    /// the surplus to the sender and completes the stream. Applies to existing
+    /// streams too.
     ///
-    /// # Storage
-    /// - Stores `Config { token, admin }` in instance storage under `DataKey::Config`
-    /// - Initializes `NextStreamId` counter to 0 for stream ID generation
-    /// - Extends TTL to prevent premature expiration (17280 ledgers threshold, 120960 max)
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Panics
-    /// - If called more than once (contract already initialized)
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
     ///
-    /// # Security
-    /// - Re-initialization is prevented to ensure immutable token and admin configuration
-    /// - No authorization required for initial setup (deployer calls this once)
-    pub fn init(env: Env, token: Address, admin: Address) {
-        if env.storage().instance().has(&DataKey::Config) {
-            panic!("already initialised");
-        }
-        let config = Config { token, admin };
-        env.storage().instance().set(&DataKey::Config, &config);
-        env.storage().instance().set(&DataKey::NextStreamId, &0u64);
+    /// # Events
+    /// - Publishes `("config", "excess")` with the new value
+    pub fn set_allow_excess_deposit(env: Env, allowed: bool) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        // Ensure instance storage (Config / NextStreamId) doesn't expire quickly
+        config.allow_excess_deposit = allowed;
+        env.storage().instance().set(&DataKey::Config, &config);
         bump_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("excess")), allowed);
+        Ok(())
     }
 
-    /// Create a new payment stream with specified parameters.
+    /// Require new rate-based streams to be funded with exactly `rate × duration`.
     ///
-    /// Establishes a new token stream from sender to recipient with defined rate and duration.
-    /// Transfers the deposit amount from sender to the contract immediately. Returns a unique
-    /// stream ID that can be used to interact with the stream.
+    /// While enabled, a deposit above that amount (after the protocol fee) fails
+    /// with `ExcessDeposit`, so senders cannot lock surplus tokens by mistake; a
+    /// deposit below it still fails with `UnfundedDeposit`. Stepped and
+    /// `Exponential` streams are exempt. Existing streams are unaffected.
+    /// Disabled by default.
     ///
-    /// # Parameters
-    /// - `sender`: Address funding the stream (must authorize the transaction)
-    /// - `recipient`: Address receiving the streamed tokens
-    /// - `deposit_amount`: Total tokens to deposit (must be > 0)
-    /// - `rate_per_second`: Streaming rate in tokens per second (must be > 0)
-    /// - `start_time`: When streaming begins (ledger timestamp)
-    /// - `cliff_time`: When tokens first become available (vesting cliff, must be in [start_time, end_time])
-    /// - `end_time`: When streaming completes (must be > start_time)
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Returns
-    /// - `u64`: Unique stream identifier for the newly created stream
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
     ///
-    /// # Authorization
-    /// - Requires authorization from the sender address
+    /// # Events
+    /// - Publishes `("config", "strict")` with the new value
+    pub fn set_strict_deposit_mode(env: Env, enabled: bool) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
+
+        config.strict_deposit_mode = enabled;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("strict")), enabled);
+        Ok(())
+    }
+
+    /// Set the protocol fee charged on new streams, in basis points.
     ///
-    /// # Validation
-    /// The function validates all parameters before creating the stream:
-    /// - `deposit_amount > 0` and `rate_per_second > 0`
-    /// - `sender != recipient` (cannot stream to yourself)
-    /// - `start_time < end_time` (valid time range)
-    /// - `cliff_time` in `[start_time, end_time]` (cliff within stream duration)
-    /// - `deposit_amount >= rate_per_second × (end_time - start_time)` (sufficient deposit)
+    /// The fee is deducted from each stream's deposit at creation, so the stored
+    /// `deposit_amount` is net of fees. Existing streams are unaffected.
     ///
-    /// # Panics
-    /// - If `deposit_amount` or `rate_per_second` is not positive
-    /// - If `sender` and `recipient` are the same address
-    /// - If `start_time >= end_time` (invalid time range)
-    /// - If `cliff_time` is not in `[start_time, end_time]`
-    /// - If `deposit_amount < rate_per_second × (end_time - start_time)` (insufficient deposit)
-    /// - If token transfer fails (insufficient balance or allowance)
-    /// - If overflow occurs calculating total streamable amount
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # State Changes
-    /// - Transfers `deposit_amount` tokens from sender to contract
-    /// - Creates new stream with status `Active`
-    /// - Increments global stream counter
-    /// - Stores stream data in persistent storage with extended TTL
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `fee_bps > MAX_CONFIG_FEE_BPS` (10%), the same cap
+    ///   `update_config` applies
     ///
     /// # Events
-    /// - Publishes `created(stream_id, deposit_amount)` event on success
+    /// - Publishes `("config", "fee_bps")` with the new value
+    pub fn set_fee_bps(env: Env, fee_bps: u32) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
+
+        if fee_bps > MAX_CONFIG_FEE_BPS {
+            return Err(ContractError::InvalidParams);
+        }
+        config.fee_bps = fee_bps;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("fee_bps")), fee_bps);
+        Ok(())
+    }
+
+    /// Set the protocol fee taken from every withdrawal, in basis points.
     ///
-    /// # Usage Notes
-    /// - Transaction is atomic: if token transfer fails, no stream is created
-    /// - Stream IDs are sequential starting from 0
-    /// - Cliff time enables vesting schedules (no withdrawals before cliff)
-    /// - Setting `cliff_time = start_time` means no cliff (immediate vesting)
-    /// - Deposit can exceed minimum required (excess remains in contract)
-    /// - Sender must have sufficient token balance and approve contract
-    /// ## Stream Limits Policy
-    /// No hard upper bounds are enforced on `deposit_amount` or stream duration.
-    /// Rationale:
-    /// - Overflow in accrual math is already prevented via `checked_mul` and clamping.
-    /// - A fixed cap would require a contract upgrade to change and conflicts with
-    ///   the overflow test suite, which exercises values up to `i128::MAX`.
-    /// - Protocol-specific limits (e.g. "max 10 M USDC per stream") belong at the
-    ///   application layer (UI or an admin-gated factory contract), where business
-    ///   context is available.
+    /// Each withdraw entry point (including `batch_withdraw`) keeps
+    /// `amount × fee_on_withdrawal_bps / 10_000` in `get_accumulated_fees` and
+    /// pays the rest out; `withdrawn_amount` still advances by the full amount.
+    /// Applies to existing streams too, on top of any creation fee (`fee_bps`).
+    /// Streams funded in a token other than the current `Config.token` are not
+    /// charged. `0` disables the fee.
     ///
-    /// Senders are responsible for the correctness of the values they supply.
-    /// The validations above (`deposit > 0`, `rate > 0`, `deposit >= rate × duration`,
-    /// valid time window) are the contract's complete set of creation constraints.
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Examples
-    /// - Linear stream: 1000 tokens over 1000 seconds, no cliff
-    ///   - `deposit_amount = 1000`, `rate = 1`, `start = 0`, `cliff = 0`, `end = 1000`
-    /// - Vesting stream: 12000 tokens over 12 months, 6-month cliff
-    ///   - `deposit_amount = 12000`, `rate = 1`, `start = 0`, `cliff = 15552000`, `end = 31104000`
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_stream(
-        env: Env,
-        sender: Address,
-        recipient: Address,
-        deposit_amount: i128,
-        rate_per_second: i128,
-        start_time: u64,
-        cliff_time: u64,
-        end_time: u64,
-    ) -> u64 {
-        sender.require_auth();
-
-        Self::validate_stream_params(
-            &sender,
-            &recipient,
-            deposit_amount,
-            rate_per_second,
-            start_time,
-            cliff_time,
-            end_time,
-        );
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `fee_bps > 10_000`
+    ///
+    /// # Events
+    /// - Publishes `("config", "wd_fee")` with the new value
+    pub fn set_fee_on_withdrawal_bps(env: Env, fee_bps: u32) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        // Transfer tokens from sender to this contract (#36)
-        // If transfer fails (insufficient balance/allowance), this will panic
-        // and no state will be persisted (atomic transaction)
-        let token_client = token::Client::new(&env, &get_token(&env));
-        token_client.transfer(&sender, &env.current_contract_address(), &deposit_amount);
+        if fee_bps > BPS_DENOMINATOR {
+            return Err(ContractError::InvalidParams);
+        }
+        config.fee_on_withdrawal_bps = fee_bps;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        // Only allocate stream id and persist state AFTER successful transfer
-        Self::persist_new_stream(
-            &env,
-            sender,
-            recipient,
-            deposit_amount,
-            rate_per_second,
-            start_time,
-            cliff_time,
-            end_time,
-        )
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("wd_fee")), fee_bps);
+        Ok(())
     }
 
-    /// Create multiple payment streams in a single transaction.
+    /// Set the smallest deposit accepted by the `create_*` functions.
     ///
-    /// Optimizes gas usage by verifying authorization once and doing a single bulk
-    /// token transfer for all streams, executing the creations atomically.
+    /// Compared against the gross deposit, before the protocol fee. Existing
+    /// streams are unaffected; `0` removes the minimum.
     ///
-    /// # Parameters
-    /// - `sender`: Address funding all streams in the batch
-    /// - `streams`: Vector of stream configuration parameters
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Returns
-    /// - `Vec<u64>`: Vector of unique stream identifiers for the newly created streams
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `amount < 0`
     ///
-    /// # Authorization
-    /// - Requires authorization from the sender address exactly once for the entire batch.
-    pub fn create_streams(
-        env: Env,
-        sender: Address,
-        streams: soroban_sdk::Vec<CreateStreamParams>,
-    ) -> soroban_sdk::Vec<u64> {
-        sender.require_auth();
-
-        let mut total_deposit: i128 = 0;
+    /// # Events
+    /// - Publishes `("config", "min_dep")` with the new value
+    pub fn set_min_deposit(env: Env, amount: i128) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        // First pass: validate all streams and calculate total deposit required
-        for params in streams.iter() {
-            Self::validate_stream_params(
-                &sender,
-                &params.recipient,
-                params.deposit_amount,
-                params.rate_per_second,
-                params.start_time,
-                params.cliff_time,
-                params.end_time,
-            );
-            total_deposit = total_deposit
-                .checked_add(params.deposit_amount)
-                .expect("overflow calculating total batch deposit");
+        if amount < 0 {
+            return Err(ContractError::InvalidParams);
         }
+        config.min_deposit = amount;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        // Bulk transfer tokens from sender to this contract atomically to save gas
-        if total_deposit > 0 {
-            let token_client = token::Client::new(&env, &get_token(&env));
-            token_client.transfer(&sender, &env.current_contract_address(), &total_deposit);
-        }
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("min_dep")), amount);
+        Ok(())
+    }
 
-        // Second pass: generate IDs, persist state, and emit events iteratively
-        let mut created_ids = soroban_sdk::Vec::new(&env);
-        for params in streams.iter() {
-            let stream_id = Self::persist_new_stream(
-                &env,
-                sender.clone(),
-                params.recipient,
-                params.deposit_amount,
-                params.rate_per_second,
-                params.start_time,
-                params.cliff_time,
-                params.end_time,
-            );
-            created_ids.push_back(stream_id);
+    /// Set the TTL (in ledgers) every stream write extends the stream's entry to.
+    ///
+    /// Applies to creation and to every later write (withdraw, pause, resume,
+    /// cancel, ...). Values below the built-in `PERSISTENT_BUMP_AMOUNT` (including
+    /// `0`) keep the built-in extension.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `ledgers` exceeds the network's maximum TTL
+    ///
+    /// # Events
+    /// - Publishes `("config", "min_ttl")` with the new value
+    pub fn set_min_ttl_ledgers(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
+
+        if ledgers > env.storage().max_ttl() {
+            return Err(ContractError::InvalidParams);
         }
+        config.min_ttl_ledgers = ledgers;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("min_ttl")), ledgers);
+        Ok(())
+    }
+
+    /// Set the minimum number of seconds between two paying withdrawals from the
+    /// same stream.
+    ///
+    /// Applies to every withdraw entry point of live streams, including existing
+    /// ones; the first withdrawal of a stream and withdrawals from cancelled
+    /// streams are never limited. `0` disables the cooldown.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    ///
+    /// # Events
+    /// - Publishes `("config", "cooldown")` with the new value
+    pub fn set_withdrawal_cooldown(env: Env, seconds: u64) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
+
+        config.withdrawal_cooldown = seconds;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        created_ids
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("cooldown")),
+            seconds,
+        );
+        Ok(())
     }
 
-    /// Pause an active payment stream.
-    ///
-    /// Temporarily halts withdrawals from the stream while preserving accrual calculations.
-    /// The stream can be resumed later by the sender or admin. Accrual continues based on
-    /// time elapsed, but the recipient cannot withdraw while paused.
+    /// Set how long after a stream is fully accrued `expire_stream` must wait.
     ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to pause
+    /// Applies to existing streams too. Defaults to `DEFAULT_EXPIRY_GRACE_PERIOD`
+    /// (30 days).
     ///
     /// # Authorization
-    /// - Requires authorization from the stream's sender (original creator)
-    /// - Admin can use `pause_stream_as_admin` for administrative override
+    /// - Requires authorization from the current admin address
     ///
-    /// # Panics
-    /// - If the stream is not in `Active` state (already paused, completed, or cancelled)
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the sender)
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
     ///
     /// # Events
-    /// - Publishes `Paused(stream_id)` event on success
-    ///
-    /// # Usage Notes
-    /// - Pausing does not affect accrual calculations (time-based)
-    /// - Recipient cannot withdraw while stream is paused
-    /// - Stream can be cancelled while paused
-    /// - Use `resume_stream` to reactivate withdrawals
-    pub fn pause_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
-        let mut stream = load_stream(&env, stream_id)?;
+    /// - Publishes `("config", "grace")` with the new value
+    pub fn set_expiry_grace_period(env: Env, seconds: u64) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        Self::require_sender_or_admin(&env, &stream.sender);
+        config.expiry_grace_period = seconds;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        if stream.status == StreamStatus::Paused {
-            panic!("stream is already paused");
-        }
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("grace")), seconds);
+        Ok(())
+    }
 
-        assert!(
-            stream.status == StreamStatus::Active,
-            "stream must be active to pause"
-        );
+    /// Set (or, with `None`, clear) the address `recover_orphaned_stream` pays.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    ///
+    /// # Events
+    /// - Publishes `("config", "recovery")` with the new value
+    pub fn set_recovery_address(
+        env: Env,
+        recovery_address: Option<Address>,
+    ) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        stream.status = StreamStatus::Paused;
-        save_stream(&env, &stream);
+        config.recovery_address = recovery_address.clone();
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
         env.events().publish(
-            (symbol_short!("paused"), stream_id),
-            StreamEvent::Paused(stream_id),
+            (symbol_short!("config"), symbol_short!("recovery")),
+            recovery_address,
         );
         Ok(())
     }
 
-    /// Resume a paused payment stream.
-    ///
-    /// Reactivates a paused stream, allowing the recipient to withdraw accrued funds again.
-    /// Only streams in `Paused` state can be resumed. Terminal states (Completed, Cancelled)
-    /// cannot be resumed.
+    /// Set how long after a stream's end `recover_orphaned_stream` must wait.
     ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to resume
+    /// Applies to existing streams too. Defaults to `DEFAULT_RECOVERY_GRACE_PERIOD`
+    /// (365 days).
     ///
     /// # Authorization
-    /// - Requires authorization from the stream's sender (original creator)
-    /// - Admin can use `resume_stream_as_admin` for administrative override
+    /// - Requires authorization from the current admin address
     ///
-    /// # Panics
-    /// - If the stream is `Active` (not paused, already running)
-    /// - If the stream is `Completed` (terminal state, cannot be resumed)
-    /// - If the stream is `Cancelled` (terminal state, cannot be resumed)
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the sender)
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
     ///
     /// # Events
-    /// - Publishes `Resumed(stream_id)` event on success
-    ///
-    /// # Usage Notes
-    /// - Only paused streams can be resumed
-    /// - Accrual calculations are time-based and unaffected by pause/resume
-    /// - After resume, recipient can immediately withdraw accrued funds
-    pub fn resume_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
-        let mut stream = load_stream(&env, stream_id)?;
-        Self::require_sender_or_admin(&env, &stream.sender);
-
-        match stream.status {
-            StreamStatus::Active => panic!("stream is active, not paused"),
-            StreamStatus::Completed => panic!("stream is completed"),
-            StreamStatus::Cancelled => panic!("stream is cancelled"),
-            StreamStatus::Paused => {}
-        }
+    /// - Publishes `("config", "rec_grace")` with the new value
+    pub fn set_recovery_grace_period(env: Env, seconds: u64) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        stream.status = StreamStatus::Active;
-        save_stream(&env, &stream);
+        config.recovery_grace_period = seconds;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
         env.events().publish(
-            (symbol_short!("resumed"), stream_id),
-            StreamEvent::Resumed(stream_id),
+            (symbol_short!("config"), symbol_short!("rec_grace")),
+            seconds,
         );
         Ok(())
     }
 
-    /// Cancel a payment stream and refund unstreamed funds to the sender.
+    /// Turn the sender whitelist on or off.
     ///
-    /// Terminates an active or paused stream, immediately refunding any unstreamed tokens
-    /// to the sender. The accrued amount (based on time elapsed) remains in the contract
-    /// for the recipient to withdraw. This is a terminal operation - cancelled streams
-    /// cannot be resumed.
-    ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to cancel
+    /// While enabled, stream creation fails with `SenderNotWhitelisted` for any
+    /// sender not added with `add_to_sender_whitelist`. Existing streams are
+    /// unaffected. Disabled by default.
     ///
     /// # Authorization
-    /// - Requires authorization from the stream's sender (original creator)
-    /// - Admin can use `cancel_stream_as_admin` for administrative override
-    ///
-    /// # Behavior
-    /// 1. Validates stream is in `Active` or `Paused` state
-    /// 2. Calculates accrued amount: `min((now - start_time) × rate, deposit_amount)`
-    /// 3. Calculates refund: `deposit_amount - accrued`
-    /// 4. Transfers refund to sender (if > 0)
-    /// 5. Sets stream status to `Cancelled`
-    /// 6. Accrued but not withdrawn amount remains for recipient
-    ///
-    /// # Returns
-    /// - Implicitly returns via state change and token transfer
+    /// - Requires authorization from the current admin address
     ///
-    /// # Panics
-    /// - If stream is not `Active` or `Paused` (already completed or cancelled)
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the sender)
-    /// - If token transfer fails (should not happen with valid contract state)
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
     ///
     /// # Events
-    /// - Publishes `Cancelled(stream_id)` event on success
-    ///
-    /// # Usage Notes
-    /// - Cancellation is irreversible (terminal state)
-    /// - Recipient can still withdraw accrued amount after cancellation
-    /// - If fully accrued (time >= end_time), sender receives no refund
-    /// - Accrual is time-based, not affected by pause state
-    /// - Can be called on paused streams
-    ///
-    /// # Examples
-    /// - Cancel at 30% completion → sender gets 70% refund, recipient can withdraw 30%
-    /// - Cancel at 100% completion → sender gets 0% refund, recipient can withdraw 100%
-    /// - Cancel before cliff → sender gets 100% refund (no accrual before cliff)
-    pub fn cancel_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
-        let mut stream = load_stream(&env, stream_id)?;
-        Self::require_sender_or_admin(&env, &stream.sender);
-        Self::require_cancellable_status(&env, stream.status);
-
-        let accrued = Self::calculate_accrued(env.clone(), stream_id)?;
-        let unstreamed = stream.deposit_amount - accrued;
-
-        // CEI: update state before external token transfer to reduce reentrancy risk.
-        stream.status = StreamStatus::Cancelled;
-        stream.cancelled_at = Some(env.ledger().timestamp());
-        save_stream(&env, &stream);
+    /// - Publishes `("config", "wl_on")` with the new value
+    pub fn set_whitelist_enabled(env: Env, enabled: bool) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        if unstreamed > 0 {
-            let token_client = token::Client::new(&env, &get_token(&env));
-            token_client.transfer(&env.current_contract_address(), &stream.sender, &unstreamed);
-        }
+        config.whitelist_enabled = enabled;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        env.events().publish(
-            (symbol_short!("cancelled"), stream_id),
-            StreamEvent::Cancelled(stream_id),
-        );
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("wl_on")), enabled);
         Ok(())
     }
 
-    /// Withdraw accrued tokens from a payment stream to the recipient.
+    /// Allow `sender` to create streams while the whitelist is enabled.
     ///
-    /// Transfers all accrued-but-not-yet-withdrawn tokens to the stream's recipient.
-    /// The amount withdrawn is calculated as `accrued - withdrawn_amount`, where accrued
-    /// is based on time elapsed since stream start. If this withdrawal completes the
-    /// stream (all deposited tokens withdrawn), the stream status transitions to `Completed`.
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to withdraw from
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `sender` is already whitelisted
     ///
-    /// # Returns
-    /// - `i128`: The amount of tokens transferred to the recipient (0 if nothing to withdraw)
+    /// # Events
+    /// - Publishes `("config", "wl_add")` with the address
+    pub fn add_to_sender_whitelist(env: Env, sender: Address) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        get_admin(&env).require_auth();
+
+        let mut whitelist = load_sender_whitelist(&env);
+        if whitelist.contains(&sender) {
+            return Err(ContractError::InvalidParams);
+        }
+        whitelist.push_back(sender.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::SenderWhitelist, &whitelist);
+        bump_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("wl_add")), sender);
+        Ok(())
+    }
+
+    /// Remove `sender` from the whitelist. Their existing streams are unaffected.
     ///
     /// # Authorization
-    /// - Requires authorization from the stream's recipient (only recipient can withdraw)
-    /// - This prevents anyone from withdrawing on behalf of the recipient
-    ///
-    /// # Zero Withdrawable Behavior
-    /// - If `accrued == withdrawn_amount` (nothing to withdraw), returns 0 immediately
-    /// - No token transfer occurs, no state change, no event published
-    /// - This is idempotent: safe to call multiple times without side effects
-    /// - Occurs before cliff time or when all accrued funds already withdrawn
-    /// - Frontends can call withdraw without pre-checking balance
-    ///
-    /// # Panics
-    /// - If the stream is `Completed` (all tokens already withdrawn)
-    /// - If the stream is `Paused` (withdrawals not allowed while paused)
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the recipient)
-    /// - If token transfer fails (insufficient contract balance, should not happen)
+    /// - Requires authorization from the current admin address
     ///
-    /// # State Changes
-    /// - Updates `withdrawn_amount` by the amount transferred (only if withdrawable > 0)
-    /// - Sets status to `Completed` if all deposited tokens are withdrawn
-    /// - Extends stream storage TTL to prevent expiration
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `sender` is not whitelisted
     ///
     /// # Events
-    /// - Publishes `withdrew(stream_id, amount)` event on success (only if amount > 0)
-    ///
-    /// # Usage Notes
-    /// - Can be called multiple times to withdraw incrementally
-    /// - Accrual is time-based: `min((now - start_time) × rate, deposit_amount)`
-    /// - Before cliff time, accrued amount is 0 (returns 0, no transfer)
-    /// - After end_time, accrued amount is capped at deposit_amount
-    /// - Works on `Active` and `Cancelled` streams, not on `Paused` or `Completed`
-    /// - For cancelled streams, only the accrued amount (not refunded) can be withdrawn
-    ///
-    /// # Examples
-    /// - Stream: 1000 tokens over 1000 seconds (1 token/sec)
-    /// - At t=0 (before cliff): withdraw() returns 0 (no transfer)
-    /// - At t=300: withdraw() returns 300 tokens
-    /// - At t=300 (again): withdraw() returns 0 (already withdrawn)
-    /// - At t=800: withdraw() returns 500 tokens (800 - 300 already withdrawn)
-    /// - At t=1000: withdraw() returns 200 tokens, status → Completed
-    pub fn withdraw(env: Env, stream_id: u64) -> Result<i128, ContractError> {
-        let mut stream = load_stream(&env, stream_id)?;
+    /// - Publishes `("config", "wl_remove")` with the address
+    pub fn remove_from_sender_whitelist(env: Env, sender: Address) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        get_admin(&env).require_auth();
 
-        // Enforce recipient-only authorization: only the stream's recipient can withdraw
-        // This is equivalent to checking env.invoker() == stream.recipient
-        // require_auth() ensures only the recipient can authorize this call,
-        // preventing anyone from withdrawing on behalf of the recipient
-        stream.recipient.require_auth();
+        let mut whitelist = load_sender_whitelist(&env);
+        let index = whitelist
+            .first_index_of(&sender)
+            .ok_or(ContractError::InvalidParams)?;
+        whitelist.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::SenderWhitelist, &whitelist);
+        bump_instance_ttl(&env);
 
-        assert!(
-            stream.status != StreamStatus::Completed,
-            "stream already completed"
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("wl_remove")),
+            sender,
         );
+        Ok(())
+    }
 
-        assert!(
-            stream.status != StreamStatus::Paused,
-            "cannot withdraw from paused stream"
-        );
+    /// Whether `sender` is on the whitelist, regardless of `whitelist_enabled`.
+    pub fn is_sender_whitelisted(env: Env, sender: Address) -> bool {
+        load_sender_whitelist(&env).contains(&sender)
+    }
 
-        let accrued = Self::calculate_accrued(env.clone(), stream_id)?;
-        let withdrawable = accrued - stream.withdrawn_amount;
+    /// Every whitelisted address, in the order they were added.
+    pub fn get_sender_whitelist(env: Env) -> soroban_sdk::Vec<Address> {
+        load_sender_whitelist(&env)
+    }
 
-        // Handle zero withdrawable: return 0 without transfer or state change (idempotent).
-        // This occurs before cliff or when all accrued funds have been withdrawn.
-        // Frontends can safely call withdraw without checking balance first.
-        if withdrawable == 0 {
-            return Ok(0);
-        }
+    /// Set the smallest `rate_per_second` accepted for new linear streams.
+    ///
+    /// Stepped streams carry no rate and are not affected. Existing streams are
+    /// unaffected; `0` removes the minimum.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `rate < 0`
+    ///
+    /// # Events
+    /// - Publishes `("config", "min_rate")` with the new value
+    pub fn set_min_rate(env: Env, rate: i128) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        // CEI: update state before external token transfer to reduce reentrancy risk.
-        stream.withdrawn_amount += withdrawable;
-        if stream.withdrawn_amount == stream.deposit_amount {
-            stream.status = StreamStatus::Completed;
+        if rate < 0 {
+            return Err(ContractError::InvalidParams);
         }
-        save_stream(&env, &stream);
-
-        let token_client = token::Client::new(&env, &get_token(&env));
-        token_client.transfer(
-            &env.current_contract_address(),
-            &stream.recipient,
-            &withdrawable,
-        );
+        config.min_rate = rate;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        env.events().publish(
-            (symbol_short!("withdrew"), stream_id),
-            Withdrawal {
-                stream_id,
-                recipient: stream.recipient.clone(),
-                amount: withdrawable,
-            },
-        );
-        Ok(withdrawable)
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("min_rate")), rate);
+        Ok(())
     }
 
-    /// Calculate the total amount accrued to the recipient at the current time.
-    ///
-    /// # Behaviour by status
-    ///
-    /// | Status      | Return value                                         |
-    /// |-------------|------------------------------------------------------|
-    /// | `Active`    | `min((min(now,end)-start) × rate, deposit_amount)`   |
-    /// | `Paused`    | Same time-based formula (accrual is not paused)      |
-    /// | `Completed` | `deposit_amount` — all tokens were accrued/withdrawn |
-    /// | `Cancelled` | Final accrued at cancellation timestamp (frozen value) |
-    ///
-    /// ## Rationale for `Cancelled`
-    /// On cancellation, unstreamed tokens are refunded immediately to the sender.
-    /// The recipient can claim only what was already accrued at cancellation time.
-    /// Returning a frozen final accrued value keeps `calculate_accrued` consistent
-    /// with contract balances and prevents post-cancel time growth.
+    /// Update mutable protocol parameters in a single call.
     ///
-    /// # Calculation
-    /// - Before `cliff_time`: returns 0 (no accrual before cliff)
-    /// - After `cliff_time`: `min((now - start_time) × rate_per_second, deposit_amount)`
-    /// - After `end_time`: elapsed time is capped at `end_time` (no accrual beyond end)
+    /// Each argument is applied only when `Some`; `None` leaves the current value
+    /// untouched. `token` and `admin` cannot be changed here — the admin rotates
+    /// through `propose_admin` / `accept_admin`.
     ///
-    /// # Panics
-    /// - If the stream does not exist (`stream_id` is invalid)
+    /// # Parameters
+    /// - `new_fee_bps`: Protocol fee in basis points (at most `MAX_CONFIG_FEE_BPS`, i.e. 10%)
+    /// - `new_min_deposit`: Minimum deposit for new streams (must be >= 0)
+    /// - `new_max_streams_per_sender`: Per-sender stream cap (0 = unlimited)
+    /// - `new_min_duration`: Shortest stream window in seconds (0 = no minimum)
+    /// - `new_min_withdraw_amount`: Dust threshold for a single withdrawal
+    ///   (must be >= 0; 0 = no threshold)
+    /// - `new_global_stream_cap`: Number of created streams at which stream
+    ///   creation stops with `GlobalCapReached`
     ///
-    /// # Usage Notes
-    /// - This is a view function (read-only, no state changes)
-    /// - No authorization required (public information)
-    /// - Returns total accrued, not withdrawable amount
-    /// - To get withdrawable amount: `calculate_accrued() - stream.withdrawn_amount`
-    /// - Active/Paused streams accrue by current time; Completed/Cancelled are deterministic
-    /// - Useful for UIs to show real-time accrual without transactions
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Examples
-    /// - Stream: 1000 tokens, 0-1000s, rate 1 token/sec, cliff at 500s
-    /// - At t=300: returns 0 (before cliff)
-    /// - At t=500: returns 500 (at cliff, accrual from start_time)
-    /// - At t=800: returns 800
-    /// - At t=1500: returns 1000 (elapsed time capped at end_time)
-    /// ## Rationale for `Completed`
-    /// When a stream reaches `Completed`, `withdrawn_amount == deposit_amount`.
-    /// There is no further accrual possible. Returning `deposit_amount` is the
-    /// deterministic, timestamp-independent answer for any UI or downstream caller.
-    pub fn calculate_accrued(env: Env, stream_id: u64) -> Result<i128, ContractError> {
-        let stream = load_stream(&env, stream_id)?;
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidParams` if `new_fee_bps > 1000`, `new_min_deposit < 0` or
+    ///   `new_min_withdraw_amount < 0`; nothing is written when any value is rejected
+    ///
+    /// # Events
+    /// - Publishes `("config", "updated")` with the resulting `Config`
+    pub fn update_config(
+        env: Env,
+        new_fee_bps: Option<u32>,
+        new_min_deposit: Option<i128>,
+        new_max_streams_per_sender: Option<u32>,
+        new_min_duration: Option<u64>,
+        new_min_withdraw_amount: Option<i128>,
+        new_global_stream_cap: Option<u64>,
+    ) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        let mut config = get_config(&env);
+        config.admin.require_auth();
 
-        if stream.status == StreamStatus::Completed {
-            return Ok(stream.deposit_amount);
+        if let Some(fee_bps) = new_fee_bps {
+            if fee_bps > MAX_CONFIG_FEE_BPS {
+                return Err(ContractError::InvalidParams);
+            }
+            config.fee_bps = fee_bps;
+        }
+        if let Some(min_deposit) = new_min_deposit {
+            if min_deposit < 0 {
+                return Err(ContractError::InvalidParams);
+            }
+            config.min_deposit = min_deposit;
+        }
+        if let Some(max_streams) = new_max_streams_per_sender {
+            config.max_streams_per_sender = max_streams;
+        }
+        if let Some(min_duration) = new_min_duration {
+            config.min_duration = min_duration;
+        }
+        if let Some(min_withdraw_amount) = new_min_withdraw_amount {
+            if min_withdraw_amount < 0 {
+                return Err(ContractError::InvalidParams);
+            }
+            config.min_withdraw_amount = min_withdraw_amount;
+        }
+        if let Some(global_stream_cap) = new_global_stream_cap {
+            config.global_stream_cap = global_stream_cap;
         }
 
-        let now = if stream.status == StreamStatus::Cancelled {
-            stream.cancelled_at.ok_or(ContractError::InvalidState)?
-        } else {
-            env.ledger().timestamp()
-        };
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(&env);
 
-        Ok(accrual::calculate_accrued_amount(
-            stream.start_time,
-            stream.cliff_time,
-            stream.end_time,
-            stream.rate_per_second,
-            stream.deposit_amount,
-            now,
-        ))
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("updated")), config);
+        Ok(())
     }
 
-    /// Retrieve the global contract configuration.
-    ///
-    /// Returns the contract's configuration containing the token address used for all
-    /// streams and the admin address authorized for administrative operations.
+    /// Transfer every accumulated protocol fee to `destination`.
     ///
     /// # Returns
-    /// - `Config`: Structure containing:
-    ///   - `token`: Address of the token contract used for all payment streams
-    ///   - `admin`: Address authorized to perform admin operations (pause, cancel, resume)
+    /// - `i128`: Amount transferred (0 if no fees have accumulated)
     ///
-    /// # Panics
-    /// - If the contract has not been initialized (missing config)
+    /// # Authorization
+    /// - Requires authorization from the current admin address
     ///
-    /// # Usage Notes
-    /// - This is a view function (read-only, no state changes)
-    /// - No authorization required (public information)
-    /// - Config is set once during `init()` and can be updated via `set_admin()`
-    /// - Useful for integrators to verify token and admin addresses
-    pub fn get_config(env: Env) -> Config {
-        get_config(&env)
+    /// # Events
+    /// - Publishes `("fees", "withdrawn")` with `(destination, amount)` when amount > 0
+    pub fn withdraw_protocol_fees(env: Env, destination: Address) -> i128 {
+        let config = get_config(&env);
+        config.admin.require_auth();
+
+        let amount = get_accumulated_fees(&env);
+        if amount == 0 {
+            return 0;
+        }
+
+        // CEI: clear the balance before the external token transfer.
+        set_accumulated_fees(&env, 0);
+        let token_client = token::Client::new(&env, &config.token);
+        token_client.transfer(&env.current_contract_address(), &destination, &amount);
+
+        env.events().publish(
+            (symbol_short!("fees"), symbol_short!("withdrawn")),
+            (destination, amount),
+        );
+        amount
     }
 
-    /// Update the admin address for the contract.
+    /// Protocol fees collected and not yet withdrawn.
+    pub fn get_accumulated_fees(env: Env) -> i128 {
+        bump_instance_ttl(&env);
+        get_accumulated_fees(&env)
+    }
+
+    /// Tokens the contract holds on behalf of streams.
     ///
-    /// Allows the current admin to rotate the admin key by setting a new admin address.
-    /// This enables key rotation without redeploying the contract. Only the current admin
-    /// may call this function.
+    /// Grows with every net deposit, top-up and extension; shrinks by each
+    /// withdrawal and by the refund paid on cancellation. Accrued-but-unwithdrawn
+    /// tokens of cancelled streams stay locked until the recipient withdraws them.
+    /// Together with `get_accumulated_fees` it equals the contract's token balance.
+    pub fn get_total_value_locked(env: Env) -> i128 {
+        bump_instance_ttl(&env);
+        get_protocol_total(&env, &DataKey::TotalLocked)
+    }
+
+    /// Tokens ever withdrawn by recipients across all streams.
+    pub fn get_total_streamed(env: Env) -> i128 {
+        bump_instance_ttl(&env);
+        get_protocol_total(&env, &DataKey::TotalStreamed)
+    }
+
+    /// Protocol-wide stream counts by status plus deposit and withdrawal totals.
     ///
-    /// # Parameters
-    /// - `new_admin`: The new admin address that will replace the current admin
+    /// Read from counters maintained on every status transition, so the cost does
+    /// not grow with the number of streams. `Pending` and `Scheduled` streams count
+    /// as active. A stream cancelled with nothing left to refund moves from
+    /// `cancelled_count` to `completed_count` once its recipient withdraws the rest.
+    pub fn get_protocol_stats(env: Env) -> ProtocolStats {
+        ProtocolStats {
+            total_streams: get_streams_created(&env),
+            active_count: get_status_count(&env, StreamStatus::Active),
+            paused_count: get_status_count(&env, StreamStatus::Paused),
+            completed_count: get_status_count(&env, StreamStatus::Completed),
+            cancelled_count: get_status_count(&env, StreamStatus::Cancelled),
+            total_deposited: get_protocol_total(&env, &DataKey::TotalDeposited),
+            total_withdrawn: get_protocol_total(&env, &DataKey::TotalStreamed),
+        }
+    }
+
+    /// Abort a pending admin proposal.
     ///
     /// # Authorization
     /// - Requires authorization from the current admin address
     ///
-    /// # Panics
-    /// - If the contract has not been initialized (missing config)
-    /// - If caller is not the current admin
-    ///
-    /// # State Changes
-    /// - Updates the admin address in the Config stored in instance storage
-    /// - Token address remains unchanged
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `NoPendingAdmin` if no proposal is pending
     ///
     /// # Events
-    /// - Publishes `admin_updated(old_admin, new_admin)` event on success
-    ///
-    /// # Usage Notes
-    /// - This is a security-critical function for admin key rotation
-    /// - The new admin immediately gains all administrative privileges
-    /// - The old admin immediately loses all administrative privileges
-    /// - No restrictions on the new admin address (can be any valid address)
-    /// - Can be called multiple times to rotate keys as needed
-    ///
-    /// # Examples
-    /// - Rotate to a new admin key: `set_admin(env, new_admin_address)`
-    /// - Transfer admin to a multisig: `set_admin(env, multisig_address)`
-    pub fn set_admin(env: Env, new_admin: Address) {
+    /// - Publishes `("admin", "cancelled")` with the discarded proposed address
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
         let mut config = get_config(&env);
-        let old_admin = config.admin.clone();
-
-        // Only current admin can update admin
-        old_admin.require_auth();
+        config.admin.require_auth();
 
-        // Update admin in config
-        config.admin = new_admin.clone();
+        let proposed = config
+            .pending_admin
+            .take()
+            .ok_or(ContractError::NoPendingAdmin)?;
         env.storage().instance().set(&DataKey::Config, &config);
-
-        // Bump TTL after instance write
         bump_instance_ttl(&env);
 
-        // Emit event with old and new admin addresses
         env.events().publish(
-            (symbol_short!("admin"), symbol_short!("updated")),
-            (old_admin, new_admin),
+            (symbol_short!("admin"), symbol_short!("cancelled")),
+            proposed,
         );
+        Ok(())
     }
 
     /// Retrieve the complete state of a payment stream.
@@ -941,8 +5885,9 @@ impl FluxoraStream {
     ///   - `withdrawn_amount`: Total tokens already withdrawn by recipient
     ///   - `status`: Current stream status (Active, Paused, Completed, Cancelled)
     ///
-    /// # Panics
-    /// - If the stream does not exist (`stream_id` is invalid)
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (`stream_id` is invalid);
+    ///   use `get_stream_state_option` to get `None` instead
     ///
     /// # Usage Notes
     /// - This is a view function (read-only, no state changes)
@@ -954,8 +5899,434 @@ impl FluxoraStream {
     ///   - `Paused`: Temporarily halted, no withdrawals allowed
     ///   - `Completed`: All tokens withdrawn, terminal state
     ///   - `Cancelled`: Terminated early, unstreamed tokens refunded, terminal state
-    pub fn get_stream_state(env: Env, stream_id: u64) -> Stream {
-        load_stream(&env, stream_id).expect("stream not found")
+    pub fn get_stream_state(env: Env, stream_id: u64) -> Result<Stream, ContractError> {
+        load_stream(&env, stream_id)
+    }
+
+    /// Return `true` if a stream with `stream_id` has been created.
+    ///
+    /// Only checks for the storage entry, so it is cheaper than
+    /// `get_stream_state` and never fails.
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Terminal (`Completed` / `Cancelled`) streams still exist
+    pub fn stream_exists(env: Env, stream_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Stream(stream_id))
+    }
+
+    /// Return `true` if `address` is the sender of stream `stream_id`.
+    ///
+    /// Lets calling contracts check a role before a privileged call without
+    /// decoding the whole `Stream`. Returns `false` for unknown streams.
+    pub fn is_sender(env: Env, stream_id: u64, address: Address) -> bool {
+        load_stream(&env, stream_id).is_ok_and(|stream| stream.sender == address)
+    }
+
+    /// Return `true` if `address` is the current recipient of stream `stream_id`.
+    ///
+    /// Follows `update_recipient`. Returns `false` for unknown streams.
+    pub fn is_recipient(env: Env, stream_id: u64, address: Address) -> bool {
+        load_stream(&env, stream_id).is_ok_and(|stream| stream.recipient == address)
+    }
+
+    /// Return `true` if `address` is the approved operator of stream `stream_id`.
+    ///
+    /// Returns `false` for unknown streams and streams without an operator.
+    pub fn is_operator(env: Env, stream_id: u64, address: Address) -> bool {
+        load_stream(&env, stream_id)
+            .is_ok_and(|stream| stream.approved_operator.as_ref() == Some(&address))
+    }
+
+    /// Extend the storage TTL of a stream so it survives long idle periods.
+    ///
+    /// Every stream write already extends the entry (see `set_min_ttl_ledgers`),
+    /// but a vesting stream nobody touches for months would otherwise expire.
+    /// Anyone can pay to keep a stream alive.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Stream whose entry to extend
+    /// - `ledgers`: Ledgers from now the entry must stay live for
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidParams` if `ledgers` is `0` or exceeds the network's maximum TTL
+    ///
+    /// # Usage Notes
+    /// - Also extends the contract instance by the same amount, since the stream
+    ///   cannot be reached once the instance (config, counters) has expired
+    /// - Never shortens a TTL that is already longer than `ledgers`
+    pub fn bump_stream_ttl(env: Env, stream_id: u64, ledgers: u32) -> Result<(), ContractError> {
+        load_stream(&env, stream_id)?;
+        if ledgers == 0 || ledgers > env.storage().max_ttl() {
+            return Err(ContractError::InvalidParams);
+        }
+
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Stream(stream_id), ledgers, ledgers);
+        env.storage().instance().extend_ttl(ledgers, ledgers);
+        Ok(())
+    }
+
+    /// Extend the storage TTL of `sender`'s stream index and of the streams in it.
+    ///
+    /// Batch form of `bump_stream_ttl` for senders with many streams. At most
+    /// `MAX_TTL_BUMP_BATCH` streams are extended per call, the oldest first;
+    /// bump later ones individually with `bump_stream_ttl`.
+    ///
+    /// # Parameters
+    /// - `sender`: Address whose streams to extend
+    /// - `ledgers`: Ledgers from now the entries must stay live for
+    ///
+    /// # Returns
+    /// - `u32`: Number of stream entries extended
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `ledgers` is `0` or exceeds the network's maximum TTL
+    pub fn bump_sender_streams_ttl(
+        env: Env,
+        sender: Address,
+        ledgers: u32,
+    ) -> Result<u32, ContractError> {
+        Self::bump_index_ttl(&env, &DataKey::SenderStreams(sender), ledgers)
+    }
+
+    /// Extend the storage TTL of `recipient`'s stream index and of the streams in it.
+    ///
+    /// Recipient counterpart of `bump_sender_streams_ttl`, with the same
+    /// `MAX_TTL_BUMP_BATCH` limit.
+    ///
+    /// # Returns
+    /// - `u32`: Number of stream entries extended
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `ledgers` is `0` or exceeds the network's maximum TTL
+    pub fn bump_recipient_streams_ttl(
+        env: Env,
+        recipient: Address,
+        ledgers: u32,
+    ) -> Result<u32, ContractError> {
+        Self::bump_index_ttl(&env, &DataKey::RecipientStreams(recipient), ledgers)
+    }
+
+    /// Non-failing variant of `get_stream_state`.
+    ///
+    /// Returns `None` instead of `StreamNotFound` for unknown IDs, so callers can
+    /// probe a stream without an error path. Exposed under this name because the
+    /// generated client already uses `try_get_stream_state` for the fallible call.
+    pub fn get_stream_state_option(env: Env, stream_id: u64) -> Option<Stream> {
+        load_stream(&env, stream_id).ok()
+    }
+
+    /// Whether `sender` already consumed `nonce` via `create_stream_with_nonce`.
+    pub fn is_nonce_used(env: Env, sender: Address, nonce: u64) -> bool {
+        is_nonce_used(&env, &sender, nonce)
+    }
+
+    /// Whether the recipient of `stream_id` may pause and resume it.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    pub fn get_can_recipient_pause(env: Env, stream_id: u64) -> Result<bool, ContractError> {
+        Ok(load_stream(&env, stream_id)?.recipient_can_pause)
+    }
+
+    /// Read several streams in one call, for clients refreshing a list of known IDs.
+    ///
+    /// # Parameters
+    /// - `stream_ids`: At most `MAX_BULK_READ` IDs; duplicates are allowed
+    ///
+    /// # Returns
+    /// - `Vec<Option<Stream>>`: One entry per ID, in input order; `None` for IDs
+    ///   that do not exist (as `get_stream_state_option`)
+    ///
+    /// # Errors
+    /// - `InvalidParams` if more than `MAX_BULK_READ` IDs are passed
+    pub fn get_streams_bulk(
+        env: Env,
+        stream_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<soroban_sdk::Vec<Option<Stream>>, ContractError> {
+        if stream_ids.len() > MAX_BULK_READ {
+            return Err(ContractError::InvalidParams);
+        }
+        let mut streams = soroban_sdk::Vec::new(&env);
+        for stream_id in stream_ids.iter() {
+            streams.push_back(load_stream(&env, stream_id).ok());
+        }
+        Ok(streams)
+    }
+
+    /// Return the IDs of every stream created by `sender`, in creation order.
+    ///
+    /// # Parameters
+    /// - `sender`: Address that funded the streams
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Stream IDs in creation order; empty if `sender` never created a stream
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Streams stay in the index after they are completed or cancelled; use
+    ///   `get_stream_state` to filter by status
+    pub fn get_streams_by_sender(env: Env, sender: Address) -> soroban_sdk::Vec<u64> {
+        load_stream_index(&env, &DataKey::SenderStreams(sender))
+    }
+
+    /// Return the number of streams created by `sender`.
+    ///
+    /// Cheaper alternative to `get_streams_by_sender(sender).len()` for callers that
+    /// only need the count.
+    pub fn get_sender_stream_count(env: Env, sender: Address) -> u64 {
+        load_stream_index(&env, &DataKey::SenderStreams(sender)).len() as u64
+    }
+
+    /// Return the capital `sender` still has committed to running streams.
+    ///
+    /// Sums `deposit_amount - withdrawn_amount` over the sender's `Active`,
+    /// `Paused` and `Scheduled` streams, walking only the sender index.
+    ///
+    /// # Errors
+    /// - `ArithmeticOverflow` if the sum overflows `i128`
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - Cost grows with the number of streams the sender ever created
+    pub fn get_sender_active_deposit_total(
+        env: Env,
+        sender: Address,
+    ) -> Result<i128, ContractError> {
+        let mut total: i128 = 0;
+        for stream_id in load_stream_index(&env, &DataKey::SenderStreams(sender)).iter() {
+            let stream = load_stream(&env, stream_id)?;
+            if matches!(
+                stream.status,
+                StreamStatus::Active | StreamStatus::Paused | StreamStatus::Scheduled
+            ) {
+                total = total
+                    .checked_add(stream.deposit_amount - stream.withdrawn_amount)
+                    .ok_or(ContractError::ArithmeticOverflow)?;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Return the IDs of every stream paying `recipient`, in creation order.
+    ///
+    /// # Parameters
+    /// - `recipient`: Address receiving the streams
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Stream IDs in creation order; empty if no stream targets `recipient`
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    /// - Includes streams from every sender
+    /// - Streams stay in the index after they are completed or cancelled; use
+    ///   `get_stream_state` to filter by status
+    pub fn get_streams_by_recipient(env: Env, recipient: Address) -> soroban_sdk::Vec<u64> {
+        load_stream_index(&env, &DataKey::RecipientStreams(recipient))
+    }
+
+    /// Return what `recipient` could withdraw right now across all its streams.
+    ///
+    /// Sums `get_withdrawable_amount` over the recipient index, so paused and
+    /// terminal streams add nothing while cancelled streams still count their
+    /// unclaimed accrual.
+    ///
+    /// # Errors
+    /// - `ArithmeticOverflow` if the sum overflows `i128`
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - Cost grows with the number of streams ever paying the recipient
+    pub fn get_recipient_claimable_total(
+        env: Env,
+        recipient: Address,
+    ) -> Result<i128, ContractError> {
+        let mut total: i128 = 0;
+        for stream_id in load_stream_index(&env, &DataKey::RecipientStreams(recipient)).iter() {
+            let stream = load_stream(&env, stream_id)?;
+            total = total
+                .checked_add(Self::withdrawable_now(&env, &stream)?)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+        Ok(total)
+    }
+
+    /// Return one page of the streams paying `recipient`, optionally filtered by status.
+    ///
+    /// Walks the recipient index in creation order, keeps the IDs whose current
+    /// status equals `status_filter` (all of them when `None`), skips the first
+    /// `offset` matches and returns up to `limit` of the rest.
+    ///
+    /// # Parameters
+    /// - `recipient`: Address receiving the streams
+    /// - `offset`: Number of matching IDs to skip
+    /// - `limit`: Maximum number of IDs to return, clamped to `MAX_RECIPIENT_PAGE_SIZE`
+    /// - `status_filter`: Only return streams currently in this status
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Matching stream IDs in creation order; shorter than `limit`
+    ///   on the last page
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - Filtering loads every indexed stream up to the end of the page, so cost
+    ///   grows with `offset` as well as `limit`
+    /// - Named without a `_paginated` suffix because contract function names are
+    ///   limited to 32 characters
+    pub fn get_stream_ids_for_recipient(
+        env: Env,
+        recipient: Address,
+        offset: u32,
+        limit: u32,
+        status_filter: Option<StreamStatus>,
+    ) -> soroban_sdk::Vec<u64> {
+        let limit = limit.min(MAX_RECIPIENT_PAGE_SIZE);
+        let mut page = soroban_sdk::Vec::new(&env);
+        let mut skipped = 0u32;
+
+        for stream_id in load_stream_index(&env, &DataKey::RecipientStreams(recipient)).iter() {
+            if page.len() >= limit {
+                break;
+            }
+            if let Some(status) = status_filter {
+                match load_stream(&env, stream_id) {
+                    Ok(stream) if stream.status == status => {}
+                    _ => continue,
+                }
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            page.push_back(stream_id);
+        }
+        page
+    }
+
+    /// Return up to `limit` streams in ID order, starting at `from_id` (inclusive).
+    ///
+    /// Lets indexers walk every stream from scratch: pass `last.stream_id + 1` of
+    /// the previous page as the next `from_id`, and stop on an empty page.
+    ///
+    /// # Parameters
+    /// - `from_id`: First stream ID to include
+    /// - `limit`: Maximum number of streams to return, clamped to `MAX_PAGE_SIZE`
+    ///
+    /// # Returns
+    /// - `Vec<Stream>`: Existing streams with IDs `>= from_id`, in ascending order;
+    ///   missing IDs are skipped and the page is shorter if fewer streams remain
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    pub fn list_streams_paginated(env: Env, from_id: u64, limit: u32) -> soroban_sdk::Vec<Stream> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let next_id = get_stream_count(&env);
+        let mut page = soroban_sdk::Vec::new(&env);
+
+        let mut stream_id = from_id;
+        while stream_id < next_id && page.len() < limit {
+            if let Ok(stream) = load_stream(&env, stream_id) {
+                page.push_back(stream);
+            }
+            stream_id += 1;
+        }
+        page
+    }
+
+    /// Return up to `limit` IDs of streams currently in `status`, scanning the
+    /// sequential ID range from `from_id` (inclusive).
+    ///
+    /// Meant for indexers bootstrapping their state: there is no per-status index,
+    /// so each call loads streams in ID order until the page is full or the ID
+    /// range is exhausted. Pass the returned `next_from_id` as the next `from_id`;
+    /// the scan is done once it reaches `get_next_stream_id`.
+    ///
+    /// # Parameters
+    /// - `status`: Status to match
+    /// - `from_id`: First stream ID to examine
+    /// - `limit`: Maximum number of IDs to return, clamped to `MAX_STATUS_PAGE_SIZE`
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Matching stream IDs in ascending order
+    /// - `u64`: ID to resume from (one past the last stream examined)
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - Cost grows with the number of streams examined, not just the number
+    ///   returned; a rare status may need many pages
+    /// - Streams with hashed IDs (`create_stream_with_nonce`) are not reached
+    pub fn get_stream_ids_by_status(
+        env: Env,
+        status: StreamStatus,
+        from_id: u64,
+        limit: u32,
+    ) -> (soroban_sdk::Vec<u64>, u64) {
+        let limit = limit.min(MAX_STATUS_PAGE_SIZE);
+        let next_id = get_stream_count(&env);
+        let mut ids = soroban_sdk::Vec::new(&env);
+
+        let mut stream_id = from_id;
+        while stream_id < next_id && ids.len() < limit {
+            if matches!(load_stream(&env, stream_id), Ok(stream) if stream.status == status) {
+                ids.push_back(stream_id);
+            }
+            stream_id += 1;
+        }
+        (ids, stream_id)
+    }
+
+    /// Return the IDs of live streams whose completion is at least `threshold_bps`.
+    ///
+    /// Best-effort scan: only stream IDs below `max_scan` are examined, in ID
+    /// order, so streams beyond that point are not reported once the stream count
+    /// exceeds `max_scan`. Completion is measured as in `get_stream_completion_bps`;
+    /// `Completed` and `Cancelled` streams are skipped.
+    ///
+    /// # Parameters
+    /// - `threshold_bps`: Minimum completion in basis points (`10_000` = fully accrued)
+    /// - `max_scan`: Number of stream IDs to examine, bounding the cost of the call
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Matching stream IDs in ascending order
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - Cost grows linearly with `max_scan`; keep it small on large deployments
+    pub fn get_streams_near_completion(
+        env: Env,
+        threshold_bps: u32,
+        max_scan: u32,
+    ) -> soroban_sdk::Vec<u64> {
+        let end = get_stream_count(&env).min(u64::from(max_scan));
+        let now = env.ledger().timestamp();
+        let mut ids = soroban_sdk::Vec::new(&env);
+
+        for stream_id in 0..end {
+            let Ok(stream) = load_stream(&env, stream_id) else {
+                continue;
+            };
+            if matches!(
+                stream.status,
+                StreamStatus::Completed | StreamStatus::Cancelled | StreamStatus::Expired
+            ) {
+                continue;
+            }
+            let completion = match Self::accrued_at(&stream, now) {
+                Ok(accrued) => Self::stream_completion_bps(&stream, accrued),
+                Err(_) => continue,
+            };
+            if completion >= threshold_bps {
+                ids.push_back(stream_id);
+            }
+        }
+        ids
     }
 
     /// Return the contract version number.
@@ -971,11 +6342,40 @@ impl FluxoraStream {
     /// - This is a view function (read-only, no state changes)
     /// - No authorization required (public information)
     /// - Version is a compile-time constant; calling this costs minimal gas
-    /// - Increment `CONTRACT_VERSION` and redeploy when introducing breaking changes
+    /// - Increment `version::MAJOR` and redeploy when introducing breaking changes
     pub fn version(_env: Env) -> u32 {
         CONTRACT_VERSION
     }
 
+    /// Return the semantic version of the deployed contract.
+    ///
+    /// Reads the compile-time constants in the `version` module, so callers can
+    /// refuse to send transactions built for an incompatible contract and tell
+    /// which migration applies to a deployment.
+    ///
+    /// # Returns
+    /// - `ContractVersion`: `{ major, minor, patch }`; `major` always equals `version()`
+    ///
+    /// # Usage Notes
+    /// - This is a view function (read-only, no state changes)
+    /// - No authorization required (public information)
+    pub fn get_version(_env: Env) -> ContractVersion {
+        ContractVersion {
+            major: version::MAJOR,
+            minor: version::MINOR,
+            patch: version::PATCH,
+        }
+    }
+
+    /// Fail with `CancelDeadlinePassed` once the stream's `cancel_after` has passed.
+    /// Only the sender's cancel paths are subject to it.
+    fn require_before_cancel_deadline(env: &Env, stream: &Stream) -> Result<(), ContractError> {
+        if stream.cancel_after != 0 && env.ledger().timestamp() > stream.cancel_after {
+            return Err(ContractError::CancelDeadlinePassed);
+        }
+        Ok(())
+    }
+
     /// Internal helper to check authorization for sender or admin.
     fn require_sender_or_admin(_env: &Env, sender: &Address) {
         // Only the sender can manage their own stream via these paths.
@@ -983,10 +6383,295 @@ impl FluxoraStream {
         sender.require_auth();
     }
 
-    fn require_cancellable_status(env: &Env, status: StreamStatus) {
-        if status != StreamStatus::Active && status != StreamStatus::Paused {
-            panic_with_error!(env, ContractError::InvalidState);
+    fn set_protocol_paused(env: &Env, paused: bool) -> Result<(), ContractError> {
+        Self::require_not_frozen(env)?;
+        let mut config = get_config(env);
+        config.admin.require_auth();
+
+        if config.protocol_paused == paused {
+            return Err(if paused {
+                ContractError::ProtocolPaused
+            } else {
+                ContractError::InvalidState
+            });
+        }
+        config.protocol_paused = paused;
+        env.storage().instance().set(&DataKey::Config, &config);
+        bump_instance_ttl(env);
+
+        let action = if paused {
+            symbol_short!("paused")
+        } else {
+            symbol_short!("resumed")
+        };
+        env.events().publish(
+            (symbol_short!("protocol"), action),
+            env.ledger().timestamp(),
+        );
+        Ok(())
+    }
+
+    /// Fail with `SenderNotWhitelisted` if the whitelist is enabled and `sender`
+    /// is not on it.
+    fn require_whitelisted_sender(
+        env: &Env,
+        config: &Config,
+        sender: &Address,
+    ) -> Result<(), ContractError> {
+        if config.whitelist_enabled && !load_sender_whitelist(env).contains(sender) {
+            return Err(ContractError::SenderNotWhitelisted);
+        }
+        Ok(())
+    }
+
+    /// Fail with `RecipientBlacklisted` if `recipient` was blacklisted.
+    fn require_recipient_allowed(env: &Env, recipient: &Address) -> Result<(), ContractError> {
+        if is_recipient_blacklisted(env, recipient) {
+            return Err(ContractError::RecipientBlacklisted);
+        }
+        Ok(())
+    }
+
+    /// With `Config.strict_deposit_mode`, fail with `ExcessDeposit` if `stream`'s
+    /// net deposit is more than its rate can pay out by `end_time`. Stepped and
+    /// `Exponential` streams release their whole deposit anyway and are exempt.
+    fn require_exact_deposit(config: &Config, stream: &Stream) -> Result<(), ContractError> {
+        if !config.strict_deposit_mode
+            || stream.intervals > 0
+            || stream.curve_type != CurveType::Linear
+        {
+            return Ok(());
+        }
+        // A cliff unlock is paid on top of the linear part, which runs from the cliff.
+        let (origin, lump_sum) = if stream.cliff_unlock_amount > 0 {
+            (stream.cliff_time, stream.cliff_unlock_amount)
+        } else {
+            (stream.start_time, 0)
+        };
+        let required = Self::streamable_amount(
+            stream.rate_per_second,
+            stream.rate_denominator,
+            stream.end_time - origin,
+        )?
+        .checked_add(lump_sum)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+        if stream.deposit_amount > required {
+            return Err(ContractError::ExcessDeposit);
+        }
+        Ok(())
+    }
+
+    /// Fail with `GlobalCapReached` if creating `new_streams` more streams would
+    /// take the created-stream count past `config.global_stream_cap`. Counts every
+    /// creation path, so nonce-derived IDs cannot bypass the cap.
+    fn require_global_capacity(
+        env: &Env,
+        config: &Config,
+        new_streams: u32,
+    ) -> Result<(), ContractError> {
+        if get_streams_created(env).saturating_add(u64::from(new_streams))
+            > config.global_stream_cap
+        {
+            return Err(ContractError::GlobalCapReached);
+        }
+        Ok(())
+    }
+
+    /// Fail with `StreamLimitExceeded` if creating `new_streams` more streams would
+    /// take `sender` past `config.max_streams_per_sender` (0 = unlimited).
+    fn require_sender_capacity(
+        env: &Env,
+        config: &Config,
+        sender: &Address,
+        new_streams: u32,
+    ) -> Result<(), ContractError> {
+        if config.max_streams_per_sender == 0 {
+            return Ok(());
+        }
+        let existing = load_stream_index(env, &DataKey::SenderStreams(sender.clone())).len();
+        if existing.saturating_add(new_streams) > config.max_streams_per_sender {
+            return Err(ContractError::StreamLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Enforce `Config.min_deposit` on the gross deposit and `Config.min_rate` on
+    /// the effective rate `rate_per_second / rate_denominator` of linear streams.
+    /// Stepped streams have no rate, so only the deposit applies.
+    fn require_minimums(
+        config: &Config,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        rate_denominator: u64,
+        stepped: bool,
+    ) -> Result<(), ContractError> {
+        if deposit_amount < config.min_deposit {
+            return Err(ContractError::InvalidParams);
+        }
+        if stepped {
+            return Ok(());
+        }
+        let below_min_rate = match config.min_rate.checked_mul(rate_denominator as i128) {
+            Some(scaled_min) => rate_per_second < scaled_min,
+            None => true,
+        };
+        if below_min_rate {
+            return Err(ContractError::InvalidParams);
+        }
+        Ok(())
+    }
+
+    /// Enforce `Config.min_duration` on the whole `[start_time, end_time]` window.
+    /// The cliff may fall anywhere inside it.
+    fn require_min_duration(
+        config: &Config,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<(), ContractError> {
+        if end_time.saturating_sub(start_time) < config.min_duration {
+            return Err(ContractError::InvalidParams);
+        }
+        Ok(())
+    }
+
+    /// Fail with `ProtocolPaused` while the admin has halted the protocol.
+    fn require_protocol_active(env: &Env) -> Result<(), ContractError> {
+        if get_config(env).protocol_paused {
+            return Err(ContractError::ProtocolPaused);
+        }
+        Ok(())
+    }
+
+    /// Fail with `ContractFrozen` once `freeze_contract` has been called. Used by
+    /// every admin function that changes `Config` or the admin.
+    fn require_not_frozen(env: &Env) -> Result<(), ContractError> {
+        if is_config_frozen(env) {
+            return Err(ContractError::ContractFrozen);
+        }
+        Ok(())
+    }
+
+    fn require_cancellable_status(status: StreamStatus) -> Result<(), ContractError> {
+        match status {
+            StreamStatus::Active | StreamStatus::Paused | StreamStatus::Scheduled => Ok(()),
+            StreamStatus::Completed
+            | StreamStatus::Cancelled
+            | StreamStatus::Pending
+            | StreamStatus::Expired => Err(ContractError::InvalidState),
+        }
+    }
+
+    /// `(ledger_time, amount)` of the stream's next unlock, or `None` when nothing
+    /// is left to unlock or the stream is not running.
+    fn next_unlock(env: &Env, stream: &Stream) -> Option<(u64, i128)> {
+        match stream.status {
+            StreamStatus::Active | StreamStatus::Paused | StreamStatus::Scheduled => {}
+            StreamStatus::Completed
+            | StreamStatus::Cancelled
+            | StreamStatus::Pending
+            | StreamStatus::Expired => return None,
+        }
+
+        let now = env.ledger().timestamp();
+        let at = Self::schedule_time(stream, now);
+        let unlocked = Self::scheduled_amount(stream, at);
+        if unlocked >= stream.deposit_amount || at >= stream.end_time {
+            return None;
+        }
+
+        let next_at = if stream.intervals > 0 {
+            let steps =
+                accrual::steps_unlocked(stream.start_time, stream.end_time, stream.intervals, at);
+            accrual::step_unlock_time(
+                stream.start_time,
+                stream.end_time,
+                stream.intervals,
+                steps + 1,
+            )
+        } else if at < stream.cliff_time && stream.cliff_unlock_amount > 0 {
+            stream.cliff_time
+        } else if stream.rate_denominator > 1 && stream.curve_type == CurveType::Linear {
+            Self::next_fractional_unlock_time(stream, at, unlocked)
+        } else {
+            // Nothing unlocks before the cliff, and nothing at `start_time` itself.
+            stream.cliff_time.max(stream.start_time + 1).max(at + 1)
+        };
+        let amount = Self::scheduled_amount(stream, next_at) - unlocked;
+        Some((now + (next_at - at), amount))
+    }
+
+    /// First schedule time after `at` at which a sub-second-rate stream unlocks
+    /// another token, given `unlocked` tokens so far. Falls back to `end_time`.
+    fn next_fractional_unlock_time(stream: &Stream, at: u64, unlocked: i128) -> u64 {
+        // The linear part runs from the cliff for cliff-unlock streams, else from start.
+        let (origin, linear_unlocked) = if stream.cliff_unlock_amount > 0 {
+            (stream.cliff_time, unlocked - stream.cliff_unlock_amount)
+        } else {
+            (stream.start_time, unlocked)
+        };
+        let elapsed = (linear_unlocked + 1)
+            .checked_mul(stream.rate_denominator as i128)
+            .map(|scaled| {
+                scaled / stream.rate_per_second + i128::from(scaled % stream.rate_per_second != 0)
+            })
+            .and_then(|elapsed| u64::try_from(elapsed).ok())
+            .and_then(|elapsed| origin.checked_add(elapsed))
+            .unwrap_or(stream.end_time);
+        elapsed
+            .max(stream.cliff_time)
+            .max(at + 1)
+            .min(stream.end_time)
+    }
+
+    /// Extend the index under `key`, its first `MAX_TTL_BUMP_BATCH` streams and the
+    /// instance to `ledgers`. Returns the number of streams extended.
+    fn bump_index_ttl(env: &Env, key: &DataKey, ledgers: u32) -> Result<u32, ContractError> {
+        if ledgers == 0 || ledgers > env.storage().max_ttl() {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let storage = env.storage().persistent();
+        let ids: soroban_sdk::Vec<u64> = storage.get(key).unwrap_or(soroban_sdk::Vec::new(env));
+        if !ids.is_empty() {
+            storage.extend_ttl(key, ledgers, ledgers);
+        }
+        let mut bumped = 0u32;
+        for stream_id in ids.iter().take(MAX_TTL_BUMP_BATCH as usize) {
+            let stream_key = DataKey::Stream(stream_id);
+            if storage.has(&stream_key) {
+                storage.extend_ttl(&stream_key, ledgers, ledgers);
+                bumped += 1;
+            }
+        }
+        env.storage().instance().extend_ttl(ledgers, ledgers);
+        Ok(bumped)
+    }
+
+    /// Non-terminal streams can receive more funds; terminal ones map to their error.
+    fn require_extendable_status(status: StreamStatus) -> Result<(), ContractError> {
+        match status {
+            StreamStatus::Cancelled => Err(ContractError::StreamCancelled),
+            StreamStatus::Completed => Err(ContractError::StreamCompleted),
+            StreamStatus::Pending => Err(ContractError::StreamPending),
+            StreamStatus::Expired => Err(ContractError::StreamExpired),
+            StreamStatus::Active | StreamStatus::Paused | StreamStatus::Scheduled => Ok(()),
+        }
+    }
+
+    /// Require `status == expected`, mapping any other status to its specific error.
+    fn require_status(status: StreamStatus, expected: StreamStatus) -> Result<(), ContractError> {
+        if status == expected {
+            return Ok(());
         }
+        Err(match status {
+            StreamStatus::Paused => ContractError::StreamPaused,
+            StreamStatus::Completed => ContractError::StreamCompleted,
+            StreamStatus::Cancelled => ContractError::StreamCancelled,
+            StreamStatus::Pending => ContractError::StreamPending,
+            StreamStatus::Scheduled => ContractError::StreamScheduled,
+            StreamStatus::Expired => ContractError::StreamExpired,
+            StreamStatus::Active => ContractError::InvalidState,
+        })
     }
 }
 
@@ -1012,51 +6697,255 @@ impl FluxoraStream {
     /// 4. Sets stream status to `Cancelled`
     /// 5. Accrued amount remains for recipient to withdraw
     ///
+    /// # Returns
+    /// - `(sender_refund, recipient_accrued)`, as for `cancel_stream`
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is not `Active` or `Paused`
+    /// - `StreamNotCancellable` if the stream was created with `cancellable = false`
+    ///
     /// # Panics
-    /// - If stream is not `Active` or `Paused`
-    /// - If the stream does not exist
     /// - If caller is not the admin
     /// - If token transfer fails
     ///
     /// # Events
-    /// - Publishes `Cancelled(stream_id)` event on success
+    /// - Publishes `("STREAM", "cancelled", stream_id)` with a `StreamCancelled` payload
     ///
     /// # Usage Notes
     /// - Admin can cancel any stream regardless of sender
     /// - Use for emergency situations or dispute resolution
     /// - Sender still receives refund of unstreamed tokens
     /// - Recipient can still withdraw accrued amount
-    pub fn cancel_stream_as_admin(env: Env, stream_id: u64) -> Result<(), ContractError> {
+    pub fn cancel_stream_as_admin(env: Env, stream_id: u64) -> Result<(i128, i128), ContractError> {
         let admin = get_admin(&env);
         admin.require_auth();
 
         let mut stream = load_stream(&env, stream_id)?;
+        Self::apply_cancel(&env, &mut stream)
+    }
+
+    /// Emergency-cancel a stream as the contract admin, recording a reason code.
+    ///
+    /// Performs the same cancellation as `cancel_stream_as_admin` (unaccrued tokens
+    /// are refunded to the sender, accrued tokens stay claimable by the recipient via
+    /// `withdraw`) and additionally publishes a `StreamAdminCancelled` event carrying
+    /// `reason_code`, for regulatory or abuse-prevention audit trails.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to cancel
+    /// - `reason_code`: Off-chain defined code describing why the stream was cancelled
+    ///
+    /// # Authorization
+    /// - Requires authorization from the contract admin
+    ///
+    /// # Returns
+    /// - `(sender_refund, recipient_accrued)`, as for `cancel_stream`
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is not `Active` or `Paused`
+    /// - `StreamNotCancellable` if the stream was created with `cancellable = false`
+    ///
+    /// # Panics
+    /// - If caller is not the admin
+    /// - If token transfer fails
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "cancelled", stream_id)` with a `StreamCancelled` payload
+    /// - Publishes `("STREAM", "admin_cancelled", stream_id)` with a
+    ///   `StreamAdminCancelled` payload
+    pub fn admin_cancel_stream(
+        env: Env,
+        stream_id: u64,
+        reason_code: u32,
+    ) -> Result<(i128, i128), ContractError> {
+        get_admin(&env).require_auth();
+
+        let mut stream = load_stream(&env, stream_id)?;
+        let (sender_refund, recipient_accrued) = Self::apply_cancel(&env, &mut stream)?;
 
-        assert!(
-            stream.status == StreamStatus::Active || stream.status == StreamStatus::Paused,
-            "stream must be active or paused to cancel"
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "admin_cancelled"),
+            stream_id,
+            StreamAdminCancelled {
+                stream_id,
+                reason_code,
+                sender_refund,
+                recipient_accrued,
+            },
         );
+        Ok((sender_refund, recipient_accrued))
+    }
 
-        let accrued = Self::calculate_accrued(env.clone(), stream_id)?;
-        let unstreamed = stream.deposit_amount - accrued;
+    /// Block `recipient` from receiving streams and cancel the ones it already has.
+    ///
+    /// New streams to a blacklisted address fail with `RecipientBlacklisted`, as
+    /// does redirecting a stream to it with `update_recipient`. Every `Active`,
+    /// `Paused` or `Scheduled` cancellable stream paying `recipient` is cancelled
+    /// as by `cancel_stream_as_admin`: the sender is refunded the unstreamed part
+    /// and the recipient keeps what had accrued, which it can still withdraw.
+    /// Non-cancellable and `Pending` streams are left alone.
+    ///
+    /// # Returns
+    /// - `u32`: Number of streams cancelled
+    ///
+    /// # Authorization
+    /// - Requires authorization from the contract admin
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `recipient` is already blacklisted
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "cancelled", stream_id)` for each cancelled stream
+    /// - Publishes `("admin", "bl_add")` with the address
+    pub fn blacklist_recipient(env: Env, recipient: Address) -> Result<u32, ContractError> {
+        get_admin(&env).require_auth();
+        if is_recipient_blacklisted(&env, &recipient) {
+            return Err(ContractError::InvalidParams);
+        }
 
-        // CEI: update state before external token transfer to reduce reentrancy risk.
-        stream.status = StreamStatus::Cancelled;
-        stream.cancelled_at = Some(env.ledger().timestamp());
-        save_stream(&env, &stream);
+        let key = DataKey::BlacklistedRecipient(recipient.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
-        if unstreamed > 0 {
-            let token_client = token::Client::new(&env, &get_token(&env));
-            token_client.transfer(&env.current_contract_address(), &stream.sender, &unstreamed);
+        let mut cancelled = 0;
+        let stream_ids = load_stream_index(&env, &DataKey::RecipientStreams(recipient.clone()));
+        for stream_id in stream_ids.iter() {
+            // Reloaded each time: cancelling a swap stream also cancels its partner.
+            let mut stream = load_stream(&env, stream_id)?;
+            if Self::require_cancellable_status(stream.status).is_err() || !stream.cancellable {
+                continue;
+            }
+            Self::apply_cancel(&env, &mut stream)?;
+            cancelled += 1;
         }
 
+        env.events()
+            .publish((symbol_short!("admin"), symbol_short!("bl_add")), recipient);
+        Ok(cancelled)
+    }
+
+    /// Let a blacklisted `recipient` receive new streams again. Streams cancelled
+    /// by `blacklist_recipient` stay cancelled.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the contract admin
+    ///
+    /// # Errors
+    /// - `InvalidParams` if `recipient` is not blacklisted
+    ///
+    /// # Events
+    /// - Publishes `("admin", "bl_remove")` with the address
+    pub fn unblacklist_recipient(env: Env, recipient: Address) -> Result<(), ContractError> {
+        get_admin(&env).require_auth();
+        if !is_recipient_blacklisted(&env, &recipient) {
+            return Err(ContractError::InvalidParams);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BlacklistedRecipient(recipient.clone()));
+
         env.events().publish(
-            (symbol_short!("cancelled"), stream_id),
-            StreamEvent::Cancelled(stream_id),
+            (symbol_short!("admin"), symbol_short!("bl_remove")),
+            recipient,
         );
         Ok(())
     }
 
+    /// Whether `blacklist_recipient` has blocked `recipient`.
+    pub fn is_recipient_blacklisted(env: Env, recipient: Address) -> bool {
+        is_recipient_blacklisted(&env, &recipient)
+    }
+
+    /// Complete a stream immediately, paying the recipient the entire remaining
+    /// deposit — not just what has accrued.
+    ///
+    /// Last-resort recipient protection (sender gone, company dissolved, court
+    /// order). Works on any non-terminal stream (`Active`, `Paused`, `Scheduled`,
+    /// `Pending`), ignores the schedule, cliff and `cancellable`, and refunds
+    /// nothing to the sender.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to complete
+    /// - `reason_code`: Off-chain defined code describing why it was forced
+    ///
+    /// # Returns
+    /// - `i128`: Amount transferred to the recipient (`deposit_amount - withdrawn_amount`)
+    ///
+    /// # Authorization
+    /// - Requires authorization from the contract admin
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` if the stream is `Completed`, `Cancelled` or `Expired`
+    ///
+    /// # Events
+    /// - Publishes `("STREAM", "force_completed", stream_id)` with a
+    ///   `StreamForceCompleted` payload
+    pub fn admin_force_complete(
+        env: Env,
+        stream_id: u64,
+        reason_code: u32,
+    ) -> Result<i128, ContractError> {
+        get_admin(&env).require_auth();
+
+        let mut stream = load_stream(&env, stream_id)?;
+        match stream.status {
+            StreamStatus::Active
+            | StreamStatus::Paused
+            | StreamStatus::Scheduled
+            | StreamStatus::Pending => {}
+            StreamStatus::Completed | StreamStatus::Cancelled | StreamStatus::Expired => {
+                return Err(ContractError::InvalidState);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        let accrued = Self::accrued_at(&stream, now)?;
+        let recipient_amount = stream.deposit_amount - stream.withdrawn_amount;
+
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        move_status_count(&env, Some(stream.status), StreamStatus::Completed);
+        push_status_record(&env, &stream, StreamStatus::Completed);
+        stream.status = StreamStatus::Completed;
+        stream.completed_at = Some(now);
+        stream.withdrawn_amount = stream.deposit_amount;
+        stream.paused_at = None;
+        stream.paused_by = PauserKind::NotPaused;
+        stream.pending_expiry = None;
+        stream.approved_operator = None;
+        save_stream(&env, &stream);
+        adjust_protocol_total(&env, &DataKey::TotalLocked, -recipient_amount)?;
+        adjust_protocol_total(&env, &DataKey::TotalStreamed, recipient_amount)?;
+
+        if recipient_amount > 0 {
+            token::Client::new(&env, &stream.token).transfer(
+                &env.current_contract_address(),
+                &stream.recipient,
+                &recipient_amount,
+            );
+        }
+
+        publish_stream_event(
+            &env,
+            Symbol::new(&env, "force_completed"),
+            stream_id,
+            StreamForceCompleted {
+                stream_id,
+                reason_code,
+                accrued,
+                recipient_amount,
+            },
+        );
+        Ok(recipient_amount)
+    }
+
     /// Pause a payment stream as the contract admin.
     ///
     /// Administrative override to pause any stream, bypassing sender authorization.
@@ -1069,13 +6958,15 @@ impl FluxoraStream {
     /// # Authorization
     /// - Requires authorization from the contract admin (set during `init`)
     ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `StreamPaused` / `StreamCompleted` / `StreamCancelled` if the stream is not `Active`
+    ///
     /// # Panics
-    /// - If the stream is not in `Active` state
-    /// - If the stream does not exist
     /// - If caller is not the admin
     ///
     /// # Events
-    /// - Publishes `Paused(stream_id)` event on success
+    /// - Publishes `("STREAM", "paused", stream_id)` with a `StreamPaused` payload
     ///
     /// # Usage Notes
     /// - Admin can pause any stream regardless of sender
@@ -1086,20 +6977,7 @@ impl FluxoraStream {
         admin.require_auth();
 
         let mut stream = load_stream(&env, stream_id)?;
-
-        assert!(
-            stream.status == StreamStatus::Active,
-            "stream is not active"
-        );
-
-        stream.status = StreamStatus::Paused;
-        save_stream(&env, &stream);
-
-        env.events().publish(
-            (symbol_short!("paused"), stream_id),
-            StreamEvent::Paused(stream_id),
-        );
-        Ok(())
+        Self::apply_pause(&env, &mut stream, PauserKind::Admin)
     }
 
     /// Resume a paused payment stream as the contract admin.
@@ -1114,13 +6992,15 @@ impl FluxoraStream {
     /// # Authorization
     /// - Requires authorization from the contract admin (set during `init`)
     ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist
+    /// - `InvalidState` / `StreamCompleted` / `StreamCancelled` if the stream is not `Paused`
+    ///
     /// # Panics
-    /// - If the stream is not in `Paused` state
-    /// - If the stream does not exist
     /// - If caller is not the admin
     ///
     /// # Events
-    /// - Publishes `Resumed(stream_id)` event on success
+    /// - Publishes `("STREAM", "resumed", stream_id)` with a `StreamResumed` payload
     ///
     /// # Usage Notes
     /// - Admin can resume any paused stream regardless of sender
@@ -1129,21 +7009,96 @@ impl FluxoraStream {
     pub fn resume_stream_as_admin(env: Env, stream_id: u64) -> Result<(), ContractError> {
         get_admin(&env).require_auth();
         let mut stream = load_stream(&env, stream_id)?;
+        Self::apply_resume(&env, &mut stream, PauserKind::Admin)
+    }
 
-        assert!(
-            stream.status == StreamStatus::Paused,
-            "stream is not paused"
-        );
+    /// Freeze a stream for compliance; alias of `pause_stream_as_admin`.
+    ///
+    /// The stream records `paused_by = Admin`, so its sender cannot resume it.
+    pub fn admin_pause_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::pause_stream_as_admin(env, stream_id)
+    }
 
-        stream.status = StreamStatus::Active;
-        save_stream(&env, &stream);
+    /// Lift any pause, including an admin freeze; alias of `resume_stream_as_admin`.
+    pub fn admin_resume_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::resume_stream_as_admin(env, stream_id)
+    }
 
-        env.events().publish(
-            (symbol_short!("resumed"), stream_id),
-            StreamEvent::Resumed(stream_id),
-        );
+    /// Halt every user-facing mutating function during a security incident.
+    ///
+    /// While paused, stream creation, withdrawals, pause/resume, cancellation,
+    /// top-ups, extensions, acceptance and operator changes fail with
+    /// `ProtocolPaused`. Views keep working, and the admin's own `_as_admin`
+    /// entry points stay available so streams can still be wound down.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `ProtocolPaused` if the protocol is already paused
+    ///
+    /// # Events
+    /// - Publishes `("protocol", "paused")` with the ledger timestamp
+    pub fn admin_pause_protocol(env: Env) -> Result<(), ContractError> {
+        Self::set_protocol_paused(&env, true)
+    }
+
+    /// Lift a pause set by `admin_pause_protocol`.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` after `freeze_contract`
+    /// - `InvalidState` if the protocol is not paused
+    ///
+    /// # Events
+    /// - Publishes `("protocol", "resumed")` with the ledger timestamp
+    pub fn admin_resume_protocol(env: Env) -> Result<(), ContractError> {
+        Self::set_protocol_paused(&env, false)
+    }
+
+    /// Whether the admin has paused the protocol.
+    pub fn is_protocol_paused(env: Env) -> bool {
+        get_config(&env).protocol_paused
+    }
+
+    /// Make the contract configuration permanently immutable.
+    ///
+    /// Afterwards every admin function that changes `Config` or the admin —
+    /// `update_config`, the `set_*` setters, `admin_set_token`, `set_admin`,
+    /// `propose_admin`, `accept_admin`, `cancel_admin_proposal`, `admin_pause_protocol` and
+    /// `admin_resume_protocol` — fails with `ContractFrozen`. Stream operations,
+    /// admin stream overrides, `withdraw_protocol_fees` and views are unaffected.
+    /// There is no way to unfreeze.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the current admin address
+    ///
+    /// # Errors
+    /// - `ContractFrozen` if the contract is already frozen
+    /// - `ProtocolPaused` while the protocol is paused (it could never be resumed)
+    ///
+    /// # Events
+    /// - Publishes `("config", "frozen")` with `true`
+    pub fn freeze_contract(env: Env) -> Result<(), ContractError> {
+        Self::require_not_frozen(&env)?;
+        get_admin(&env).require_auth();
+        Self::require_protocol_active(&env)?;
+
+        env.storage().instance().set(&DataKey::ConfigFrozen, &true);
+        bump_instance_ttl(&env);
+
+        env.events()
+            .publish((symbol_short!("config"), symbol_short!("frozen")), true);
         Ok(())
     }
+
+    /// Whether `freeze_contract` has been called.
+    pub fn is_frozen(env: Env) -> bool {
+        is_config_frozen(&env)
+    }
 }
 
 #[cfg(test)]
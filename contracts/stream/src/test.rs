@@ -7,7 +7,7 @@ use soroban_sdk::{
     Address, Env, FromVal, TryFromVal, Vec,
 };
 
-use crate::{FluxoraStream, FluxoraStreamClient, StreamEvent, StreamStatus};
+use crate::{FluxoraStream, FluxoraStreamClient, StreamStatus};
 
 // ---------------------------------------------------------------------------
 // Test helpers
@@ -127,6 +127,22 @@ impl<'a> TestContext<'a> {
         )
     }
 
+    /// Create the default stream shape (1000 units over 0..1000 at 1/s, no cliff)
+    /// with non-default `options`.
+    fn create_stream_with(&self, options: crate::StreamOptions) -> u64 {
+        self.env.ledger().set_timestamp(0);
+        self.client().create_stream_with_options(
+            &self.sender,
+            &self.recipient,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &options,
+        )
+    }
+
     /// Create a stream with a cliff at t=500 out of 1000s.
     fn create_cliff_stream(&self) -> u64 {
         self.env.ledger().set_timestamp(0);
@@ -189,7 +205,7 @@ fn test_init_stores_token_and_admin() {
 }
 
 #[test]
-#[should_panic(expected = "already initialised")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_init_second_call_fails() {
     let env = Env::default();
     let contract_id = env.register_contract(None, FluxoraStream);
@@ -204,7 +220,7 @@ fn test_init_second_call_fails() {
 }
 
 #[test]
-#[should_panic(expected = "contract not initialised: missing config")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn test_get_config_before_init_fails() {
     let env = Env::default();
     let contract_id = env.register_contract(None, FluxoraStream);
@@ -220,7 +236,7 @@ fn test_get_config_before_init_fails() {
 /// The panic message "contract not initialised: missing config" provides clear
 /// feedback to integrators that init() must be called first.
 #[test]
-#[should_panic(expected = "contract not initialised: missing config")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn test_get_config_uninitialized_contract_panics() {
     let env = Env::default();
     let contract_id = env.register_contract(None, FluxoraStream);
@@ -248,7 +264,7 @@ fn test_init_stores_config() {
 }
 
 #[test]
-#[should_panic(expected = "already initialised")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_init_twice_panics() {
     let env = Env::default();
     env.mock_all_auths();
@@ -330,7 +346,7 @@ fn test_init_with_different_addresses() {
 
 /// Re-init with the exact same token and admin must still panic.
 #[test]
-#[should_panic(expected = "already initialised")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_reinit_same_token_same_admin_panics() {
     let env = Env::default();
     env.mock_all_auths();
@@ -348,7 +364,7 @@ fn test_reinit_same_token_same_admin_panics() {
 
 /// Re-init with a different token but same admin must panic.
 #[test]
-#[should_panic(expected = "already initialised")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_reinit_different_token_same_admin_panics() {
     let env = Env::default();
     env.mock_all_auths();
@@ -367,7 +383,7 @@ fn test_reinit_different_token_same_admin_panics() {
 
 /// Re-init with same token but a different admin must panic.
 #[test]
-#[should_panic(expected = "already initialised")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_reinit_same_token_different_admin_panics() {
     let env = Env::default();
     env.mock_all_auths();
@@ -416,8 +432,8 @@ fn test_config_unchanged_after_failed_reinit() {
         })
         .unwrap_or("no message");
     assert!(
-        panic_msg.contains("already initialised"),
-        "panic message should contain 'already initialised', but was '{}'",
+        panic_msg.contains("Error(Contract, #3)"),
+        "panic message should contain AlreadyInitialized (#3), but was '{}'",
         panic_msg
     );
 
@@ -471,8 +487,8 @@ fn test_operations_work_after_failed_reinit() {
         })
         .unwrap_or("no message");
     assert!(
-        panic_msg.contains("already initialised"),
-        "panic message should contain 'already initialised', but was '{}'",
+        panic_msg.contains("Error(Contract, #3)"),
+        "panic message should contain AlreadyInitialized (#3), but was '{}'",
         panic_msg
     );
 
@@ -547,11 +563,11 @@ fn test_withdraw_emits_event() {
     let events = ctx.env.events().all();
     let event = events.last().unwrap();
 
-    let event_data = crate::Withdrawal::try_from_val(&ctx.env, &event.2).unwrap();
+    let event_data = crate::StreamWithdrawn::try_from_val(&ctx.env, &event.2).unwrap();
     assert_eq!(event_data.stream_id, stream_id);
-    assert_eq!(event_data.recipient, ctx.recipient);
     assert_eq!(event_data.amount, withdrawn);
     assert_eq!(event_data.amount, 500);
+    assert_eq!(event_data.withdrawn_total, 500);
 }
 
 /// Create a stream, perform partial withdraws then a final withdraw, and
@@ -592,7 +608,7 @@ fn test_withdraw_partial_then_full_updates_state() {
 }
 
 #[test]
-#[should_panic(expected = "deposit_amount must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_zero_deposit_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -608,7 +624,7 @@ fn test_create_stream_zero_deposit_panics() {
 }
 
 #[test]
-#[should_panic(expected = "start_time must be before end_time")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_invalid_times_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -701,6 +717,8 @@ fn test_create_stream_multiple_loop() {
     let mut counter = 0;
     let mut stream_vec = Vec::new(&ctx.env);
     loop {
+        // Each create is its own transaction on-chain, with its own budget.
+        ctx.env.budget().reset_default();
         let stream_id = ctx.client().create_stream(
             &ctx.sender,
             &ctx.recipient,
@@ -813,7 +831,7 @@ fn test_create_stream_long_duration_accepted() {
 
 /// end_time exactly equal to start_time must panic
 #[test]
-#[should_panic(expected = "start_time must be before end_time")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_end_equals_start_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -830,7 +848,7 @@ fn test_create_stream_end_equals_start_panics() {
 
 /// end_time strictly less than start_time must panic
 #[test]
-#[should_panic(expected = "start_time must be before end_time")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_end_before_start_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -847,7 +865,7 @@ fn test_create_stream_end_before_start_panics() {
 
 /// end_time exactly one second before start_time (boundary)
 #[test]
-#[should_panic(expected = "start_time must be before end_time")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_end_one_less_than_start_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -866,7 +884,7 @@ fn test_create_stream_end_one_less_than_start_panics() {
 
 /// cliff_time one second before start_time (lower boundary violation)
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_cliff_one_before_start_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -883,7 +901,7 @@ fn test_create_stream_cliff_one_before_start_panics() {
 
 /// cliff_time one second after end_time (upper boundary violation)
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_cliff_one_after_end_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -900,7 +918,7 @@ fn test_create_stream_cliff_one_after_end_panics() {
 
 /// cliff_time far before start_time
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_cliff_far_before_start_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -910,14 +928,14 @@ fn test_create_stream_cliff_far_before_start_panics() {
         &1000_i128,
         &1_i128,
         &500u64,
-        &0u64, // cliff far before start
+        &100u64, // cliff far before start (0 would mean "no cliff")
         &1500u64,
     );
 }
 
 /// cliff_time far after end_time
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_cliff_far_after_end_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -974,7 +992,7 @@ fn test_create_stream_cliff_at_end_valid() {
 
 /// deposit_amount of zero must panic
 #[test]
-#[should_panic(expected = "deposit_amount must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_deposit_zero_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -991,7 +1009,7 @@ fn test_create_stream_deposit_zero_panics() {
 
 /// deposit_amount of -1 must panic
 #[test]
-#[should_panic(expected = "deposit_amount must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_deposit_minus_one_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1008,7 +1026,7 @@ fn test_create_stream_deposit_minus_one_panics() {
 
 /// deposit_amount of i128::MIN must panic
 #[test]
-#[should_panic(expected = "deposit_amount must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_deposit_i128_min_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1045,7 +1063,7 @@ fn test_create_stream_deposit_one_valid() {
 
 /// rate_per_second of zero must panic
 #[test]
-#[should_panic(expected = "rate_per_second must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_rate_zero_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1062,7 +1080,7 @@ fn test_create_stream_rate_zero_panics() {
 
 /// rate_per_second of -1 must panic
 #[test]
-#[should_panic(expected = "rate_per_second must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_rate_minus_one_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1079,7 +1097,7 @@ fn test_create_stream_rate_minus_one_panics() {
 
 /// rate_per_second of i128::MIN must panic
 #[test]
-#[should_panic(expected = "rate_per_second must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_rate_i128_min_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1116,7 +1134,7 @@ fn test_create_stream_rate_one_valid() {
 
 /// deposit one less than required (rate * duration - 1) must panic
 #[test]
-#[should_panic(expected = "deposit_amount must cover total streamable amount")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_create_stream_deposit_one_less_than_required_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1153,7 +1171,7 @@ fn test_create_stream_deposit_exactly_required_valid() {
 
 /// deposit much less than rate * duration must panic
 #[test]
-#[should_panic(expected = "deposit_amount must cover total streamable amount")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_create_stream_deposit_far_below_required_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1192,7 +1210,7 @@ fn test_create_stream_deposit_above_required_valid() {
 
 /// sender and recipient are the same address must panic
 #[test]
-#[should_panic(expected = "sender and recipient must be different")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_sender_is_recipient_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1231,7 +1249,7 @@ fn test_create_stream_different_sender_recipient_valid() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "rate_per_second must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_zero_rate_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1247,7 +1265,7 @@ fn test_create_stream_zero_rate_panics() {
 }
 
 #[test]
-#[should_panic(expected = "sender and recipient must be different")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_sender_equals_recipient_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1267,7 +1285,7 @@ fn test_create_stream_sender_equals_recipient_panics() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_cliff_before_start_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(100);
@@ -1283,7 +1301,7 @@ fn test_create_stream_cliff_before_start_panics() {
 }
 
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_cliff_after_end_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -1337,7 +1355,7 @@ fn test_create_stream_cliff_equals_end_succeeds() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "deposit_amount must cover total streamable amount")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_create_stream_deposit_less_than_total_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -2144,24 +2162,40 @@ fn test_admin_can_resume_stream() {
 }
 
 #[test]
-#[should_panic(expected = "stream is already paused")]
-fn test_pause_already_paused_panics() {
+fn test_pause_already_paused_is_noop() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
     ctx.client().pause_stream(&stream_id);
-    ctx.client().pause_stream(&stream_id); // second pause should panic
+    let events_before = ctx.env.events().all().len();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream(&stream_id); // a racing second pause succeeds quietly
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.paused_at, Some(100));
+    assert_eq!(ctx.env.events().all().len(), events_before);
+    assert_eq!(ctx.client().get_status_history(&stream_id).len(), 2);
 }
 
 #[test]
-#[should_panic(expected = "stream is active, not paused")]
-fn test_resume_active_stream_panics() {
+fn test_resume_active_stream_is_noop() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
+    let events_before = ctx.env.events().all().len();
+    ctx.client().resume_stream(&stream_id);
+    assert_eq!(ctx.env.events().all().len(), events_before);
+
+    ctx.client().pause_stream(&stream_id);
     ctx.client().resume_stream(&stream_id);
+    ctx.client().resume_stream(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(ctx.client().get_status_history(&stream_id).len(), 3);
 }
 
 #[test]
-#[should_panic(expected = "stream is completed")]
+#[should_panic(expected = "Error(Contract, #10)")]
 fn test_resume_completed_stream_panics() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -2173,7 +2207,7 @@ fn test_resume_completed_stream_panics() {
 }
 
 #[test]
-#[should_panic(expected = "stream is cancelled")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_resume_cancelled_stream_panics() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -2184,7 +2218,7 @@ fn test_resume_cancelled_stream_panics() {
 }
 
 #[test]
-#[should_panic(expected = "stream must be active to pause")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_pause_cancelled_stream_panics() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -2233,7 +2267,8 @@ fn test_cancel_stream_as_admin() {
     let stream_id = ctx.create_default_stream();
     ctx.env.ledger().set_timestamp(0);
 
-    ctx.client().cancel_stream_as_admin(&stream_id);
+    let split = ctx.client().cancel_stream_as_admin(&stream_id);
+    assert_eq!(split, (1000, 0));
 
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.status, StreamStatus::Cancelled);
@@ -2371,7 +2406,7 @@ fn test_withdraw_full_completes_stream() {
 }
 
 #[test]
-#[should_panic(expected = "cannot withdraw from paused stream")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_withdraw_from_paused_stream_completes_if_full() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -2396,7 +2431,7 @@ fn test_withdraw_nothing_panics() {
 }
 
 #[test]
-#[should_panic(expected = "stream already completed")]
+#[should_panic(expected = "Error(Contract, #10)")]
 fn test_withdraw_already_completed_panics() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -2649,7 +2684,7 @@ fn test_withdraw_not_recipient_unauthorized() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "cannot withdraw from paused stream")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_withdraw_paused_stream_panics() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -2758,26 +2793,36 @@ fn test_pause_resume_events() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
 
+    ctx.env.ledger().set_timestamp(100);
     ctx.client().pause_stream(&stream_id);
 
     let events = ctx.env.events().all();
     let last_event = events.last().unwrap();
 
     // Check pause event
-    // The event is published as ((symbol_short!("paused"), stream_id), StreamEvent::Paused(stream_id))
+    // The event is published as (("STREAM", "paused", stream_id), StreamPaused { .. })
     assert_eq!(
-        Option::<StreamEvent>::from_val(&ctx.env, &last_event.2).unwrap(),
-        StreamEvent::Paused(stream_id)
+        crate::StreamPaused::from_val(&ctx.env, &last_event.2),
+        crate::StreamPaused {
+            stream_id,
+            paused_at: 100,
+            admin: false,
+        }
     );
 
+    ctx.env.ledger().set_timestamp(250);
     ctx.client().resume_stream(&stream_id);
     let events = ctx.env.events().all();
     let last_event = events.last().unwrap();
 
     // Check resume event
     assert_eq!(
-        Option::<StreamEvent>::from_val(&ctx.env, &last_event.2).unwrap(),
-        StreamEvent::Resumed(stream_id)
+        crate::StreamResumed::from_val(&ctx.env, &last_event.2),
+        crate::StreamResumed {
+            stream_id,
+            resumed_at: 250,
+            admin: false,
+        }
     );
 }
 
@@ -2786,6 +2831,7 @@ fn test_cancel_event() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
 
+    ctx.env.ledger().set_timestamp(300);
     ctx.client().cancel_stream(&stream_id);
 
     let events = ctx.env.events().all();
@@ -2793,8 +2839,12 @@ fn test_cancel_event() {
 
     // Check cancel event
     assert_eq!(
-        Option::<StreamEvent>::from_val(&ctx.env, &last_event.2).unwrap(),
-        StreamEvent::Cancelled(stream_id)
+        crate::StreamCancelled::from_val(&ctx.env, &last_event.2),
+        crate::StreamCancelled {
+            stream_id,
+            sender_refund: 700,
+            recipient_accrued: 300,
+        }
     );
 }
 
@@ -3261,7 +3311,7 @@ fn test_cancel_stream_admin_success() {
 
 /// Test creating a stream with negative deposit amount panics
 #[test]
-#[should_panic(expected = "deposit_amount must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_negative_deposit_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -3278,7 +3328,7 @@ fn test_create_stream_negative_deposit_panics() {
 
 /// Test creating a stream with negative rate_per_second panics
 #[test]
-#[should_panic(expected = "rate_per_second must be positive")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_negative_rate_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -3295,7 +3345,7 @@ fn test_create_stream_negative_rate_panics() {
 
 /// Test creating a stream where start_time equals end_time panics
 #[test]
-#[should_panic(expected = "start_time must be before end_time")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_equal_start_end_times_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -3329,7 +3379,7 @@ fn test_create_stream_cliff_equals_start() {
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.cliff_time, 100);
     assert_eq!(state.start_time, 100);
-    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.status, StreamStatus::Scheduled);
 }
 
 /// Test creating a stream with cliff_time equal to end_time (valid edge case)
@@ -3498,7 +3548,7 @@ fn test_create_stream_future_start_time() {
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.start_time, 1000);
     assert_eq!(state.end_time, 2000);
-    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.status, StreamStatus::Scheduled);
 }
 
 /// Test token balance changes after creating stream
@@ -3595,12 +3645,13 @@ fn test_create_stream_all_fields_correct() {
     assert_eq!(state.cliff_time, cliff);
     assert_eq!(state.end_time, end);
     assert_eq!(state.withdrawn_amount, 0);
-    assert_eq!(state.status, StreamStatus::Active);
+    // start_time is in the future, so the stream waits in Scheduled.
+    assert_eq!(state.status, StreamStatus::Scheduled);
 }
 
 /// Test that creating stream with same sender and recipient panics
 #[test]
-#[should_panic(expected = "sender and recipient must be different")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_self_stream_panics() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -3698,7 +3749,7 @@ fn test_withdraw_multiple_times() {
 }
 
 #[test]
-#[should_panic(expected = "cliff_time must be within [start_time, end_time]")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_invalid_cliff_panics() {
     let ctx = TestContext::setup();
     ctx.client().create_stream(
@@ -4504,7 +4555,7 @@ fn test_withdraw_zero_before_cliff() {
 /// Test withdraw when accrued - withdrawn = 0 after full withdrawal
 /// Should panic with "stream already completed"
 #[test]
-#[should_panic(expected = "stream already completed")]
+#[should_panic(expected = "Error(Contract, #10)")]
 fn test_withdraw_zero_after_full_withdrawal() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -5442,8 +5493,8 @@ fn test_failed_create_stream_does_not_advance_counter() {
         })
         .unwrap_or("no message");
     assert!(
-        panic_msg.contains("deposit_amount must cover total streamable amount"),
-        "panic message should contain 'deposit_amount must cover total streamable amount', but was '{}'",
+        panic_msg.contains("Error(Contract, #6)"),
+        "panic message should contain UnfundedDeposit (#6), but was '{}'",
         panic_msg
     );
 
@@ -5844,7 +5895,7 @@ fn test_withdraw_after_cancel_status_stays_cancelled() {
 /// Test: Verify that completed stream cannot be withdrawn again
 /// Accessing a completed stream's withdraw should panic
 #[test]
-#[should_panic(expected = "stream already completed")]
+#[should_panic(expected = "Error(Contract, #10)")]
 fn test_withdraw_completed_stream_panics() {
     let ctx = TestContext::setup();
     let stream_id = ctx.create_default_stream();
@@ -6067,7 +6118,7 @@ fn test_create_streams_batch_success() {
 }
 
 #[test]
-#[should_panic(expected = "deposit_amount must cover total streamable amount")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_create_streams_batch_atomic_failure() {
     let ctx = TestContext::setup();
     ctx.env.ledger().set_timestamp(0);
@@ -6099,7 +6150,7 @@ fn test_create_streams_batch_atomic_failure() {
 }
 
 #[test]
-#[should_panic(expected = "sender and recipient must be different")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_streams_batch_sender_recipient_panic() {
     let ctx = TestContext::setup();
 
@@ -6180,3 +6231,9843 @@ fn test_create_streams_batch_strict_auth() {
     let stream_ids = ctx.client().create_streams(&ctx.sender, &streams);
     assert_eq!(stream_ids.len(), 2);
 }
+
+// ---------------------------------------------------------------------------
+// Tests — batch_withdraw
+// ---------------------------------------------------------------------------
+
+/// Create `n` default streams (1000 tokens over 1000s) from sender to recipient.
+fn create_default_streams(ctx: &TestContext, n: u32) -> Vec<u64> {
+    let mut ids = Vec::new(&ctx.env);
+    for _ in 0..n {
+        ids.push_back(ctx.create_default_stream());
+    }
+    ids
+}
+
+#[test]
+fn test_batch_withdraw_five_streams() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &(5_000_i128));
+    let ids = create_default_streams(&ctx, 5);
+
+    ctx.env.ledger().set_timestamp(300);
+    let amounts = ctx.client().batch_withdraw(&ids);
+
+    assert_eq!(amounts.len(), 5);
+    for amount in amounts.iter() {
+        assert_eq!(amount, 300);
+    }
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1500);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 3500);
+
+    for id in ids.iter() {
+        let state = ctx.client().get_stream_state(&id);
+        assert_eq!(state.withdrawn_amount, 300);
+        assert_eq!(state.status, StreamStatus::Active);
+    }
+}
+
+#[test]
+fn test_batch_withdraw_returns_amounts_in_input_order() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let second = ctx.create_default_stream();
+
+    // Drain `first` partially so the two streams have different withdrawable amounts.
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&first);
+
+    ctx.env.ledger().set_timestamp(500);
+    let amounts = ctx
+        .client()
+        .batch_withdraw(&soroban_sdk::vec![&ctx.env, second, first]);
+
+    assert_eq!(amounts, soroban_sdk::vec![&ctx.env, 500_i128, 300_i128]);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+}
+
+#[test]
+fn test_batch_withdraw_paused_stream_rolls_back_whole_batch() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &(5_000_i128));
+    let ids = create_default_streams(&ctx, 5);
+    ctx.client().pause_stream(&ids.get(3).unwrap());
+
+    ctx.env.ledger().set_timestamp(300);
+    let result = ctx.client().try_batch_withdraw(&ids);
+    assert!(result.is_err());
+
+    // No stream was touched and no tokens moved.
+    for id in ids.iter() {
+        assert_eq!(ctx.client().get_stream_state(&id).withdrawn_amount, 0);
+    }
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 5000);
+}
+
+#[test]
+fn test_batch_withdraw_past_end_completes_all_streams() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &(5_000_i128));
+    let ids = create_default_streams(&ctx, 5);
+
+    ctx.env.ledger().set_timestamp(5000);
+    let amounts = ctx.client().batch_withdraw(&ids);
+
+    for amount in amounts.iter() {
+        assert_eq!(amount, 1000);
+    }
+    for id in ids.iter() {
+        let state = ctx.client().get_stream_state(&id);
+        assert_eq!(state.withdrawn_amount, 1000);
+        assert_eq!(state.status, StreamStatus::Completed);
+    }
+    assert_eq!(ctx.token().balance(&ctx.recipient), 5000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_batch_withdraw_mixed_recipients_panics() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let other_recipient = Address::generate(&ctx.env);
+    let second = ctx.client().create_stream(
+        &ctx.sender,
+        &other_recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client()
+        .batch_withdraw(&soroban_sdk::vec![&ctx.env, first, second]);
+}
+
+#[test]
+fn test_batch_withdraw_empty_is_noop() {
+    let ctx = TestContext::setup();
+    let amounts = ctx.client().batch_withdraw(&Vec::new(&ctx.env));
+    assert_eq!(amounts.len(), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — calculate_accrued_at
+// ---------------------------------------------------------------------------
+
+/// Stream starting at t=100 with a cliff at t=300, ending at t=1100 (1 token/s).
+fn create_delayed_cliff_stream(ctx: &TestContext) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &300u64,
+        &1100u64,
+    )
+}
+
+#[test]
+fn test_calculate_accrued_at_before_start_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &0), 0);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &99), 0);
+}
+
+#[test]
+fn test_calculate_accrued_at_before_cliff_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &100), 0);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &299), 0);
+}
+
+#[test]
+fn test_calculate_accrued_at_between_cliff_and_end_is_linear() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &300), 200);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &600), 500);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &1099), 999);
+}
+
+#[test]
+fn test_calculate_accrued_at_after_end_is_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &1100), 1000);
+    assert_eq!(
+        ctx.client().calculate_accrued_at(&stream_id, &u64::MAX),
+        1000
+    );
+}
+
+#[test]
+fn test_calculate_accrued_at_does_not_depend_on_ledger_time() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(1_000_000);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &600), 500);
+}
+
+#[test]
+fn test_calculate_accrued_at_matches_calculate_accrued_at_ledger_time() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+
+    for t in [0u64, 50, 100, 299, 300, 301, 750, 1099, 1100, 5000] {
+        ctx.env.ledger().set_timestamp(t);
+        assert_eq!(
+            ctx.client().calculate_accrued_at(&stream_id, &t),
+            ctx.client().calculate_accrued(&stream_id),
+            "mismatch at t={t}"
+        );
+    }
+}
+
+#[test]
+fn test_calculate_accrued_at_cancelled_stream_is_frozen() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &200), 200);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &400), 400);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &900), 400);
+    assert_eq!(
+        ctx.client().calculate_accrued_at(&stream_id, &900),
+        ctx.client().calculate_accrued(&stream_id)
+    );
+}
+
+#[test]
+fn test_calculate_accrued_at_completed_stream_follows_schedule() {
+    let ctx = TestContext::setup();
+    let stream_id = create_delayed_cliff_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(1100);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &50), 0);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &299), 0);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &600), 500);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &1100), 1000);
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &5000), 1000);
+}
+
+#[test]
+fn test_calculate_accrued_at_stream_not_found() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_calculate_accrued_at(&999, &0);
+    assert!(result.is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_withdrawable_amount
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_withdrawable_amount_before_any_withdrawal_equals_accrued() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 400);
+    assert_eq!(
+        ctx.client().get_withdrawable_amount(&stream_id),
+        ctx.client().calculate_accrued(&stream_id)
+    );
+}
+
+#[test]
+fn test_get_withdrawable_amount_after_partial_withdrawal() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(700);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 400);
+}
+
+#[test]
+fn test_get_withdrawable_amount_completed_stream_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_get_withdrawable_amount_paused_stream_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+
+    ctx.client().resume_stream(&stream_id);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 500);
+}
+
+#[test]
+fn test_get_withdrawable_amount_before_cliff_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+
+    ctx.env.ledger().set_timestamp(499);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_get_withdrawable_amount_cancelled_stream_matches_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().cancel_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(900);
+    let withdrawable = ctx.client().get_withdrawable_amount(&stream_id);
+    assert_eq!(withdrawable, 250);
+    assert_eq!(ctx.client().withdraw(&stream_id), withdrawable);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_get_withdrawable_amount_does_not_mutate_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(600);
+    ctx.client().get_withdrawable_amount(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 0);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — typed ContractError codes via generated try_* client methods
+// ---------------------------------------------------------------------------
+
+use crate::ContractError;
+
+#[test]
+fn test_error_already_initialized() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_init(&ctx.token_id, &ctx.admin);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_error_not_initialized() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, FluxoraStream);
+    let client = FluxoraStreamClient::new(&env, &contract_id);
+    let expected = soroban_sdk::Error::from_contract_error(ContractError::NotInitialized as u32);
+    assert!(matches!(client.try_get_config(), Err(Ok(e)) if e == expected));
+}
+
+#[test]
+fn test_error_stream_not_found() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().try_withdraw(&42),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+    assert!(matches!(
+        ctx.client().try_get_stream_state(&42),
+        Err(Ok(ContractError::StreamNotFound))
+    ));
+}
+
+#[test]
+fn test_error_try_create_stream_invalid_params() {
+    let ctx = TestContext::setup();
+    let client = ctx.client();
+    let s = &ctx.sender;
+    let r = &ctx.recipient;
+
+    // deposit <= 0
+    assert_eq!(
+        client.try_create_stream(s, r, &0, &1, &0, &0, &1000),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    // rate <= 0
+    assert_eq!(
+        client.try_create_stream(s, r, &1000, &0, &0, &0, &1000),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    // sender == recipient
+    assert_eq!(
+        client.try_create_stream(s, s, &1000, &1, &0, &0, &1000),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    // start >= end
+    assert_eq!(
+        client.try_create_stream(s, r, &1000, &1, &1000, &1000, &1000),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    // cliff outside [start, end]
+    assert_eq!(
+        client.try_create_stream(s, r, &1000, &1, &0, &1001, &1000),
+        Err(Ok(ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn test_error_try_create_stream_unfunded_deposit() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &999_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(result, Err(Ok(ContractError::UnfundedDeposit)));
+}
+
+#[test]
+fn test_error_try_create_stream_arithmetic_overflow() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &i128::MAX,
+        &i128::MAX,
+        &0u64,
+        &0u64,
+        &3u64,
+    );
+    assert_eq!(result, Err(Ok(ContractError::ArithmeticOverflow)));
+}
+
+#[test]
+fn test_error_try_withdraw_stream_paused() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(
+        ctx.client().try_withdraw(&stream_id),
+        Err(Ok(ContractError::StreamPaused))
+    );
+}
+
+#[test]
+fn test_error_try_withdraw_stream_completed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    assert_eq!(
+        ctx.client().try_withdraw(&stream_id),
+        Err(Ok(ContractError::StreamCompleted))
+    );
+}
+
+#[test]
+fn test_error_try_pause_stream_by_status() {
+    let ctx = TestContext::setup();
+    let paused = ctx.create_default_stream();
+    let cancelled = ctx.create_default_stream();
+    let completed = ctx.create_default_stream();
+
+    ctx.client().pause_stream(&paused);
+    ctx.client().cancel_stream(&cancelled);
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&completed);
+
+    assert_eq!(ctx.client().try_pause_stream(&paused), Ok(Ok(())));
+    assert_eq!(
+        ctx.client().try_pause_stream(&cancelled),
+        Err(Ok(ContractError::StreamCancelled))
+    );
+    assert_eq!(
+        ctx.client().try_pause_stream(&completed),
+        Err(Ok(ContractError::StreamCompleted))
+    );
+}
+
+#[test]
+fn test_error_try_resume_stream_by_status() {
+    let ctx = TestContext::setup();
+    let active = ctx.create_default_stream();
+    let cancelled = ctx.create_default_stream();
+
+    ctx.client().cancel_stream(&cancelled);
+
+    assert_eq!(ctx.client().try_resume_stream(&active), Ok(Ok(())));
+    assert_eq!(
+        ctx.client().try_resume_stream(&cancelled),
+        Err(Ok(ContractError::StreamCancelled))
+    );
+}
+
+#[test]
+fn test_error_try_cancel_stream_terminal_is_invalid_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(
+        ctx.client().try_cancel_stream(&stream_id),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_error_try_batch_withdraw_unauthorized_recipient() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let second = ctx.client().create_stream(
+        &ctx.sender,
+        &Address::generate(&ctx.env),
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    assert_eq!(
+        ctx.client()
+            .try_batch_withdraw(&soroban_sdk::vec![&ctx.env, first, second]),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_try_variants_return_ok_on_success() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(ctx.client().try_pause_stream(&stream_id), Ok(Ok(())));
+    assert_eq!(ctx.client().try_resume_stream(&stream_id), Ok(Ok(())));
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().try_withdraw(&stream_id), Ok(Ok(100)));
+    assert_eq!(
+        ctx.client().try_cancel_stream(&stream_id),
+        Ok(Ok((900, 100)))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — two-step admin transfer
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_propose_and_accept_admin_rotates_admin() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+
+    ctx.client().propose_admin(&new_admin);
+    let config = ctx.client().get_config();
+    assert_eq!(config.admin, ctx.admin);
+    assert_eq!(config.pending_admin, Some(new_admin.clone()));
+
+    ctx.client().accept_admin();
+    let config = ctx.client().get_config();
+    assert_eq!(config.admin, new_admin);
+    assert_eq!(config.pending_admin, None);
+}
+
+#[test]
+fn test_old_admin_keeps_privileges_until_accept() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let new_admin = Address::generate(&ctx.env);
+
+    ctx.client().propose_admin(&new_admin);
+    ctx.client().pause_stream_as_admin(&stream_id);
+
+    let auths = ctx.env.auths();
+    assert_eq!(auths[0].0, ctx.admin);
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_by_non_proposed_address_fails() {
+    let ctx = TestContext::setup_strict();
+    let new_admin = Address::generate(&ctx.env);
+    let impostor = Address::generate(&ctx.env);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.admin,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "propose_admin",
+            args: (&new_admin,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().propose_admin(&new_admin);
+
+    ctx.env.mock_auths(&[MockAuth {
+        address: &impostor,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "accept_admin",
+            args: ().into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().accept_admin();
+}
+
+#[test]
+fn test_accept_admin_without_proposal_returns_error() {
+    let ctx = TestContext::setup();
+
+    assert_eq!(
+        ctx.client().try_accept_admin(),
+        Err(Ok(crate::ContractError::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn test_cancel_admin_proposal_clears_pending() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+
+    ctx.client().propose_admin(&new_admin);
+    ctx.client().cancel_admin_proposal();
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.admin, ctx.admin);
+    assert_eq!(config.pending_admin, None);
+    assert_eq!(
+        ctx.client().try_accept_admin(),
+        Err(Ok(crate::ContractError::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn test_cancel_admin_proposal_without_proposal_returns_error() {
+    let ctx = TestContext::setup();
+
+    assert_eq!(
+        ctx.client().try_cancel_admin_proposal(),
+        Err(Ok(crate::ContractError::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn test_propose_admin_replaces_earlier_proposal() {
+    let ctx = TestContext::setup();
+    let first = Address::generate(&ctx.env);
+    let second = Address::generate(&ctx.env);
+
+    ctx.client().propose_admin(&first);
+    ctx.client().propose_admin(&second);
+    assert_eq!(
+        ctx.client().get_config().pending_admin,
+        Some(second.clone())
+    );
+
+    ctx.client().accept_admin();
+    assert_eq!(ctx.client().get_config().admin, second);
+}
+
+#[test]
+fn test_set_admin_clears_pending_proposal() {
+    let ctx = TestContext::setup();
+    let proposed = Address::generate(&ctx.env);
+    let direct = Address::generate(&ctx.env);
+
+    ctx.client().propose_admin(&proposed);
+    ctx.client().set_admin(&direct);
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.admin, direct);
+    assert_eq!(config.pending_admin, None);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — topup_stream
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_topup_before_end_extends_deposit_and_end_time() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().topup_stream(&stream_id, &500_i128);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1500);
+    assert_eq!(state.end_time, 1500);
+    assert_eq!(state.status, StreamStatus::Active);
+
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_500);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1_500);
+}
+
+#[test]
+fn test_topup_before_end_accrual_follows_new_schedule() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().topup_stream(&stream_id, &500_i128);
+
+    ctx.env.ledger().set_timestamp(1200);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1200);
+
+    ctx.env.ledger().set_timestamp(2000);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1500);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_topup_after_original_end_extends_from_now() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1200);
+    ctx.client().topup_stream(&stream_id, &300_i128);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1300);
+    assert_eq!(state.end_time, 1500);
+
+    // Accrual is min(deposit, rate × elapsed) against the updated values.
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1200);
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1300);
+}
+
+#[test]
+fn test_topup_after_original_end_pays_elapsed_part_immediately() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(900);
+    assert_eq!(ctx.client().withdraw(&stream_id), 900);
+
+    // 200s have passed since end_time, so 200 of the 300 top-up accrue at once.
+    ctx.env.ledger().set_timestamp(1200);
+    ctx.client().topup_stream(&stream_id, &300_i128);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100 + 200);
+
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_topup_remainder_counts_towards_deposit() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &2_i128,
+        &0u64,
+        &0u64,
+        &500u64,
+    );
+
+    ctx.client().topup_stream(&stream_id, &101_i128);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1101);
+    assert_eq!(state.end_time, 550);
+}
+
+#[test]
+fn test_topup_paused_stream_allowed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.client().topup_stream(&stream_id, &100_i128);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.end_time, 1100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_topup_cancelled_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+
+    ctx.client().topup_stream(&stream_id, &100_i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_topup_completed_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.client().topup_stream(&stream_id, &100_i128);
+}
+
+#[test]
+fn test_topup_non_positive_amount_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(
+        ctx.client().try_topup_stream(&stream_id, &0_i128),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_topup_stream(&stream_id, &-5_i128),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn test_topup_requires_sender_auth() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.client().topup_stream(&stream_id, &100_i128);
+
+    let auths = ctx.env.auths();
+    assert_eq!(auths[0].0, ctx.sender);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — lifecycle event schema
+// ---------------------------------------------------------------------------
+
+/// Collect the `("STREAM", name, stream_id)` events published by the contract.
+fn stream_events(ctx: &TestContext) -> std::vec::Vec<(soroban_sdk::Symbol, u64, soroban_sdk::Val)> {
+    let mut out = std::vec::Vec::new();
+    for (contract, topics, data) in ctx.env.events().all().iter() {
+        if contract != ctx.contract_id || topics.len() != 3 {
+            continue;
+        }
+        let ns = soroban_sdk::Symbol::from_val(&ctx.env, &topics.get_unchecked(0));
+        if ns != soroban_sdk::symbol_short!("STREAM") {
+            continue;
+        }
+        out.push((
+            soroban_sdk::Symbol::from_val(&ctx.env, &topics.get_unchecked(1)),
+            u64::from_val(&ctx.env, &topics.get_unchecked(2)),
+            data,
+        ));
+    }
+    out
+}
+
+#[test]
+fn test_full_lifecycle_emits_stream_events() {
+    use soroban_sdk::symbol_short;
+
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().resume_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    let events = stream_events(&ctx);
+    let names: std::vec::Vec<_> = events.iter().map(|e| e.0.clone()).collect();
+    assert_eq!(
+        names,
+        std::vec![
+            symbol_short!("created"),
+            symbol_short!("paused"),
+            symbol_short!("resumed"),
+            symbol_short!("withdrawn"),
+            symbol_short!("withdrawn"),
+            symbol_short!("completed"),
+        ]
+    );
+    assert!(events.iter().all(|e| e.1 == stream_id));
+
+    let created = crate::StreamCreated::from_val(&ctx.env, &events[0].2);
+    assert_eq!(created.stream_id, stream_id);
+    assert_eq!(created.deposit_amount, 1000);
+    assert_eq!(created.end_time, 1000);
+    assert_eq!(
+        crate::StreamPaused::from_val(&ctx.env, &events[1].2),
+        crate::StreamPaused {
+            stream_id,
+            paused_at: 200,
+            admin: false,
+        }
+    );
+    assert_eq!(
+        crate::StreamResumed::from_val(&ctx.env, &events[2].2),
+        crate::StreamResumed {
+            stream_id,
+            resumed_at: 300,
+            admin: false,
+        }
+    );
+    assert_eq!(
+        crate::StreamWithdrawn::from_val(&ctx.env, &events[3].2),
+        crate::StreamWithdrawn {
+            stream_id,
+            amount: 400,
+            fee: 0,
+            withdrawn_total: 400
+        }
+    );
+    // The final withdrawal emits `withdrawn` followed by `completed`.
+    assert_eq!(
+        crate::StreamWithdrawn::from_val(&ctx.env, &events[4].2),
+        crate::StreamWithdrawn {
+            stream_id,
+            amount: 600,
+            fee: 0,
+            withdrawn_total: 1000
+        }
+    );
+    assert_eq!(
+        crate::StreamCompleted::from_val(&ctx.env, &events[5].2),
+        crate::StreamCompleted { stream_id }
+    );
+}
+
+#[test]
+fn test_cancel_event_reports_refund_and_accrued() {
+    use soroban_sdk::symbol_short;
+
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(600);
+    ctx.client().cancel_stream_as_admin(&stream_id);
+
+    let events = stream_events(&ctx);
+    let last = events.last().unwrap();
+    assert_eq!(last.0, symbol_short!("cancelled"));
+    assert_eq!(last.1, stream_id);
+    assert_eq!(
+        crate::StreamCancelled::from_val(&ctx.env, &last.2),
+        crate::StreamCancelled {
+            stream_id,
+            sender_refund: 400,
+            recipient_accrued: 600,
+        }
+    );
+}
+
+#[test]
+fn test_batch_withdraw_emits_completed_per_drained_stream() {
+    use soroban_sdk::symbol_short;
+
+    let ctx = TestContext::setup();
+    let ids = create_default_streams(&ctx, 2);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().batch_withdraw(&ids);
+
+    let names: std::vec::Vec<_> = stream_events(&ctx).into_iter().map(|e| e.0).collect();
+    assert_eq!(
+        names,
+        std::vec![
+            symbol_short!("created"),
+            symbol_short!("created"),
+            symbol_short!("withdrawn"),
+            symbol_short!("completed"),
+            symbol_short!("withdrawn"),
+            symbol_short!("completed"),
+        ]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_refundable_amount
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_refundable_before_start_equals_deposit() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &100u64,
+        &1100u64,
+    );
+
+    ctx.env.ledger().set_timestamp(50);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 1000);
+}
+
+#[test]
+fn test_refundable_at_half_equals_half_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 500);
+}
+
+#[test]
+fn test_refundable_before_cliff_equals_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+
+    ctx.env.ledger().set_timestamp(499);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 1000);
+}
+
+#[test]
+fn test_refundable_after_end_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_refundable_cancelled_stream_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_refundable_completed_stream_is_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_refundable_paused_stream_keeps_accruing() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 400);
+}
+
+#[test]
+fn test_refundable_matches_actual_cancel_refund() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(370);
+    let quoted = ctx.client().get_refundable_amount(&stream_id);
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.client().cancel_stream(&stream_id);
+
+    let refunded = ctx.token().balance(&ctx.sender) - sender_before;
+    assert_eq!(quoted, 630);
+    assert_eq!(refunded, quoted);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_refundable_stream_not_found() {
+    let ctx = TestContext::setup();
+    ctx.client().get_refundable_amount(&99);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — extend_stream_end_time
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_extend_end_time_charges_rate_times_extension() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(300);
+    let charged = ctx
+        .client()
+        .extend_stream_end_time(&stream_id, &1500u64, &500_i128);
+
+    assert_eq!(charged, 500);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.end_time, 1500);
+    assert_eq!(state.deposit_amount, 1500);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_500);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1_500);
+
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1500);
+}
+
+#[test]
+fn test_extend_end_time_overpayment_stays_with_sender() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    let charged = ctx
+        .client()
+        .extend_stream_end_time(&stream_id, &1200u64, &1_000_i128);
+
+    assert_eq!(charged, 200);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).deposit_amount,
+        1200
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_800);
+}
+
+#[test]
+fn test_extend_end_time_aligns_with_sibling_cliff() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    // A sibling vesting stream whose cliff falls after the first stream ends.
+    let sibling = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2000_i128,
+        &1_i128,
+        &0u64,
+        &1_250u64,
+        &2000u64,
+    );
+    let sibling_cliff = ctx.client().get_stream_state(&sibling).cliff_time;
+
+    ctx.env.ledger().set_timestamp(800);
+    ctx.client()
+        .extend_stream_end_time(&stream_id, &sibling_cliff, &250_i128);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.end_time, sibling_cliff);
+    assert_eq!(state.deposit_amount, 1250);
+
+    // The first stream finishes exactly when the sibling's cliff unlocks.
+    ctx.env.ledger().set_timestamp(sibling_cliff);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1250);
+    assert_eq!(ctx.client().calculate_accrued(&sibling), 1250);
+}
+
+#[test]
+fn test_extend_end_time_not_after_current_end_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(
+        ctx.client()
+            .try_extend_stream_end_time(&stream_id, &1000u64, &100_i128),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_extend_stream_end_time(&stream_id, &900u64, &100_i128),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn test_extend_end_time_in_the_past_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    // The stream ended at 1000; extending to 1500 when the ledger is already at
+    // 2000 would extend into the past.
+    ctx.env.ledger().set_timestamp(2000);
+    assert_eq!(
+        ctx.client()
+            .try_extend_stream_end_time(&stream_id, &1500u64, &500_i128),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn test_extend_end_time_insufficient_deposit_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(
+        ctx.client()
+            .try_extend_stream_end_time(&stream_id, &1500u64, &499_i128),
+        Err(Ok(crate::ContractError::UnfundedDeposit))
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_extend_end_time_cancelled_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+
+    ctx.client()
+        .extend_stream_end_time(&stream_id, &1500u64, &500_i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_extend_end_time_completed_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.client()
+        .extend_stream_end_time(&stream_id, &1500u64, &500_i128);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — cancel_stream_as_recipient
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_recipient_cancel_at_30_percent_splits_funds() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(300);
+    let paid = ctx.client().cancel_stream_as_recipient(&stream_id);
+
+    assert_eq!(paid, 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_700);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Cancelled);
+    assert_eq!(state.withdrawn_amount, 300);
+    assert_eq!(state.cancelled_at, Some(300));
+}
+
+#[test]
+fn test_recipient_cancel_at_zero_percent_full_refund() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    let paid = ctx.client().cancel_stream_as_recipient(&stream_id);
+
+    assert_eq!(paid, 0);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_recipient_cancel_after_partial_withdrawal_pays_remainder() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(450);
+    let paid = ctx.client().cancel_stream_as_recipient(&stream_id);
+
+    assert_eq!(paid, 250);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 450);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_550);
+}
+
+#[test]
+#[should_panic]
+fn test_recipient_cancel_by_third_party_panics() {
+    let ctx = TestContext::setup_strict();
+    let stream_id = {
+        ctx.env.mock_all_auths();
+        ctx.create_default_stream()
+    };
+    let stranger = Address::generate(&ctx.env);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &stranger,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "cancel_stream_as_recipient",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().cancel_stream_as_recipient(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_recipient_cancel_disabled_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.client().set_recipient_cancel_allowed(&false);
+    assert!(!ctx.client().get_config().recipient_cancel_allowed);
+
+    ctx.client().cancel_stream_as_recipient(&stream_id);
+}
+
+#[test]
+fn test_recipient_cancel_allowed_by_default_and_can_be_reenabled() {
+    let ctx = TestContext::setup();
+    assert!(ctx.client().get_config().recipient_cancel_allowed);
+
+    ctx.client().set_recipient_cancel_allowed(&false);
+    ctx.client().set_recipient_cancel_allowed(&true);
+
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream_as_recipient(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_recipient_cancel_terminal_stream_is_invalid_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(
+        ctx.client().try_cancel_stream_as_recipient(&stream_id),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — admin_cancel_stream
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_cancel_active_stream_splits_balances() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    let (sender_refund, recipient_accrued) = ctx.client().admin_cancel_stream(&stream_id, &7u32);
+    assert_eq!((sender_refund, recipient_accrued), (600, 400));
+
+    // Unaccrued portion refunded immediately; accrued portion stays in the contract.
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_600);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 400);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Cancelled);
+    assert_eq!(state.cancelled_at, Some(400));
+
+    ctx.env.ledger().set_timestamp(900);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_admin_cancel_paused_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().admin_cancel_stream(&stream_id, &1u32),
+        (750, 250)
+    );
+
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_750);
+}
+
+#[test]
+fn test_admin_cancel_emits_reason_code() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().admin_cancel_stream(&stream_id, &42u32);
+
+    let events = ctx.env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &topics.get_unchecked(1)),
+        soroban_sdk::Symbol::new(&ctx.env, "admin_cancelled")
+    );
+    assert_eq!(
+        crate::StreamAdminCancelled::from_val(&ctx.env, &data),
+        crate::StreamAdminCancelled {
+            stream_id,
+            reason_code: 42,
+            sender_refund: 900,
+            recipient_accrued: 100,
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_admin_cancel_by_non_admin_panics() {
+    let ctx = TestContext::setup_strict();
+    let stream_id = {
+        ctx.env.mock_all_auths();
+        ctx.create_default_stream()
+    };
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "admin_cancel_stream",
+            args: (stream_id, 1u32).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().admin_cancel_stream(&stream_id, &1u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_admin_cancel_twice_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.client().admin_cancel_stream(&stream_id, &1u32);
+    ctx.client().admin_cancel_stream(&stream_id, &1u32);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — pending streams (accept / reject / expiry)
+// ---------------------------------------------------------------------------
+
+/// Pending 1000-unit stream shaped as 0..1000 with a cliff at 200, offer open until t=500.
+fn create_pending_default_stream(ctx: &TestContext) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_pending_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &200u64,
+        &1000u64,
+        &500u64,
+    )
+}
+
+#[test]
+fn test_pending_stream_does_not_accrue() {
+    let ctx = TestContext::setup();
+    let stream_id = create_pending_default_stream(&ctx);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Pending);
+    assert_eq!(state.pending_expiry, Some(500));
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 0);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+    assert_eq!(ctx.client().get_refundable_amount(&stream_id), 1000);
+}
+
+#[test]
+fn test_accept_stream_starts_schedule_now() {
+    let ctx = TestContext::setup();
+    let stream_id = create_pending_default_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().accept_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.start_time, 300);
+    assert_eq!(state.cliff_time, 500);
+    assert_eq!(state.end_time, 1300);
+    assert_eq!(state.pending_expiry, None);
+
+    ctx.env.ledger().set_timestamp(499);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 0);
+    ctx.env.ledger().set_timestamp(800);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+    ctx.env.ledger().set_timestamp(1300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_accept_stream_by_non_recipient_panics() {
+    let ctx = TestContext::setup_strict();
+    let stream_id = {
+        ctx.env.mock_all_auths();
+        create_pending_default_stream(&ctx)
+    };
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "accept_stream",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().accept_stream(&stream_id);
+}
+
+#[test]
+fn test_accept_stream_after_expiry_fails() {
+    let ctx = TestContext::setup();
+    let stream_id = create_pending_default_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(501);
+    assert_eq!(
+        ctx.client().try_accept_stream(&stream_id),
+        Err(Ok(crate::ContractError::PendingExpired))
+    );
+}
+
+#[test]
+fn test_accept_non_pending_stream_is_invalid_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(
+        ctx.client().try_accept_stream(&stream_id),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+    assert_eq!(
+        ctx.client().try_reject_stream(&stream_id),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_reject_stream_refunds_full_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = create_pending_default_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().reject_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Cancelled);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+
+    // Nothing ever accrued, so there is nothing for the recipient to withdraw.
+    ctx.env.ledger().set_timestamp(2000);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 0);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+}
+
+#[test]
+fn test_claim_expired_pending_refunds_sender() {
+    let ctx = TestContext::setup();
+    let stream_id = create_pending_default_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(
+        ctx.client().try_claim_expired_pending(&stream_id),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+
+    ctx.env.ledger().set_timestamp(501);
+    ctx.client().claim_expired_pending(&stream_id);
+
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_pending_stream_blocks_withdraw_and_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = create_pending_default_stream(&ctx);
+
+    assert_eq!(
+        ctx.client().try_withdraw(&stream_id),
+        Err(Ok(crate::ContractError::StreamPending))
+    );
+    assert_eq!(
+        ctx.client().try_pause_stream(&stream_id),
+        Err(Ok(crate::ContractError::StreamPending))
+    );
+    assert_eq!(
+        ctx.client().try_topup_stream(&stream_id, &100_i128),
+        Err(Ok(crate::ContractError::StreamPending))
+    );
+}
+
+#[test]
+fn test_create_pending_stream_rejects_past_expiry() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(100);
+
+    assert_eq!(
+        ctx.client().try_create_pending_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+            &100u64,
+        ),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — batch_create_streams
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_batch_create_ten_streams_to_different_recipients() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+
+    let mut params = Vec::new(&ctx.env);
+    let mut recipients = std::vec::Vec::new();
+    let mut total: i128 = 0;
+    for i in 0..10u32 {
+        let recipient = Address::generate(&ctx.env);
+        let deposit = 100 * (i as i128 + 1);
+        total += deposit;
+        params.push_back(CreateStreamParams {
+            recipient: recipient.clone(),
+            deposit_amount: deposit,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: deposit as u64,
+        });
+        recipients.push(recipient);
+    }
+
+    let ids = ctx.client().batch_create_streams(&ctx.sender, &params);
+
+    assert_eq!(ids.len(), 10);
+    for (i, stream_id) in ids.iter().enumerate() {
+        assert_eq!(stream_id, i as u64);
+        let state = ctx.client().get_stream_state(&stream_id);
+        assert_eq!(state.recipient, recipients[i]);
+        assert_eq!(state.deposit_amount, 100 * (i as i128 + 1));
+    }
+    assert_eq!(total, 5_500);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000 - total);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), total);
+}
+
+#[test]
+fn test_batch_create_invalid_entry_creates_nothing() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+
+    let params = vec![
+        &ctx.env,
+        CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 1000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        },
+        CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 10,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        },
+    ];
+
+    assert_eq!(
+        ctx.client().try_batch_create_streams(&ctx.sender, &params),
+        Err(Ok(crate::ContractError::UnfundedDeposit))
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.client().get_streams_by_sender(&ctx.sender).len(), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — protocol fee
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_zero_fee_is_default_and_valid() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_config().fee_bps, 0);
+
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).deposit_amount,
+        1000
+    );
+    assert_eq!(ctx.client().get_accumulated_fees(), 0);
+
+    ctx.client().set_fee_bps(&0);
+    assert_eq!(ctx.client().get_config().fee_bps, 0);
+}
+
+#[test]
+fn test_fee_enabled_stream_has_net_deposit() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_bps(&100); // 1%
+
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1010_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(ctx.client().get_accumulated_fees(), 10);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_990);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1010);
+
+    // The recipient can only ever withdraw the net deposit.
+    ctx.env.ledger().set_timestamp(2000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 10);
+}
+
+#[test]
+fn test_fee_must_leave_deposit_covering_stream() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_bps(&100);
+
+    // 1000 gross → 990 net, which no longer covers 1 token/s for 1000s.
+    assert_eq!(
+        ctx.client().try_create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        ),
+        Err(Ok(crate::ContractError::UnfundedDeposit))
+    );
+}
+
+#[test]
+fn test_fee_accumulates_across_streams() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_bps(&250); // 2.5%
+    ctx.env.ledger().set_timestamp(0);
+
+    for _ in 0..3 {
+        ctx.client().create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &2000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        );
+    }
+    assert_eq!(ctx.client().get_accumulated_fees(), 150);
+
+    let params = vec![
+        &ctx.env,
+        CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 2000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        },
+    ];
+    let ids = ctx.client().create_streams(&ctx.sender, &params);
+    assert_eq!(
+        ctx.client()
+            .get_stream_state(&ids.get_unchecked(0))
+            .deposit_amount,
+        1950
+    );
+    assert_eq!(ctx.client().get_accumulated_fees(), 200);
+}
+
+#[test]
+fn test_withdraw_protocol_fees_clears_balance() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_bps(&1000); // 10%
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    let treasury = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().withdraw_protocol_fees(&treasury), 200);
+    assert_eq!(ctx.token().balance(&treasury), 200);
+    assert_eq!(ctx.client().get_accumulated_fees(), 0);
+
+    // A second withdrawal has nothing to send.
+    assert_eq!(ctx.client().withdraw_protocol_fees(&treasury), 0);
+    assert_eq!(ctx.token().balance(&treasury), 200);
+}
+
+#[test]
+fn test_fee_bps_above_config_cap_rejected() {
+    let ctx = TestContext::setup();
+
+    let e = crate::ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client()
+            .try_set_fee_bps(&(crate::MAX_CONFIG_FEE_BPS + 1)),
+        Err(Ok(e))
+    );
+    assert_eq!(ctx.client().try_set_fee_bps(&10_000), Err(Ok(e)));
+    assert_eq!(ctx.client().get_config().fee_bps, 0);
+
+    ctx.client().set_fee_bps(&crate::MAX_CONFIG_FEE_BPS);
+    assert_eq!(ctx.client().get_config().fee_bps, 1_000);
+}
+
+#[test]
+#[should_panic]
+fn test_withdraw_protocol_fees_requires_admin() {
+    let ctx = TestContext::setup_strict();
+    let treasury = Address::generate(&ctx.env);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "withdraw_protocol_fees",
+            args: (&treasury,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().withdraw_protocol_fees(&treasury);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — update_config
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_update_config_defaults() {
+    let ctx = TestContext::setup();
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.min_deposit, 0);
+    assert_eq!(config.max_streams_per_sender, 0);
+    assert_eq!(config.min_duration, 0);
+}
+
+#[test]
+fn test_update_config_fee_only() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&Some(250), &None, &None, &None, &None, &None);
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 250);
+    assert_eq!(config.min_deposit, 0);
+    assert_eq!(config.max_streams_per_sender, 0);
+}
+
+#[test]
+fn test_update_config_min_deposit_only() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &Some(500_i128), &None, &None, &None, &None);
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.min_deposit, 500);
+    assert_eq!(config.max_streams_per_sender, 0);
+}
+
+#[test]
+fn test_update_config_max_streams_only() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(5), &None, &None, &None);
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.min_deposit, 0);
+    assert_eq!(config.max_streams_per_sender, 5);
+}
+
+#[test]
+fn test_update_config_all_fields_and_preserves_identity() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&Some(1000), &Some(100_i128), &Some(20), &None, &None, &None);
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 1000);
+    assert_eq!(config.min_deposit, 100);
+    assert_eq!(config.max_streams_per_sender, 20);
+    assert_eq!(config.admin, ctx.admin);
+    assert_eq!(config.token, ctx.token_id);
+
+    // A later partial update leaves the other fields alone.
+    ctx.client()
+        .update_config(&Some(0), &None, &None, &None, &None, &None);
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.min_deposit, 100);
+    assert_eq!(config.max_streams_per_sender, 20);
+}
+
+#[test]
+fn test_update_config_all_none_is_noop() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&Some(50), &Some(10_i128), &Some(2), &None, &None, &None);
+    ctx.client()
+        .update_config(&None, &None, &None, &None, &None, &None);
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 50);
+    assert_eq!(config.min_deposit, 10);
+    assert_eq!(config.max_streams_per_sender, 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_update_config_fee_above_cap_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&Some(1001), &None, &None, &None, &None, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_update_config_negative_min_deposit_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &Some(-1_i128), &None, &None, &None, &None);
+}
+
+#[test]
+fn test_update_config_rejects_whole_update_on_invalid_field() {
+    let ctx = TestContext::setup();
+
+    assert_eq!(
+        ctx.client()
+            .try_update_config(&Some(100), &Some(-5_i128), &Some(3), &None, &None, &None),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+    let config = ctx.client().get_config();
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.min_deposit, 0);
+    assert_eq!(config.max_streams_per_sender, 0);
+}
+
+#[test]
+fn test_update_config_emits_event() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&Some(10), &None, &None, &None, &None, &None);
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(last.0, ctx.contract_id);
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &last.1.get(1).unwrap()),
+        soroban_sdk::symbol_short!("updated")
+    );
+    let config = crate::Config::from_val(&ctx.env, &last.2);
+    assert_eq!(config.fee_bps, 10);
+}
+
+#[test]
+#[should_panic]
+fn test_update_config_requires_admin() {
+    let ctx = TestContext::setup_strict();
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    let args = (
+        Some(100_u32),
+        None::<i128>,
+        None::<u32>,
+        None::<u64>,
+        None::<i128>,
+    );
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "update_config",
+            args: args.into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client()
+        .update_config(&Some(100), &None, &None, &None, &None, &None);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — PauseMode::AccrualFreeze
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_stream_defaults_to_withdrawal_block() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.pause_mode, crate::PauseMode::WithdrawalBlock);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.total_paused_duration, 0);
+}
+
+#[test]
+fn test_create_stream_with_default_options_matches_create_stream() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &crate::StreamOptions::default(),
+    );
+
+    // Pause at 300, resume at 700: accrual keeps running in the background.
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(700);
+    ctx.client().resume_stream(&stream_id);
+
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 700);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.total_paused_duration, 400);
+}
+
+#[test]
+fn test_freeze_pause_stops_accrual() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        pause_mode: crate::PauseMode::AccrualFreeze,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).paused_at,
+        Some(300)
+    );
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 300);
+
+    ctx.env.ledger().set_timestamp(700);
+    ctx.client().resume_stream(&stream_id);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 300);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.total_paused_duration, 400);
+    assert_eq!(state.status, StreamStatus::Active);
+}
+
+#[test]
+fn test_freeze_pause_extends_stream_by_paused_duration() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        pause_mode: crate::PauseMode::AccrualFreeze,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(700);
+    ctx.client().resume_stream(&stream_id);
+
+    // The nominal end time passes with 400 seconds still to stream.
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 600);
+
+    ctx.env.ledger().set_timestamp(1399);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 999);
+
+    ctx.env.ledger().set_timestamp(1400);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_freeze_pause_accumulates_across_cycles() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        pause_mode: crate::PauseMode::AccrualFreeze,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().resume_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(450);
+    ctx.client().resume_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.total_paused_duration, 150);
+
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 450);
+}
+
+#[test]
+fn test_freeze_pause_then_cancel_refunds_frozen_balance() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        pause_mode: crate::PauseMode::AccrualFreeze,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(800);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_700);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+}
+
+#[test]
+fn test_freeze_pause_before_cliff_delays_cliff() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &500u64,
+        &1000u64,
+        &crate::StreamOptions {
+            pause_mode: crate::PauseMode::AccrualFreeze,
+            ..Default::default()
+        },
+    );
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().resume_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(599);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 0);
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 500);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — operator delegation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_operator_withdraws_to_recipient() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+
+    ctx.client().approve_operator(&stream_id, &operator);
+    assert_eq!(
+        ctx.client().get_operator(&stream_id),
+        Some(operator.clone())
+    );
+
+    ctx.env.ledger().set_timestamp(400);
+    let amount = ctx.client().withdraw_as_operator(&stream_id, &operator);
+    assert_eq!(amount, 400);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+    assert_eq!(ctx.token().balance(&operator), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        400
+    );
+}
+
+#[test]
+fn test_operator_defaults_to_none() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_operator(&stream_id), None);
+}
+
+#[test]
+fn test_revoked_operator_is_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+
+    ctx.client().approve_operator(&stream_id, &operator);
+    ctx.client().revoke_operator(&stream_id);
+    assert_eq!(ctx.client().get_operator(&stream_id), None);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(
+        ctx.client().try_withdraw_as_operator(&stream_id, &operator),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_operator_rejected_on_other_stream() {
+    let ctx = TestContext::setup();
+    let approved = ctx.create_default_stream();
+    let other = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+
+    ctx.client().approve_operator(&approved, &operator);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(
+        ctx.client().try_withdraw_as_operator(&other, &operator),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_approve_operator_replaces_previous() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let first = Address::generate(&ctx.env);
+    let second = Address::generate(&ctx.env);
+
+    ctx.client().approve_operator(&stream_id, &first);
+    ctx.client().approve_operator(&stream_id, &second);
+    assert_eq!(ctx.client().get_operator(&stream_id), Some(second));
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(
+        ctx.client().try_withdraw_as_operator(&stream_id, &first),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_approve_recipient_as_operator_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client()
+            .try_approve_operator(&stream_id, &ctx.recipient),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn test_operator_cleared_on_completion() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(
+        ctx.client().withdraw_as_operator(&stream_id, &operator),
+        1000
+    );
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.approved_operator, None);
+}
+
+#[test]
+fn test_operator_cleared_on_cancel() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.client().get_operator(&stream_id), None);
+    assert_eq!(
+        ctx.client().try_approve_operator(&stream_id, &operator),
+        Err(Ok(crate::ContractError::StreamCancelled))
+    );
+}
+
+#[test]
+fn test_operator_cannot_withdraw_paused_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().try_withdraw_as_operator(&stream_id, &operator),
+        Err(Ok(crate::ContractError::StreamPaused))
+    );
+}
+
+#[test]
+fn test_revoke_without_operator_is_noop() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let before = ctx.env.events().all().len();
+    ctx.client().revoke_operator(&stream_id);
+    assert_eq!(ctx.env.events().all().len(), before);
+}
+
+#[test]
+fn test_operator_events() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+
+    ctx.client().approve_operator(&stream_id, &operator);
+    ctx.client().revoke_operator(&stream_id);
+
+    let events = stream_events(&ctx);
+    let names: std::vec::Vec<soroban_sdk::Symbol> =
+        events.iter().map(|(name, _, _)| name.clone()).collect();
+    assert_eq!(
+        names,
+        std::vec![
+            soroban_sdk::symbol_short!("created"),
+            soroban_sdk::Symbol::new(&ctx.env, "operator_approved"),
+            soroban_sdk::Symbol::new(&ctx.env, "operator_revoked"),
+        ]
+    );
+    let payload = crate::StreamOperatorUpdated::from_val(&ctx.env, &events[2].2);
+    assert_eq!(
+        payload,
+        crate::StreamOperatorUpdated {
+            stream_id,
+            operator,
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_approve_operator_requires_recipient_auth() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "approve_operator",
+            args: (stream_id, &operator).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().approve_operator(&stream_id, &operator);
+}
+
+#[test]
+#[should_panic]
+fn test_withdraw_as_operator_requires_operator_auth() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+    ctx.env.ledger().set_timestamp(400);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "withdraw_as_operator",
+            args: (stream_id, &operator).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().withdraw_as_operator(&stream_id, &operator);
+}
+
+/// Strict-auth stream with an approved operator, whose auth is the only one
+/// mocked for `fn_name` called with `args`.
+fn setup_operator_only_auth(
+    fn_name: &'static str,
+    args: impl Fn(&TestContext, u64) -> soroban_sdk::Vec<soroban_sdk::Val>,
+) -> (TestContext<'static>, u64) {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+    ctx.env.ledger().set_timestamp(400);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke};
+    let args = args(&ctx, stream_id);
+    ctx.env.mock_auths(&[MockAuth {
+        address: &operator,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name,
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+    (ctx, stream_id)
+}
+
+#[test]
+#[should_panic]
+fn test_operator_cannot_cancel_stream() {
+    use soroban_sdk::IntoVal;
+    let (ctx, stream_id) =
+        setup_operator_only_auth("cancel_stream", |ctx, id| (id,).into_val(&ctx.env));
+    ctx.client().cancel_stream(&stream_id);
+}
+
+#[test]
+#[should_panic]
+fn test_operator_cannot_pause_stream() {
+    use soroban_sdk::IntoVal;
+    let (ctx, stream_id) =
+        setup_operator_only_auth("pause_stream", |ctx, id| (id,).into_val(&ctx.env));
+    ctx.client().pause_stream(&stream_id);
+}
+
+#[test]
+#[should_panic]
+fn test_operator_cannot_update_recipient() {
+    use soroban_sdk::IntoVal;
+    let (ctx, stream_id) = setup_operator_only_auth("update_recipient", |ctx, id| {
+        (id, &ctx.admin).into_val(&ctx.env)
+    });
+    ctx.client().update_recipient(&stream_id, &ctx.admin);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — step-function vesting
+// ---------------------------------------------------------------------------
+
+/// 1000 tokens in 4 quarterly tranches over 0..1000.
+fn create_quarterly_step_stream(ctx: &TestContext) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_step_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &4u32,
+        &0u64,
+        &1000u64,
+    )
+}
+
+#[test]
+fn test_step_stream_stores_intervals() {
+    let ctx = TestContext::setup();
+    let stream_id = create_quarterly_step_stream(&ctx);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.intervals, 4);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(state.rate_per_second, 0);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+
+    let linear_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_stream_state(&linear_id).intervals, 0);
+}
+
+#[test]
+fn test_step_stream_accrual_at_quarters() {
+    let ctx = TestContext::setup();
+    let stream_id = create_quarterly_step_stream(&ctx);
+
+    for (t, expected) in [
+        (0u64, 0i128),
+        (249, 0),
+        (250, 250),
+        (499, 250),
+        (500, 500),
+        (750, 750),
+        (999, 750),
+        (1000, 1000),
+        (5000, 1000),
+    ] {
+        ctx.env.ledger().set_timestamp(t);
+        assert_eq!(
+            ctx.client().calculate_accrued(&stream_id),
+            expected,
+            "accrued at t={}",
+            t
+        );
+    }
+}
+
+#[test]
+fn test_step_stream_withdraw_per_tranche() {
+    let ctx = TestContext::setup();
+    let stream_id = create_quarterly_step_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 250);
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 750);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+}
+
+#[test]
+fn test_step_stream_remainder_in_last_tranche() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_step_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &3u32,
+        &0u64,
+        &900u64,
+    );
+
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 666);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 900);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 334);
+
+    ctx.env.ledger().set_timestamp(900);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 1000);
+}
+
+#[test]
+fn test_step_stream_next_unlock_views() {
+    let ctx = TestContext::setup();
+    let stream_id = create_quarterly_step_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(0);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 250);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 250);
+
+    ctx.env.ledger().set_timestamp(250);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 500);
+
+    ctx.env.ledger().set_timestamp(999);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 1000);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 250);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 0);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_next_unlock_views_for_linear_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+    let state = ctx.client().get_stream_state(&stream_id);
+
+    ctx.env.ledger().set_timestamp(state.start_time);
+    assert_eq!(
+        ctx.client().get_next_unlock_time(&stream_id),
+        state.cliff_time
+    );
+    assert_eq!(
+        ctx.client().get_next_unlock_amount(&stream_id),
+        (state.cliff_time - state.start_time) as i128 * state.rate_per_second
+    );
+
+    ctx.env.ledger().set_timestamp(state.cliff_time + 10);
+    assert_eq!(
+        ctx.client().get_next_unlock_time(&stream_id),
+        state.cliff_time + 11
+    );
+    assert_eq!(
+        ctx.client().get_next_unlock_amount(&stream_id),
+        state.rate_per_second
+    );
+
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 0);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_step_stream_cancel_refunds_locked_tranches() {
+    let ctx = TestContext::setup();
+    let stream_id = create_quarterly_step_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(600);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+}
+
+#[test]
+fn test_step_stream_rejects_topup_and_extension() {
+    let ctx = TestContext::setup();
+    let stream_id = create_quarterly_step_stream(&ctx);
+
+    assert_eq!(
+        ctx.client().try_topup_stream(&stream_id, &100_i128),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_extend_stream_end_time(&stream_id, &2000u64, &1000_i128),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_step_stream_invalid_params() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+
+    for (deposit, intervals, start, end) in [
+        (0_i128, 4u32, 0u64, 1000u64),
+        (1000, 0, 0, 1000),
+        (1000, 11, 0, 10),
+        (1000, 4, 1000, 1000),
+    ] {
+        assert_eq!(
+            ctx.client().try_create_step_stream(
+                &ctx.sender,
+                &ctx.recipient,
+                &deposit,
+                &intervals,
+                &start,
+                &end,
+            ),
+            Err(Ok(crate::ContractError::InvalidParams))
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — cliff unlock lump sum
+// ---------------------------------------------------------------------------
+
+/// Cliff at 500 releases 300, then 1 token/s until 1000: deposit 300 + 500.
+fn create_cliff_unlock_stream(ctx: &TestContext) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &800_i128,
+        &1_i128,
+        &0u64,
+        &500u64,
+        &1000u64,
+        &crate::StreamOptions {
+            cliff_unlock_amount: 300,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_cliff_unlock_amount_view() {
+    let ctx = TestContext::setup();
+    let stream_id = create_cliff_unlock_stream(&ctx);
+    assert_eq!(ctx.client().get_cliff_unlock_amount(&stream_id), 300);
+
+    let plain_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_cliff_unlock_amount(&plain_id), 0);
+}
+
+#[test]
+fn test_cliff_unlock_nothing_before_cliff() {
+    let ctx = TestContext::setup();
+    let stream_id = create_cliff_unlock_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(499);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 0);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 500);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 300);
+}
+
+#[test]
+fn test_cliff_unlock_withdraw_at_cliff_gets_lump_sum() {
+    let ctx = TestContext::setup();
+    let stream_id = create_cliff_unlock_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+}
+
+#[test]
+fn test_cliff_unlock_then_linear_accrual() {
+    let ctx = TestContext::setup();
+    let stream_id = create_cliff_unlock_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(700);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 800);
+    assert_eq!(state.status, StreamStatus::Completed);
+}
+
+#[test]
+fn test_cliff_unlock_cancel_refunds_linear_remainder() {
+    let ctx = TestContext::setup();
+    let stream_id = create_cliff_unlock_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(600);
+    ctx.client().cancel_stream(&stream_id);
+    // 300 lump sum + 100 streamed stay with the recipient.
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000 - 400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+}
+
+#[test]
+fn test_cliff_unlock_underfunded_rejected() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    assert_eq!(
+        ctx.client().try_create_stream_with_options(
+            &ctx.sender,
+            &ctx.recipient,
+            &799_i128,
+            &1_i128,
+            &0u64,
+            &500u64,
+            &1000u64,
+            &crate::StreamOptions {
+                cliff_unlock_amount: 300,
+                ..Default::default()
+            },
+        ),
+        Err(Ok(crate::ContractError::UnfundedDeposit))
+    );
+}
+
+#[test]
+fn test_cliff_unlock_invalid_amounts_rejected() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    for unlock in [-1_i128, 801] {
+        assert_eq!(
+            ctx.client().try_create_stream_with_options(
+                &ctx.sender,
+                &ctx.recipient,
+                &800_i128,
+                &1_i128,
+                &0u64,
+                &500u64,
+                &1000u64,
+                &crate::StreamOptions {
+                    cliff_unlock_amount: unlock,
+                    ..Default::default()
+                },
+            ),
+            Err(Ok(crate::ContractError::InvalidParams))
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — scheduled streams
+// ---------------------------------------------------------------------------
+
+/// 1000 tokens over 1000..2000 created at t=0.
+fn create_scheduled_stream(ctx: &TestContext) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &1000u64,
+        &1000u64,
+        &2000u64,
+    )
+}
+
+#[test]
+fn test_future_start_creates_scheduled_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Scheduled
+    );
+
+    // A stream starting now is Active straight away.
+    let active_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client().get_stream_state(&active_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_scheduled_stream_accrues_nothing_before_start() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(999);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Scheduled
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_withdraw_scheduled_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+fn test_withdraw_activates_scheduled_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(1300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+
+    let names: std::vec::Vec<soroban_sdk::Symbol> = stream_events(&ctx)
+        .iter()
+        .map(|(name, _, _)| name.clone())
+        .collect();
+    assert_eq!(
+        names,
+        std::vec![
+            soroban_sdk::symbol_short!("created"),
+            soroban_sdk::symbol_short!("activated"),
+            soroban_sdk::symbol_short!("withdrawn"),
+        ]
+    );
+}
+
+#[test]
+fn test_calculate_accrued_activates_scheduled_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(1200);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 200);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_activate_stream_explicitly() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(999);
+    assert_eq!(
+        ctx.client().try_activate_stream(&stream_id),
+        Err(Ok(crate::ContractError::StreamScheduled))
+    );
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().activate_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+
+    let (_, _, data) = stream_events(&ctx).last().unwrap().clone();
+    assert_eq!(
+        crate::StreamActivated::from_val(&ctx.env, &data),
+        crate::StreamActivated {
+            stream_id,
+            activated_at: 1000,
+        }
+    );
+
+    assert_eq!(
+        ctx.client().try_activate_stream(&stream_id),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn test_cancel_scheduled_stream_full_refund() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().cancel_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Cancelled);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+}
+
+#[test]
+fn test_pause_scheduled_stream_before_start_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = create_scheduled_stream(&ctx);
+
+    assert_eq!(
+        ctx.client().try_pause_stream(&stream_id),
+        Err(Ok(crate::ContractError::StreamScheduled))
+    );
+
+    ctx.env.ledger().set_timestamp(1100);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — protocol pause
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_protocol_pause_blocks_withdraw_and_resume_restores() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(!ctx.client().is_protocol_paused());
+
+    ctx.client().admin_pause_protocol();
+    assert!(ctx.client().is_protocol_paused());
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(
+        ctx.client().try_withdraw(&stream_id),
+        Err(Ok(crate::ContractError::ProtocolPaused))
+    );
+
+    // Views keep working while paused.
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 300);
+    assert!(ctx.client().get_config().protocol_paused);
+
+    ctx.client().admin_resume_protocol();
+    assert!(!ctx.client().is_protocol_paused());
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+}
+
+#[test]
+fn test_protocol_pause_blocks_mutating_functions() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().admin_pause_protocol();
+
+    let paused = crate::ContractError::ProtocolPaused;
+    assert_eq!(
+        ctx.client().try_create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        ),
+        Err(Ok(paused))
+    );
+    assert_eq!(ctx.client().try_cancel_stream(&stream_id), Err(Ok(paused)));
+    assert_eq!(ctx.client().try_pause_stream(&stream_id), Err(Ok(paused)));
+    assert_eq!(ctx.client().try_resume_stream(&stream_id), Err(Ok(paused)));
+    assert_eq!(
+        ctx.client().try_topup_stream(&stream_id, &10_i128),
+        Err(Ok(paused))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_batch_withdraw(&soroban_sdk::vec![&ctx.env, stream_id]),
+        Err(Ok(paused))
+    );
+}
+
+#[test]
+fn test_protocol_pause_leaves_admin_paths_available() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().admin_pause_protocol();
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream_as_admin(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_600);
+}
+
+#[test]
+fn test_protocol_pause_twice_and_resume_unpaused_rejected() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().try_admin_resume_protocol(),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+    ctx.client().admin_pause_protocol();
+    assert_eq!(
+        ctx.client().try_admin_pause_protocol(),
+        Err(Ok(crate::ContractError::ProtocolPaused))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_protocol_pause_requires_admin() {
+    let ctx = TestContext::setup_strict();
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "admin_pause_protocol",
+            args: ().into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().admin_pause_protocol();
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdraw_to
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_to_cold_wallet() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let cold_wallet = Address::generate(&ctx.env);
+
+    ctx.env.ledger().set_timestamp(250);
+    let amount = ctx.client().withdraw_to(&stream_id, &cold_wallet);
+    assert_eq!(amount, 250);
+    assert_eq!(ctx.token().balance(&cold_wallet), 250);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 750);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 250);
+    assert_eq!(state.status, StreamStatus::Active);
+
+    // A later plain withdraw only pays what accrued since.
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 150);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 150);
+}
+
+#[test]
+fn test_withdraw_to_recipient_matches_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1000);
+    let amount = ctx.client().withdraw_to(&stream_id, &ctx.recipient);
+    assert_eq!(amount, 1000);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_withdraw_to_nothing_accrued_returns_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let cold_wallet = Address::generate(&ctx.env);
+
+    assert_eq!(ctx.client().withdraw_to(&stream_id, &cold_wallet), 0);
+    assert_eq!(ctx.token().balance(&cold_wallet), 0);
+}
+
+#[test]
+fn test_withdraw_to_paused_stream_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let cold_wallet = Address::generate(&ctx.env);
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(
+        ctx.client().try_withdraw_to(&stream_id, &cold_wallet),
+        Err(Ok(crate::ContractError::StreamPaused))
+    );
+}
+
+#[test]
+fn test_withdraw_to_as_operator() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    let cold_wallet = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+
+    ctx.env.ledger().set_timestamp(600);
+    let amount = ctx
+        .client()
+        .withdraw_to_as_operator(&stream_id, &operator, &cold_wallet);
+    assert_eq!(amount, 600);
+    assert_eq!(ctx.token().balance(&cold_wallet), 600);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        600
+    );
+}
+
+#[test]
+fn test_withdraw_to_as_unapproved_operator_rejected() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let stranger = Address::generate(&ctx.env);
+
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(
+        ctx.client()
+            .try_withdraw_to_as_operator(&stream_id, &stranger, &stranger),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_withdraw_to_requires_recipient_auth() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    let cold_wallet = Address::generate(&ctx.env);
+    ctx.env.ledger().set_timestamp(500);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "withdraw_to",
+            args: (stream_id, cold_wallet.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().withdraw_to(&stream_id, &cold_wallet);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — irrevocable (non-cancellable) streams
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_streams_are_cancellable_by_default() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(ctx.client().get_stream_cancellable(&stream_id));
+    assert!(crate::StreamOptions::default().cancellable);
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_irrevocable_stream_rejects_every_cancel_path() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancellable: false,
+        ..Default::default()
+    });
+    assert!(!ctx.client().get_stream_cancellable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(200);
+    let not_cancellable = crate::ContractError::StreamNotCancellable;
+    assert_eq!(
+        ctx.client().try_cancel_stream(&stream_id),
+        Err(Ok(not_cancellable))
+    );
+    assert_eq!(
+        ctx.client().try_cancel_stream_as_recipient(&stream_id),
+        Err(Ok(not_cancellable))
+    );
+    assert_eq!(
+        ctx.client().try_cancel_stream_as_admin(&stream_id),
+        Err(Ok(not_cancellable))
+    );
+    assert_eq!(
+        ctx.client().try_admin_cancel_stream(&stream_id, &1u32),
+        Err(Ok(not_cancellable))
+    );
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_irrevocable_stream_cancel_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancellable: false,
+        ..Default::default()
+    });
+    ctx.client().cancel_stream(&stream_id);
+}
+
+#[test]
+fn test_irrevocable_stream_can_pause_resume_and_complete() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancellable: false,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+    assert_eq!(
+        ctx.client().try_cancel_stream(&stream_id),
+        Err(Ok(crate::ContractError::StreamNotCancellable))
+    );
+    ctx.client().resume_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_stream_analytics
+// ---------------------------------------------------------------------------
+
+fn analytics(
+    accrued: i128,
+    withdrawable: i128,
+    refundable: i128,
+    completion_bps: u32,
+    time_remaining: u64,
+) -> crate::StreamAnalytics {
+    crate::StreamAnalytics {
+        accrued,
+        withdrawable,
+        refundable,
+        completion_bps,
+        time_remaining,
+    }
+}
+
+#[test]
+fn test_stream_analytics_through_lifecycle() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(0, 0, 1000, 0, 1000)
+    );
+
+    ctx.env.ledger().set_timestamp(250);
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(250, 250, 750, 2_500, 750)
+    );
+
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(600, 350, 400, 6_000, 400)
+    );
+
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(1000, 750, 0, 10_000, 0)
+    );
+
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(1000, 0, 0, 10_000, 0)
+    );
+}
+
+#[test]
+fn test_stream_analytics_matches_individual_views() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    for t in [0u64, 1, 333, 500, 999, 1000, 2000] {
+        ctx.env.ledger().set_timestamp(t);
+        let a = ctx.client().get_stream_analytics(&stream_id);
+        assert_eq!(a.accrued, ctx.client().calculate_accrued(&stream_id));
+        assert_eq!(
+            a.withdrawable,
+            ctx.client().get_withdrawable_amount(&stream_id)
+        );
+        assert_eq!(a.refundable, ctx.client().get_refundable_amount(&stream_id));
+        assert!(a.completion_bps <= 10_000);
+    }
+}
+
+#[test]
+fn test_stream_analytics_paused_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().pause_stream(&stream_id);
+
+    // WithdrawalBlock pauses keep accruing but nothing is withdrawable.
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(500, 0, 500, 5_000, 500)
+    );
+}
+
+#[test]
+fn test_stream_analytics_cancelled_stream_keeps_unclaimed_visible() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(800);
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(300, 300, 0, 10_000, 0)
+    );
+
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_analytics(&stream_id),
+        analytics(300, 0, 0, 10_000, 0)
+    );
+}
+
+#[test]
+fn test_stream_analytics_completion_bps_bounded_for_large_deposit() {
+    let ctx = TestContext::setup();
+    let deposit = i128::MAX / 2;
+    let rate = deposit / 1000;
+    ctx.sac.mint(&ctx.sender, &deposit);
+
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &deposit,
+        &rate,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    ctx.env.ledger().set_timestamp(500);
+    let a = ctx.client().get_stream_analytics(&stream_id);
+    assert!(a.completion_bps <= 10_000);
+    assert_eq!(a.completion_bps, 5_000);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert!(ctx.client().get_stream_analytics(&stream_id).completion_bps <= 10_000);
+}
+
+#[test]
+fn test_stream_analytics_not_found() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().try_get_stream_analytics(&99u64),
+        Err(Ok(crate::ContractError::StreamNotFound))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — simulate_cancel
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_simulate_cancel_boundaries() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &100u64,
+        &1100u64,
+    );
+
+    assert_eq!(ctx.client().simulate_cancel(&stream_id, &0), (1000, 0));
+    assert_eq!(ctx.client().simulate_cancel(&stream_id, &600), (500, 500));
+    assert_eq!(ctx.client().simulate_cancel(&stream_id, &1100), (0, 1000));
+    assert_eq!(ctx.client().simulate_cancel(&stream_id, &5000), (0, 1000));
+}
+
+#[test]
+fn test_simulate_cancel_does_not_mutate_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let before = ctx.client().get_stream_state(&stream_id);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().simulate_cancel(&stream_id, &400), (600, 400));
+
+    let after = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(after.status, before.status);
+    assert_eq!(after.withdrawn_amount, before.withdrawn_amount);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+}
+
+#[test]
+fn test_simulate_cancel_terminal_streams_rejected() {
+    let ctx = TestContext::setup();
+    let cancelled = ctx.create_default_stream();
+    ctx.client().cancel_stream(&cancelled);
+    assert_eq!(
+        ctx.client().try_simulate_cancel(&cancelled, &500),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+
+    let completed = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&completed);
+    assert_eq!(
+        ctx.client().try_simulate_cancel(&completed, &1000),
+        Err(Ok(crate::ContractError::InvalidState))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_simulate_cancel_cancelled_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+    ctx.client().simulate_cancel(&stream_id, &0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_next_stream_id
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_next_stream_id_before_init_is_zero() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, FluxoraStream);
+    let client = FluxoraStreamClient::new(&env, &contract_id);
+    assert_eq!(client.get_next_stream_id(), 0);
+}
+
+#[test]
+fn test_get_next_stream_id_predicts_each_create() {
+    let ctx = TestContext::setup();
+    for n in 0..5u64 {
+        assert_eq!(ctx.client().get_next_stream_id(), n);
+        let stream_id = ctx.create_default_stream();
+        assert_eq!(stream_id, n);
+    }
+    assert_eq!(ctx.client().get_next_stream_id(), 5);
+}
+
+#[test]
+fn test_get_next_stream_id_unchanged_by_failed_create() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &0_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert!(result.is_err());
+    assert_eq!(ctx.client().get_next_stream_id(), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream_exists / get_stream_state_option
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_stream_exists_false_for_never_created_ids() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.client().stream_exists(&0));
+    assert!(ctx.client().get_stream_state_option(&0).is_none());
+
+    ctx.create_default_stream();
+    assert!(!ctx.client().stream_exists(&1));
+    assert!(!ctx.client().stream_exists(&u64::MAX));
+    assert!(ctx.client().get_stream_state_option(&1).is_none());
+}
+
+#[test]
+fn test_stream_exists_true_in_every_status() {
+    let ctx = TestContext::setup();
+
+    let active = ctx.create_default_stream();
+    let paused = ctx.create_default_stream();
+    ctx.client().pause_stream(&paused);
+    let cancelled = ctx.create_default_stream();
+    ctx.client().cancel_stream(&cancelled);
+    let scheduled = create_scheduled_stream(&ctx);
+    let pending = ctx.client().create_pending_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &500u64,
+    );
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&active);
+
+    for (stream_id, status) in [
+        (active, StreamStatus::Completed),
+        (paused, StreamStatus::Paused),
+        (cancelled, StreamStatus::Cancelled),
+        (scheduled, StreamStatus::Scheduled),
+        (pending, StreamStatus::Pending),
+    ] {
+        assert!(ctx.client().stream_exists(&stream_id));
+        let state = ctx.client().get_stream_state_option(&stream_id).unwrap();
+        assert_eq!(state.stream_id, stream_id);
+        assert_eq!(state.status, status);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests — max_streams_per_sender
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_sender_stream_cap_enforced() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(3), &None, &None, &None);
+
+    for _ in 0..3 {
+        ctx.create_default_stream();
+    }
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 3);
+
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::StreamLimitExceeded)));
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 3);
+    assert_eq!(ctx.token().balance(&ctx.sender), 7_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_sender_stream_cap_exceeded_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(1), &None, &None, &None);
+    ctx.create_default_stream();
+    ctx.create_default_stream();
+}
+
+#[test]
+fn test_sender_stream_cap_counts_terminal_streams() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(1), &None, &None, &None);
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::StreamLimitExceeded)));
+}
+
+#[test]
+fn test_sender_stream_cap_is_per_sender() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(1), &None, &None, &None);
+    ctx.create_default_stream();
+
+    let other_sender = Address::generate(&ctx.env);
+    ctx.sac.mint(&other_sender, &1000_i128);
+    ctx.client().create_stream(
+        &other_sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(ctx.client().get_sender_stream_count(&other_sender), 1);
+}
+
+#[test]
+fn test_sender_stream_cap_zero_is_unlimited() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_config().max_streams_per_sender, 0);
+    for _ in 0..10 {
+        ctx.create_default_stream();
+    }
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 10);
+}
+
+#[test]
+fn test_sender_stream_cap_applies_to_whole_batch() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(2), &None, &None, &None);
+    ctx.create_default_stream();
+
+    let params = CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 1000,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+    };
+    let batch = soroban_sdk::vec![&ctx.env, params.clone(), params.clone()];
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &batch),
+        Err(Ok(crate::ContractError::StreamLimitExceeded))
+    );
+
+    let batch = soroban_sdk::vec![&ctx.env, params];
+    assert_eq!(ctx.client().create_streams(&ctx.sender, &batch).len(), 1);
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — list_streams_paginated
+// ---------------------------------------------------------------------------
+
+fn page_ids(page: &soroban_sdk::Vec<crate::Stream>) -> std::vec::Vec<u64> {
+    page.iter().map(|stream| stream.stream_id).collect()
+}
+
+#[test]
+fn test_list_streams_paginated_pages_of_three() {
+    let ctx = TestContext::setup();
+    for _ in 0..10 {
+        ctx.create_default_stream();
+    }
+
+    let mut from_id = 0u64;
+    let mut pages = std::vec::Vec::new();
+    loop {
+        let page = ctx.client().list_streams_paginated(&from_id, &3);
+        if page.is_empty() {
+            break;
+        }
+        from_id = page.last().unwrap().stream_id + 1;
+        pages.push(page_ids(&page));
+    }
+
+    assert_eq!(
+        pages,
+        std::vec![
+            std::vec![0, 1, 2],
+            std::vec![3, 4, 5],
+            std::vec![6, 7, 8],
+            std::vec![9],
+        ]
+    );
+}
+
+#[test]
+fn test_list_streams_paginated_returns_full_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&stream_id);
+
+    let page = ctx.client().list_streams_paginated(&0, &10);
+    assert_eq!(page.len(), 1);
+    let stream = page.get(0).unwrap();
+    assert_eq!(stream.sender, ctx.sender);
+    assert_eq!(stream.recipient, ctx.recipient);
+    assert_eq!(stream.status, StreamStatus::Paused);
+}
+
+#[test]
+fn test_list_streams_paginated_past_end_is_empty() {
+    let ctx = TestContext::setup();
+    assert!(ctx.client().list_streams_paginated(&0, &10).is_empty());
+
+    for _ in 0..3 {
+        ctx.create_default_stream();
+    }
+    assert!(ctx.client().list_streams_paginated(&3, &10).is_empty());
+    assert!(ctx
+        .client()
+        .list_streams_paginated(&u64::MAX, &10)
+        .is_empty());
+    assert!(ctx.client().list_streams_paginated(&0, &0).is_empty());
+}
+
+#[test]
+fn test_list_streams_paginated_clamps_limit() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &100_000_i128);
+    for _ in 0..(crate::MAX_PAGE_SIZE + 5) {
+        ctx.create_default_stream();
+    }
+
+    let page = ctx.client().list_streams_paginated(&0, &u32::MAX);
+    assert_eq!(page.len(), crate::MAX_PAGE_SIZE);
+
+    let rest = ctx
+        .client()
+        .list_streams_paginated(&(crate::MAX_PAGE_SIZE as u64), &u32::MAX);
+    assert_eq!(rest.len(), 5);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_stream_ids_by_status
+// ---------------------------------------------------------------------------
+
+/// Create 20 streams: IDs `4k + 1` paused, `4k + 2` cancelled, the rest active.
+fn create_mixed_status_streams(ctx: &TestContext) {
+    ctx.sac.mint(&ctx.sender, &20_000_i128);
+    for i in 0..20u64 {
+        let stream_id = ctx.create_default_stream();
+        match i % 4 {
+            1 => ctx.client().pause_stream(&stream_id),
+            2 => {
+                ctx.client().cancel_stream(&stream_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_stream_ids_by_status_pages_active_streams() {
+    let ctx = TestContext::setup();
+    create_mixed_status_streams(&ctx);
+
+    let mut from_id = 0u64;
+    let mut pages = std::vec::Vec::new();
+    loop {
+        let (ids, next_from_id) =
+            ctx.client()
+                .get_stream_ids_by_status(&StreamStatus::Active, &from_id, &4);
+        if ids.is_empty() {
+            assert_eq!(next_from_id, ctx.client().get_next_stream_id());
+            break;
+        }
+        assert!(next_from_id > ids.last().unwrap());
+        from_id = next_from_id;
+        pages.push(ids.iter().collect::<std::vec::Vec<u64>>());
+    }
+
+    assert_eq!(
+        pages,
+        std::vec![
+            std::vec![0, 3, 4, 7],
+            std::vec![8, 11, 12, 15],
+            std::vec![16, 19],
+        ]
+    );
+}
+
+#[test]
+fn test_stream_ids_by_status_filters_each_status() {
+    let ctx = TestContext::setup();
+    create_mixed_status_streams(&ctx);
+
+    let (paused, next) = ctx
+        .client()
+        .get_stream_ids_by_status(&StreamStatus::Paused, &0, &100);
+    assert_eq!(paused, soroban_sdk::vec![&ctx.env, 1, 5, 9, 13, 17]);
+    assert_eq!(next, 20);
+
+    let (cancelled, _) = ctx
+        .client()
+        .get_stream_ids_by_status(&StreamStatus::Cancelled, &10, &100);
+    assert_eq!(cancelled, soroban_sdk::vec![&ctx.env, 10, 14, 18]);
+
+    let (completed, next) =
+        ctx.client()
+            .get_stream_ids_by_status(&StreamStatus::Completed, &0, &100);
+    assert!(completed.is_empty());
+    assert_eq!(next, 20);
+}
+
+#[test]
+fn test_stream_ids_by_status_empty_and_past_end() {
+    let ctx = TestContext::setup();
+    let (ids, next) = ctx
+        .client()
+        .get_stream_ids_by_status(&StreamStatus::Active, &0, &10);
+    assert!(ids.is_empty());
+    assert_eq!(next, 0);
+
+    ctx.create_default_stream();
+    let (ids, next) = ctx
+        .client()
+        .get_stream_ids_by_status(&StreamStatus::Active, &5, &10);
+    assert!(ids.is_empty());
+    assert_eq!(next, 5);
+
+    let (ids, next) = ctx
+        .client()
+        .get_stream_ids_by_status(&StreamStatus::Active, &0, &0);
+    assert!(ids.is_empty());
+    assert_eq!(next, 0);
+}
+
+#[test]
+fn test_stream_ids_by_status_clamps_limit() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &200_000_i128);
+    for _ in 0..(crate::MAX_STATUS_PAGE_SIZE + 3) {
+        // Each create is its own transaction on-chain, with its own budget.
+        ctx.env.budget().reset_default();
+        ctx.create_default_stream();
+    }
+
+    ctx.env.budget().reset_default();
+    let (ids, next) = ctx
+        .client()
+        .get_stream_ids_by_status(&StreamStatus::Active, &0, &u32::MAX);
+    assert_eq!(ids.len(), crate::MAX_STATUS_PAGE_SIZE);
+    assert_eq!(next, u64::from(crate::MAX_STATUS_PAGE_SIZE));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — total value locked / total streamed
+// ---------------------------------------------------------------------------
+
+/// TVL plus unwithdrawn fees must always equal the contract's token balance.
+fn assert_tvl_matches_balance(ctx: &TestContext) {
+    assert_eq!(
+        ctx.client().get_total_value_locked() + ctx.client().get_accumulated_fees(),
+        ctx.token().balance(&ctx.contract_id)
+    );
+}
+
+#[test]
+fn test_totals_start_at_zero() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_total_value_locked(), 0);
+    assert_eq!(ctx.client().get_total_streamed(), 0);
+}
+
+#[test]
+fn test_totals_track_withdrawals_and_cancellations() {
+    let ctx = TestContext::setup();
+    let a = ctx.create_default_stream();
+    let b = ctx.create_default_stream();
+    let c = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_total_value_locked(), 3000);
+    assert_tvl_matches_balance(&ctx);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&a);
+    assert_eq!(ctx.client().get_total_value_locked(), 2700);
+    assert_eq!(ctx.client().get_total_streamed(), 300);
+    assert_tvl_matches_balance(&ctx);
+
+    // Cancel refunds 600; the 400 accrued stays locked until withdrawn.
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&b);
+    assert_eq!(ctx.client().get_total_value_locked(), 2100);
+    assert_tvl_matches_balance(&ctx);
+
+    ctx.client().withdraw(&b);
+    assert_eq!(ctx.client().get_total_value_locked(), 1700);
+    assert_eq!(ctx.client().get_total_streamed(), 700);
+    assert_tvl_matches_balance(&ctx);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&a);
+    ctx.client().withdraw(&c);
+    assert_eq!(ctx.client().get_total_value_locked(), 0);
+    assert_eq!(ctx.client().get_total_streamed(), 2400);
+    assert_tvl_matches_balance(&ctx);
+}
+
+#[test]
+fn test_totals_with_fees_topups_and_pending_refunds() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&Some(100), &None, &None, &None, &None, &None);
+
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    // 1% fee: 1980 locked for the stream, 20 in fees.
+    assert_eq!(ctx.client().get_total_value_locked(), 1980);
+    assert_tvl_matches_balance(&ctx);
+
+    ctx.client().topup_stream(&stream_id, &100_i128);
+    assert_eq!(ctx.client().get_total_value_locked(), 2080);
+    assert_tvl_matches_balance(&ctx);
+
+    let pending = ctx.client().create_pending_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &500u64,
+    );
+    assert_tvl_matches_balance(&ctx);
+    ctx.client().reject_stream(&pending);
+    assert_eq!(ctx.client().get_total_value_locked(), 2080);
+    assert_tvl_matches_balance(&ctx);
+
+    ctx.client().withdraw_protocol_fees(&ctx.admin);
+    assert_tvl_matches_balance(&ctx);
+}
+
+#[test]
+fn test_totals_track_batch_operations() {
+    let ctx = TestContext::setup();
+    let params = CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 1000,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+    };
+    let ids = ctx.client().create_streams(
+        &ctx.sender,
+        &soroban_sdk::vec![&ctx.env, params.clone(), params],
+    );
+    assert_eq!(ctx.client().get_total_value_locked(), 2000);
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().batch_withdraw(&ids);
+    assert_eq!(ctx.client().get_total_value_locked(), 1500);
+    assert_eq!(ctx.client().get_total_streamed(), 500);
+    assert_tvl_matches_balance(&ctx);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — admin pause (paused_by)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_pause_stream_records_admin_pauser() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().admin_pause_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.paused_by, crate::PauserKind::Admin);
+
+    let events = ctx.env.events().all();
+    assert_eq!(
+        crate::StreamPaused::from_val(&ctx.env, &events.last().unwrap().2),
+        crate::StreamPaused {
+            stream_id,
+            paused_at: 100,
+            admin: true,
+        }
+    );
+}
+
+#[test]
+fn test_sender_cannot_resume_admin_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().admin_pause_stream(&stream_id);
+
+    assert_eq!(
+        ctx.client().try_resume_stream(&stream_id),
+        Err(Ok(crate::ContractError::Unauthorized))
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Paused
+    );
+}
+
+#[test]
+fn test_admin_resume_stream_lifts_admin_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().admin_pause_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().admin_resume_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.paused_by, crate::PauserKind::NotPaused);
+
+    let events = ctx.env.events().all();
+    assert_eq!(
+        crate::StreamResumed::from_val(&ctx.env, &events.last().unwrap().2),
+        crate::StreamResumed {
+            stream_id,
+            resumed_at: 300,
+            admin: true,
+        }
+    );
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+}
+
+#[test]
+fn test_sender_pause_records_sender_and_admin_can_resume() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).paused_by,
+        crate::PauserKind::Sender
+    );
+
+    ctx.client().admin_resume_stream(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_admin_pause_stream_requires_admin() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "admin_pause_stream",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().admin_pause_stream(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — validate_stream_params
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_validate_stream_params_valid_returns_empty() {
+    let ctx = TestContext::setup();
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &100u64,
+        &1000u64,
+    );
+    assert_eq!(errors.len(), 0);
+
+    // A valid answer matches create_stream accepting the same parameters.
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &100u64,
+        &1000u64,
+    );
+}
+
+#[test]
+fn test_validate_stream_params_reports_every_violation() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &Some(1), &None, &None, &None);
+    ctx.create_default_stream();
+
+    // Self-stream, bad window and an underfunded deposit, from a sender at the cap.
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.sender,
+        &10_i128,
+        &1_i128,
+        &0u64,
+        &2000u64,
+        &1000u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![
+            &ctx.env,
+            crate::ContractError::InvalidParams,
+            crate::ContractError::UnfundedDeposit,
+            crate::ContractError::StreamLimitExceeded,
+        ]
+    );
+}
+
+#[test]
+fn test_validate_stream_params_overflow_and_fee() {
+    let ctx = TestContext::setup();
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &i128::MAX,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![&ctx.env, crate::ContractError::ArithmeticOverflow]
+    );
+
+    // With a 1% fee the net deposit no longer covers rate × duration.
+    ctx.client().set_fee_bps(&100);
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![&ctx.env, crate::ContractError::UnfundedDeposit]
+    );
+}
+
+#[test]
+fn test_validate_stream_params_has_no_side_effects() {
+    let ctx = TestContext::setup();
+    let sender_balance = ctx.token().balance(&ctx.sender);
+
+    // No balance is needed: the sender could never fund this deposit.
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1_000_000_000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(errors.len(), 0);
+    assert_eq!(ctx.client().get_next_stream_id(), 0);
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_balance);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — min_deposit / min_rate
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_minimums_default_to_zero() {
+    let ctx = TestContext::setup();
+    let config = ctx.client().get_config();
+    assert_eq!(config.min_deposit, 0);
+    assert_eq!(config.min_rate, 0);
+}
+
+#[test]
+fn test_create_stream_at_exact_minimums_passes() {
+    let ctx = TestContext::setup();
+    ctx.client().set_min_deposit(&1000_i128);
+    ctx.client().set_min_rate(&1_i128);
+
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).deposit_amount,
+        1000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_create_stream_below_min_deposit_panics() {
+    let ctx = TestContext::setup();
+    ctx.client().set_min_deposit(&1000_i128);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &999_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &999u64,
+    );
+}
+
+#[test]
+fn test_create_stream_below_min_rate_rejected() {
+    let ctx = TestContext::setup();
+    ctx.client().set_min_rate(&2_i128);
+
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidParams)));
+    assert_eq!(ctx.client().get_next_stream_id(), 0);
+
+    // The view reports the same violation.
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![&ctx.env, crate::ContractError::InvalidParams]
+    );
+}
+
+#[test]
+fn test_min_deposit_applies_to_batch_and_step_streams() {
+    let ctx = TestContext::setup();
+    ctx.client().set_min_deposit(&1000_i128);
+    ctx.client().set_min_rate(&1_i128);
+
+    let mut streams = soroban_sdk::Vec::new(&ctx.env);
+    streams.push_back(CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 500,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 500,
+    });
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &streams),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+
+    // Stepped streams have no rate, so only the deposit minimum applies.
+    let step_id = ctx.client().create_step_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &4,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(ctx.client().get_stream_state(&step_id).rate_per_second, 0);
+}
+
+#[test]
+fn test_raising_minimum_leaves_existing_streams_untouched() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.client().set_min_deposit(&5000_i128);
+    ctx.client().set_min_rate(&10_i128);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_lowering_minimums_to_zero_allows_any_value() {
+    let ctx = TestContext::setup();
+    ctx.client().set_min_deposit(&5000_i128);
+    ctx.client().set_min_rate(&10_i128);
+    ctx.client().set_min_deposit(&0_i128);
+    ctx.client().set_min_rate(&0_i128);
+
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1u64,
+    );
+    assert_eq!(ctx.client().get_next_stream_id(), 1);
+}
+
+#[test]
+fn test_negative_minimums_rejected() {
+    let ctx = TestContext::setup();
+    let e = crate::ContractError::InvalidParams;
+    assert_eq!(ctx.client().try_set_min_deposit(&-1_i128), Err(Ok(e)));
+    assert_eq!(ctx.client().try_set_min_rate(&-1_i128), Err(Ok(e)));
+
+    let config = ctx.client().get_config();
+    assert_eq!(config.min_deposit, 0);
+    assert_eq!(config.min_rate, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_set_min_rate_requires_admin_auth() {
+    let ctx = TestContext::setup_strict();
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_min_rate",
+            args: (5_i128,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().set_min_rate(&5_i128);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — terminal timestamps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cancel_records_cancelled_at() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client().cancel_stream(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.cancelled_at, Some(500));
+    assert_eq!(state.completed_at, None);
+
+    // Accrual stays frozen at cancelled_at.
+    ctx.env.ledger().set_timestamp(900);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 500);
+}
+
+#[test]
+fn test_full_withdrawal_records_completed_at() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_stream_state(&stream_id).completed_at, None);
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_stream_state(&stream_id).completed_at, None);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.completed_at, Some(1000));
+    assert_eq!(state.cancelled_at, None);
+}
+
+#[test]
+fn test_cancelled_stream_never_gets_completed_at() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(700);
+    ctx.client().withdraw(&stream_id);
+
+    // Claiming the accrued 300 leaves the stream Cancelled, not Completed.
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 300);
+    assert_eq!(state.status, StreamStatus::Cancelled);
+    assert_eq!(state.cancelled_at, Some(300));
+    assert_eq!(state.completed_at, None);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — cancel_stream_batch
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cancel_stream_batch_at_different_progress_levels() {
+    let ctx = TestContext::setup();
+    let linear = ctx.create_default_stream(); // 1000 over 0–1000
+    let fast = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &2_i128,
+        &0u64,
+        &0u64,
+        &500u64,
+    );
+    let cliff = ctx.create_cliff_stream(); // cliff at 500
+    assert_eq!(ctx.token().balance(&ctx.sender), 7_000);
+
+    ctx.env.ledger().set_timestamp(400);
+    let ids = soroban_sdk::vec![&ctx.env, linear, fast, cliff];
+    let results = ctx.client().cancel_stream_batch(&ids);
+
+    assert_eq!(
+        results,
+        soroban_sdk::vec![
+            &ctx.env,
+            crate::BatchCancelResult {
+                stream_id: linear,
+                refund: 600,
+                recipient_accrued: 400,
+            },
+            crate::BatchCancelResult {
+                stream_id: fast,
+                refund: 200,
+                recipient_accrued: 800,
+            },
+            crate::BatchCancelResult {
+                stream_id: cliff,
+                refund: 1000,
+                recipient_accrued: 0,
+            },
+        ]
+    );
+    for id in ids.iter() {
+        let state = ctx.client().get_stream_state(&id);
+        assert_eq!(state.status, StreamStatus::Cancelled);
+        assert_eq!(state.cancelled_at, Some(400));
+    }
+
+    // One refund of 1800 to the sender; accrued amounts stay claimable.
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_800);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1_200);
+
+    ctx.env.ledger().set_timestamp(2_000);
+    let claimed = ctx.client().batch_withdraw(&ids);
+    assert_eq!(claimed, soroban_sdk::vec![&ctx.env, 400_i128, 800, 0]);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1_200);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_cancel_stream_batch_empty_is_noop() {
+    let ctx = TestContext::setup();
+    let results = ctx.client().cancel_stream_batch(&Vec::new(&ctx.env));
+    assert_eq!(results.len(), 0);
+}
+
+#[test]
+fn test_cancel_stream_batch_foreign_stream_rolls_back() {
+    let ctx = TestContext::setup();
+    let own = ctx.create_default_stream();
+
+    let other_sender = Address::generate(&ctx.env);
+    ctx.sac.mint(&other_sender, &1_000_i128);
+    let foreign = ctx.client().create_stream(
+        &other_sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    ctx.env.ledger().set_timestamp(100);
+    let result = ctx
+        .client()
+        .try_cancel_stream_batch(&soroban_sdk::vec![&ctx.env, own, foreign]);
+    assert_eq!(result, Err(Ok(crate::ContractError::Unauthorized)));
+    assert_eq!(
+        ctx.client().get_stream_state(&own).status,
+        StreamStatus::Active
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+}
+
+#[test]
+fn test_cancel_stream_batch_invalid_id_rolls_back() {
+    let ctx = TestContext::setup();
+    let id = ctx.create_default_stream();
+
+    let e = crate::ContractError::StreamNotFound;
+    let ids = soroban_sdk::vec![&ctx.env, id, 99];
+    assert_eq!(ctx.client().try_cancel_stream_batch(&ids), Err(Ok(e)));
+
+    // A duplicate ID fails on its second cancellation.
+    let e = crate::ContractError::InvalidState;
+    let ids = soroban_sdk::vec![&ctx.env, id, id];
+    assert_eq!(ctx.client().try_cancel_stream_batch(&ids), Err(Ok(e)));
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_cancel_stream_batch_requires_sender_auth() {
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let id = ctx.create_default_stream();
+    let ids = soroban_sdk::vec![&ctx.env, id];
+
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "cancel_stream_batch",
+            args: (ids.clone(),).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().cancel_stream_batch(&ids);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — is_withdrawable
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_is_withdrawable_linear_lifecycle() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream(); // cliff at 500
+
+    // Before the cliff nothing is claimable.
+    ctx.env.ledger().set_timestamp(499);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(500);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+
+    // Zero balance right after a withdrawal.
+    ctx.client().withdraw(&stream_id);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(600);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+
+    ctx.client().pause_stream(&stream_id);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+    ctx.client().resume_stream(&stream_id);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+}
+
+#[test]
+fn test_is_withdrawable_cancelled_stream_until_claimed() {
+    let ctx = TestContext::setup();
+    let claimable = ctx.create_default_stream();
+    let fresh = ctx.create_default_stream();
+
+    // Cancelled before anything accrued: nothing to claim.
+    ctx.client().cancel_stream(&fresh);
+    assert!(!ctx.client().is_withdrawable(&fresh));
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&claimable);
+    assert!(ctx.client().is_withdrawable(&claimable));
+    ctx.client().withdraw(&claimable);
+    assert!(!ctx.client().is_withdrawable(&claimable));
+}
+
+#[test]
+fn test_is_withdrawable_false_while_protocol_paused() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(500);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+
+    ctx.client().admin_pause_protocol();
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 500);
+
+    ctx.client().admin_resume_protocol();
+    assert!(ctx.client().is_withdrawable(&stream_id));
+}
+
+#[test]
+fn test_is_withdrawable_scheduled_and_pending_streams() {
+    let ctx = TestContext::setup();
+    let scheduled = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &100u64,
+        &1100u64,
+    );
+    let pending = ctx.client().create_pending_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &5000u64,
+    );
+
+    ctx.env.ledger().set_timestamp(50);
+    assert!(!ctx.client().is_withdrawable(&scheduled));
+    assert!(!ctx.client().is_withdrawable(&pending));
+
+    // Past its start a scheduled stream is claimable; withdraw activates it.
+    ctx.env.ledger().set_timestamp(150);
+    assert!(ctx.client().is_withdrawable(&scheduled));
+    assert!(!ctx.client().is_withdrawable(&pending));
+}
+
+#[test]
+fn test_is_withdrawable_missing_stream() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().try_is_withdrawable(&7),
+        Err(Ok(crate::ContractError::StreamNotFound))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — min_duration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_create_stream_at_exact_min_duration_passes() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &Some(1000), &None, &None);
+    assert_eq!(ctx.client().get_config().min_duration, 1000);
+
+    let stream_id = ctx.create_default_stream(); // exactly 1000s
+    assert_eq!(ctx.client().get_stream_state(&stream_id).end_time, 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_create_stream_one_second_below_min_duration_panics() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &Some(1000), &None, &None);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &999_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &999u64,
+    );
+}
+
+#[test]
+fn test_min_duration_zero_allows_one_second_stream() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &Some(60), &None, &None);
+    ctx.client()
+        .update_config(&None, &None, &None, &Some(0), &None, &None);
+
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1u64,
+    );
+    assert_eq!(ctx.client().get_next_stream_id(), 1);
+}
+
+#[test]
+fn test_min_duration_ignores_cliff_offset() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &Some(1000), &None, &None);
+
+    // A cliff 1s before the end is fine: only the full window must meet the minimum.
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &999u64,
+        &1000u64,
+    );
+    assert_eq!(ctx.client().get_stream_state(&stream_id).cliff_time, 999);
+}
+
+#[test]
+fn test_min_duration_reported_and_applied_to_batches() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &Some(1000), &None, &None);
+
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &500_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &500u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![&ctx.env, crate::ContractError::InvalidParams]
+    );
+
+    let mut streams = Vec::new(&ctx.env);
+    streams.push_back(CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 500,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 500,
+    });
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &streams),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_create_step_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &500_i128,
+            &5,
+            &0u64,
+            &500u64
+        ),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — update_recipient
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_update_recipient_moves_stream_and_emits_event() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.client().update_recipient(&stream_id, &new_recipient);
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(
+        crate::RecipientUpdated::from_val(&ctx.env, &last.2),
+        crate::RecipientUpdated {
+            stream_id,
+            old_recipient: ctx.recipient.clone(),
+            new_recipient: new_recipient.clone(),
+        }
+    );
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.recipient, new_recipient);
+    assert_eq!(state.approved_operator, None);
+    assert_eq!(
+        ctx.client().get_streams_by_recipient(&ctx.recipient).len(),
+        0
+    );
+    assert_eq!(
+        ctx.client().get_streams_by_recipient(&new_recipient),
+        soroban_sdk::vec![&ctx.env, stream_id]
+    );
+}
+
+#[test]
+fn test_withdraw_after_update_recipient_pays_new_address() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&stream_id);
+
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.client().update_recipient(&stream_id, &new_recipient);
+
+    // Accrued-but-unclaimed tokens follow the stream to the new recipient.
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&new_recipient), 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 200);
+}
+
+#[test]
+#[should_panic]
+fn test_old_recipient_cannot_withdraw_after_update() {
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.client().update_recipient(&stream_id, &new_recipient);
+
+    ctx.env.ledger().set_timestamp(500);
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "withdraw",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().withdraw(&stream_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_update_recipient_completed_stream_panics() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.client()
+        .update_recipient(&stream_id, &Address::generate(&ctx.env));
+}
+
+#[test]
+fn test_update_recipient_rejects_cancelled_and_invalid_targets() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    let e = crate::ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client()
+            .try_update_recipient(&stream_id, &ctx.recipient),
+        Err(Ok(e))
+    );
+    assert_eq!(
+        ctx.client().try_update_recipient(&stream_id, &ctx.sender),
+        Err(Ok(e))
+    );
+
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(
+        ctx.client()
+            .try_update_recipient(&stream_id, &Address::generate(&ctx.env)),
+        Err(Ok(crate::ContractError::StreamCancelled))
+    );
+}
+
+#[test]
+fn test_update_recipient_keeps_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.client()
+        .update_recipient(&stream_id, &Address::generate(&ctx.env));
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.paused_at, Some(100));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — transfer_stream_sender
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_transfer_stream_sender_updates_indexes_and_emits_event() {
+    let ctx = TestContext::setup();
+    let kept = ctx.create_default_stream();
+    let moved = ctx.create_default_stream();
+    let new_sender = Address::generate(&ctx.env);
+
+    ctx.client().transfer_stream_sender(&moved, &new_sender);
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(
+        crate::SenderTransferred::from_val(&ctx.env, &last.2),
+        crate::SenderTransferred {
+            stream_id: moved,
+            old_sender: ctx.sender.clone(),
+            new_sender: new_sender.clone(),
+        }
+    );
+    assert_eq!(ctx.client().get_stream_state(&moved).sender, new_sender);
+    assert_eq!(
+        ctx.client().get_streams_by_sender(&ctx.sender),
+        soroban_sdk::vec![&ctx.env, kept]
+    );
+    assert_eq!(
+        ctx.client().get_streams_by_sender(&new_sender),
+        soroban_sdk::vec![&ctx.env, moved]
+    );
+}
+
+#[test]
+fn test_new_sender_can_cancel_and_receives_refund() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let new_sender = Address::generate(&ctx.env);
+    ctx.client().transfer_stream_sender(&stream_id, &new_sender);
+
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&new_sender), 600);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+}
+
+#[test]
+#[should_panic]
+fn test_old_sender_cannot_cancel_after_transfer() {
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    ctx.client()
+        .transfer_stream_sender(&stream_id, &Address::generate(&ctx.env));
+
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "cancel_stream",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().cancel_stream(&stream_id);
+}
+
+#[test]
+fn test_transfer_stream_sender_rejections() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    let e = crate::ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client()
+            .try_transfer_stream_sender(&stream_id, &ctx.sender),
+        Err(Ok(e))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_transfer_stream_sender(&stream_id, &ctx.recipient),
+        Err(Ok(e))
+    );
+
+    // The new sender's stream cap applies to transferred streams too.
+    let busy_sender = Address::generate(&ctx.env);
+    ctx.sac.mint(&busy_sender, &1_000_i128);
+    ctx.client().create_stream(
+        &busy_sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    ctx.client()
+        .update_config(&None, &None, &Some(1), &None, &None, &None);
+    assert_eq!(
+        ctx.client()
+            .try_transfer_stream_sender(&stream_id, &busy_sender),
+        Err(Ok(crate::ContractError::StreamLimitExceeded))
+    );
+
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(
+        ctx.client()
+            .try_transfer_stream_sender(&stream_id, &Address::generate(&ctx.env)),
+        Err(Ok(crate::ContractError::StreamCancelled))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream TTL maintenance
+// ---------------------------------------------------------------------------
+
+/// Remaining TTL of a stream's persistent entry, read from inside the contract.
+fn stream_ttl(ctx: &TestContext, stream_id: u64) -> u32 {
+    use soroban_sdk::testutils::storage::Persistent as _;
+    ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env
+            .storage()
+            .persistent()
+            .get_ttl(&crate::DataKey::Stream(stream_id))
+    })
+}
+
+#[test]
+#[should_panic]
+fn test_idle_stream_expires_without_bump() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let seq = ctx.env.ledger().sequence();
+
+    ctx.env.ledger().set_sequence_number(seq + 200_000);
+    ctx.client().get_stream_state(&stream_id);
+}
+
+#[test]
+fn test_bump_stream_ttl_keeps_idle_stream_alive() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let seq = ctx.env.ledger().sequence();
+
+    ctx.client().bump_stream_ttl(&stream_id, &500_000);
+    assert_eq!(stream_ttl(&ctx, stream_id), 500_000);
+
+    ctx.env.ledger().set_sequence_number(seq + 200_000);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.stream_id, stream_id);
+}
+
+#[test]
+fn test_bump_stream_ttl_rejections() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    let e = crate::ContractError::InvalidParams;
+    assert_eq!(ctx.client().try_bump_stream_ttl(&stream_id, &0), Err(Ok(e)));
+    assert_eq!(
+        ctx.client().try_bump_stream_ttl(&stream_id, &u32::MAX),
+        Err(Ok(e))
+    );
+    assert_eq!(
+        ctx.client().try_bump_stream_ttl(&42, &1_000),
+        Err(Ok(crate::ContractError::StreamNotFound))
+    );
+}
+
+#[test]
+fn test_min_ttl_ledgers_applies_on_every_write() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_config().min_ttl_ledgers, 0);
+    ctx.client().set_min_ttl_ledgers(&300_000);
+
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(stream_ttl(&ctx, stream_id), 300_000);
+
+    // Regular traffic keeps the instance alive; the idle stream drains meanwhile.
+    let seq = ctx.env.ledger().sequence();
+    for offset in [110_000, 220_000, 290_000] {
+        ctx.env.ledger().set_sequence_number(seq + offset);
+        ctx.client().get_config();
+    }
+    assert_eq!(stream_ttl(&ctx, stream_id), 10_000);
+
+    // A write once the TTL is below the threshold extends it to the configured value.
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().pause_stream(&stream_id);
+    assert_eq!(stream_ttl(&ctx, stream_id), 300_000);
+}
+
+#[test]
+fn test_set_min_ttl_ledgers_above_max_rejected() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().try_set_min_ttl_ledgers(&u32::MAX),
+        Err(Ok(crate::ContractError::InvalidParams))
+    );
+    assert_eq!(ctx.client().get_config().min_ttl_ledgers, 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_protocol_stats
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_protocol_stats_zero_after_init() {
+    let ctx = TestContext::setup();
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!(
+        stats,
+        crate::ProtocolStats {
+            total_streams: 0,
+            active_count: 0,
+            paused_count: 0,
+            completed_count: 0,
+            cancelled_count: 0,
+            total_deposited: 0,
+            total_withdrawn: 0,
+        }
+    );
+}
+
+#[test]
+fn test_protocol_stats_counts_batch_created_streams() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let params = |start_time: u64| CreateStreamParams {
+        recipient: Address::generate(&ctx.env),
+        deposit_amount: 1000,
+        rate_per_second: 1,
+        start_time,
+        cliff_time: start_time,
+        end_time: start_time + 1000,
+    };
+    ctx.client().create_streams(
+        &ctx.sender,
+        &soroban_sdk::vec![&ctx.env, params(0), params(100)],
+    );
+
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!((stats.total_streams, stats.active_count), (2, 2));
+    assert_eq!(stats.total_deposited, 2000);
+}
+
+#[test]
+fn test_protocol_stats_admin_pause_and_resume_move_buckets() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.client().admin_pause_stream(&stream_id);
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!((stats.active_count, stats.paused_count), (0, 1));
+
+    ctx.client().admin_resume_stream(&stream_id);
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!((stats.active_count, stats.paused_count), (1, 0));
+}
+
+#[test]
+fn test_protocol_stats_batch_cancel_counts_each_stream() {
+    let ctx = TestContext::setup();
+    let ids = create_default_streams(&ctx, 3);
+    ctx.client().pause_stream(&ids.get(1).unwrap());
+
+    ctx.client().cancel_stream_batch(&ids);
+
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!(stats.cancelled_count, 3);
+    assert_eq!(stats.active_count + stats.paused_count, 0);
+}
+
+#[test]
+fn test_protocol_stats_total_deposited_includes_extension() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client()
+        .extend_stream_end_time(&stream_id, &1500u64, &500_i128);
+
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!(stats.total_deposited, 1500);
+    assert_eq!(stats.total_withdrawn, 0);
+}
+
+#[test]
+fn test_protocol_stats_pending_accept_stays_active() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_pending_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &100u64,
+    );
+    assert_eq!(ctx.client().get_protocol_stats().active_count, 1);
+
+    ctx.client().accept_stream(&stream_id);
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!((stats.total_streams, stats.active_count), (1, 1));
+}
+
+#[test]
+fn test_protocol_stats_cancelled_without_refund_completes_on_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1500);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.client().get_protocol_stats().cancelled_count, 1);
+
+    ctx.client().withdraw(&stream_id);
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!((stats.cancelled_count, stats.completed_count), (0, 1));
+    assert_eq!(stats.total_withdrawn, 1000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_stream_completion_bps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_completion_bps_tracks_accrual() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    for (t, expected) in [
+        (0u64, 0u32),
+        (250, 2_500),
+        (500, 5_000),
+        (750, 7_500),
+        (1000, 10_000),
+        (5000, 10_000),
+    ] {
+        ctx.env.ledger().set_timestamp(t);
+        assert_eq!(
+            ctx.client().get_stream_completion_bps(&stream_id),
+            expected,
+            "t={t}"
+        );
+        assert_eq!(
+            ctx.client().get_stream_analytics(&stream_id).completion_bps,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_completion_bps_scheduled_stream_is_zero() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &500u64,
+        &500u64,
+        &1500u64,
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Scheduled
+    );
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().get_stream_completion_bps(&stream_id), 0);
+}
+
+#[test]
+fn test_completion_bps_completed_stream_is_full() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+
+    assert_eq!(ctx.client().get_stream_completion_bps(&stream_id), 10_000);
+}
+
+#[test]
+fn test_completion_bps_cliff_stream_counts_from_start() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+
+    ctx.env.ledger().set_timestamp(499);
+    assert_eq!(ctx.client().get_stream_completion_bps(&stream_id), 0);
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().get_stream_completion_bps(&stream_id), 5_000);
+}
+
+#[test]
+fn test_completion_bps_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_stream_completion_bps(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream memo
+// ---------------------------------------------------------------------------
+
+fn create_memo_stream(ctx: &TestContext, memo: &soroban_sdk::Bytes) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream_with_memo(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        memo,
+    )
+}
+
+#[test]
+fn test_create_stream_with_memo_stores_memo() {
+    let ctx = TestContext::setup();
+    let memo = soroban_sdk::Bytes::from_array(&ctx.env, &[7u8; 32]);
+    let stream_id = create_memo_stream(&ctx, &memo);
+
+    assert_eq!(ctx.client().get_stream_memo(&stream_id), memo);
+    assert_eq!(ctx.client().get_stream_state(&stream_id).memo, memo);
+}
+
+#[test]
+fn test_create_stream_with_memo_accepts_max_len() {
+    let ctx = TestContext::setup();
+    let memo = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 64]);
+    let stream_id = create_memo_stream(&ctx, &memo);
+    assert_eq!(ctx.client().get_stream_memo(&stream_id).len(), 64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_create_stream_with_memo_too_long_panics() {
+    let ctx = TestContext::setup();
+    let memo = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 65]);
+    create_memo_stream(&ctx, &memo);
+}
+
+#[test]
+fn test_create_stream_with_memo_too_long_moves_no_tokens() {
+    let ctx = TestContext::setup();
+    let memo = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 65]);
+    let result = ctx.client().try_create_stream_with_memo(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &memo,
+    );
+
+    let e = ContractError::InvalidParams;
+    assert_eq!(result, Err(Ok(e)));
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.client().get_next_stream_id(), 0);
+}
+
+#[test]
+fn test_create_stream_with_empty_memo_matches_create_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = create_memo_stream(&ctx, &soroban_sdk::Bytes::new(&ctx.env));
+    let plain_id = ctx.create_default_stream();
+
+    assert!(ctx.client().get_stream_memo(&stream_id).is_empty());
+    assert!(ctx.client().get_stream_memo(&plain_id).is_empty());
+}
+
+#[test]
+fn test_get_stream_memo_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_stream_memo(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — agreement hash
+// ---------------------------------------------------------------------------
+
+fn create_agreement_stream(ctx: &TestContext, hash: &soroban_sdk::BytesN<32>) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream_with_agreement(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        hash,
+    )
+}
+
+#[test]
+fn test_create_stream_with_agreement_stores_hash() {
+    let ctx = TestContext::setup();
+    let hash = soroban_sdk::BytesN::from_array(&ctx.env, &[9u8; 32]);
+    let stream_id = create_agreement_stream(&ctx, &hash);
+
+    assert_eq!(ctx.client().get_agreement_hash(&stream_id), Some(hash));
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+}
+
+#[test]
+fn test_create_stream_without_agreement_has_no_hash() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_agreement_hash(&stream_id), None);
+}
+
+#[test]
+fn test_agreement_hash_survives_stream_changes() {
+    let ctx = TestContext::setup();
+    let hash = soroban_sdk::BytesN::from_array(&ctx.env, &[3u8; 32]);
+    let stream_id = create_agreement_stream(&ctx, &hash);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.client().update_recipient(&stream_id, &new_recipient);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(ctx.client().get_agreement_hash(&stream_id), Some(hash));
+}
+
+#[test]
+fn test_get_agreement_hash_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_agreement_hash(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — stream description
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_stream_description_sets_and_updates() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(ctx.client().get_stream_description(&stream_id).is_empty());
+
+    let first = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 16]);
+    ctx.client().set_stream_description(&stream_id, &first);
+    assert_eq!(ctx.env.auths()[0].0, ctx.sender);
+    assert_eq!(ctx.client().get_stream_description(&stream_id), first);
+
+    let second = soroban_sdk::Bytes::from_array(&ctx.env, &[2u8; 256]);
+    ctx.client().set_stream_description(&stream_id, &second);
+    assert_eq!(ctx.client().get_stream_description(&stream_id), second);
+
+    ctx.client()
+        .set_stream_description(&stream_id, &soroban_sdk::Bytes::new(&ctx.env));
+    assert!(ctx.client().get_stream_description(&stream_id).is_empty());
+}
+
+#[test]
+fn test_set_stream_description_emits_event() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let description = soroban_sdk::Bytes::from_array(&ctx.env, &[5u8; 8]);
+    ctx.client()
+        .set_stream_description(&stream_id, &description);
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &last.1.get(1).unwrap()),
+        soroban_sdk::Symbol::new(&ctx.env, "description_updated")
+    );
+    assert_eq!(
+        crate::StreamDescriptionUpdated::from_val(&ctx.env, &last.2),
+        crate::StreamDescriptionUpdated {
+            stream_id,
+            description,
+        }
+    );
+}
+
+#[test]
+fn test_set_stream_description_too_long_fails() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let description = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 257]);
+    let result = ctx
+        .client()
+        .try_set_stream_description(&stream_id, &description);
+    assert_eq!(result, Err(Ok(ContractError::InvalidParams)));
+    assert!(ctx.client().get_stream_description(&stream_id).is_empty());
+}
+
+#[test]
+fn test_set_stream_description_on_completed_stream_fails() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    let description = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 4]);
+    let result = ctx
+        .client()
+        .try_set_stream_description(&stream_id, &description);
+    assert_eq!(result, Err(Ok(ContractError::StreamCompleted)));
+
+    let other_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&other_id);
+    let result = ctx
+        .client()
+        .try_set_stream_description(&other_id, &description);
+    assert_eq!(result, Err(Ok(ContractError::StreamCancelled)));
+}
+
+#[test]
+#[should_panic]
+fn test_set_stream_description_by_recipient_panics() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_default_stream();
+    let description = soroban_sdk::Bytes::from_array(&ctx.env, &[1u8; 4]);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_stream_description",
+            args: (stream_id, description.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client()
+        .set_stream_description(&stream_id, &description);
+}
+
+#[test]
+fn test_get_stream_description_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_stream_description(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdraw_count
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdraw_count_starts_at_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 0);
+    assert_eq!(ctx.client().get_stream_state(&stream_id).withdraw_count, 0);
+}
+
+#[test]
+fn test_withdraw_count_increments_per_payout() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 1);
+
+    ctx.env.ledger().set_timestamp(300);
+    let destination = Address::generate(&ctx.env);
+    ctx.client().withdraw_to(&stream_id, &destination);
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 2);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.withdraw_count, 3);
+}
+
+#[test]
+fn test_withdraw_count_ignores_zero_withdrawals() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 0);
+
+    ctx.env.ledger().set_timestamp(600);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 1);
+}
+
+#[test]
+fn test_withdraw_count_unchanged_by_failed_withdraw() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert!(ctx.client().try_withdraw(&stream_id).is_err());
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 0);
+}
+
+#[test]
+fn test_get_withdrawal_count_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_withdrawal_count(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — last_withdrawal_time
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_last_withdrawal_time_none_before_first_withdrawal() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&stream_id); // before the cliff: pays nothing
+
+    assert_eq!(
+        ctx.client()
+            .get_stream_state(&stream_id)
+            .last_withdrawal_time,
+        None
+    );
+    assert_eq!(ctx.client().time_since_last_withdrawal(&stream_id), None);
+}
+
+#[test]
+fn test_last_withdrawal_time_tracks_latest_withdrawal() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(250);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client()
+            .get_stream_state(&stream_id)
+            .last_withdrawal_time,
+        Some(250)
+    );
+    assert_eq!(ctx.client().time_since_last_withdrawal(&stream_id), Some(0));
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(
+        ctx.client().time_since_last_withdrawal(&stream_id),
+        Some(150)
+    );
+
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(450);
+    assert_eq!(
+        ctx.client().time_since_last_withdrawal(&stream_id),
+        Some(50)
+    );
+}
+
+#[test]
+fn test_last_withdrawal_time_kept_after_completion() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(1300);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.last_withdrawal_time, Some(1000));
+    assert_eq!(
+        ctx.client().time_since_last_withdrawal(&stream_id),
+        Some(300)
+    );
+}
+
+#[test]
+fn test_time_since_last_withdrawal_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_time_since_last_withdrawal(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_streams_near_completion
+// ---------------------------------------------------------------------------
+
+/// Five 1000-second streams started at 0, 200, 400, 600 and 800; at t=1000 they
+/// are 100%, 80%, 60%, 40% and 20% complete.
+fn create_staggered_streams(ctx: &TestContext) -> Vec<u64> {
+    let mut ids = Vec::new(&ctx.env);
+    for start in [0u64, 200, 400, 600, 800] {
+        ctx.env.ledger().set_timestamp(start);
+        ids.push_back(ctx.client().create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &1000_i128,
+            &1_i128,
+            &start,
+            &start,
+            &(start + 1000),
+        ));
+    }
+    ctx.env.ledger().set_timestamp(1000);
+    ids
+}
+
+#[test]
+fn test_near_completion_returns_streams_over_threshold() {
+    let ctx = TestContext::setup();
+    let ids = create_staggered_streams(&ctx);
+
+    assert_eq!(
+        ctx.client().get_streams_near_completion(&5_000, &100),
+        soroban_sdk::vec![
+            &ctx.env,
+            ids.get(0).unwrap(),
+            ids.get(1).unwrap(),
+            ids.get(2).unwrap()
+        ]
+    );
+    assert_eq!(
+        ctx.client().get_streams_near_completion(&8_000, &100),
+        soroban_sdk::vec![&ctx.env, ids.get(0).unwrap(), ids.get(1).unwrap()]
+    );
+    assert_eq!(ctx.client().get_streams_near_completion(&0, &100), ids);
+}
+
+#[test]
+fn test_near_completion_skips_terminal_streams() {
+    let ctx = TestContext::setup();
+    let ids = create_staggered_streams(&ctx);
+    ctx.client().withdraw(&ids.get(0).unwrap());
+    ctx.client().cancel_stream(&ids.get(1).unwrap());
+
+    assert_eq!(
+        ctx.client().get_streams_near_completion(&5_000, &100),
+        soroban_sdk::vec![&ctx.env, ids.get(2).unwrap()]
+    );
+}
+
+#[test]
+fn test_near_completion_limited_to_max_scan() {
+    let ctx = TestContext::setup();
+    let ids = create_staggered_streams(&ctx);
+
+    assert_eq!(
+        ctx.client().get_streams_near_completion(&5_000, &2),
+        soroban_sdk::vec![&ctx.env, ids.get(0).unwrap(), ids.get(1).unwrap()]
+    );
+    assert!(ctx.client().get_streams_near_completion(&0, &0).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — sub-second rates (rate_denominator)
+// ---------------------------------------------------------------------------
+
+/// Create a stream paying `rate_per_second / rate_denominator` tokens per second
+/// over `[0, end_time]`.
+fn try_create_fractional_stream(
+    ctx: &TestContext,
+    deposit: i128,
+    rate_per_second: i128,
+    rate_denominator: u64,
+    end_time: u64,
+) -> Result<u64, ContractError> {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client()
+        .try_create_stream_with_options(
+            &ctx.sender,
+            &ctx.recipient,
+            &deposit,
+            &rate_per_second,
+            &0u64,
+            &0u64,
+            &end_time,
+            &crate::StreamOptions {
+                rate_denominator,
+                ..Default::default()
+            },
+        )
+        .map(|id| id.unwrap())
+        .map_err(|e| e.unwrap())
+}
+
+#[test]
+fn test_rate_denominator_accrues_one_token_per_ten_seconds() {
+    let ctx = TestContext::setup();
+    let stream_id = try_create_fractional_stream(&ctx, 10, 1, 10, 100).unwrap();
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).rate_denominator,
+        10
+    );
+
+    for (t, expected) in [(9u64, 0_i128), (10, 1), (55, 5), (99, 9), (100, 10)] {
+        ctx.env.ledger().set_timestamp(t);
+        assert_eq!(
+            ctx.client().calculate_accrued(&stream_id),
+            expected,
+            "t={t}"
+        );
+    }
+
+    assert_eq!(ctx.client().withdraw(&stream_id), 10);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_rate_denominator_default_is_one() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).rate_denominator,
+        1
+    );
+}
+
+#[test]
+fn test_rate_denominator_zero_rejected() {
+    let ctx = TestContext::setup();
+    let e = ContractError::InvalidParams;
+    assert_eq!(try_create_fractional_stream(&ctx, 10, 1, 0, 100), Err(e));
+}
+
+#[test]
+fn test_rate_denominator_deposit_must_cover_schedule() {
+    let ctx = TestContext::setup();
+    let e = ContractError::UnfundedDeposit;
+    assert_eq!(try_create_fractional_stream(&ctx, 9, 1, 10, 100), Err(e));
+    // 1/3 token per second over 100 seconds needs ceil(33.3) = 34 tokens.
+    assert_eq!(try_create_fractional_stream(&ctx, 33, 1, 3, 100), Err(e));
+    assert!(try_create_fractional_stream(&ctx, 34, 1, 3, 100).is_ok());
+}
+
+#[test]
+fn test_rate_denominator_applies_to_min_rate() {
+    let ctx = TestContext::setup();
+    ctx.client().set_min_rate(&1_i128);
+
+    let e = ContractError::InvalidParams;
+    assert_eq!(try_create_fractional_stream(&ctx, 10, 1, 10, 100), Err(e));
+    assert!(try_create_fractional_stream(&ctx, 100, 10, 10, 100).is_ok());
+}
+
+#[test]
+fn test_rate_denominator_topup_and_extension_use_effective_rate() {
+    let ctx = TestContext::setup();
+    let stream_id = try_create_fractional_stream(&ctx, 10, 1, 10, 100).unwrap();
+
+    // 5 tokens at 1/10 per second buy 50 more seconds.
+    ctx.client().topup_stream(&stream_id, &5_i128);
+    assert_eq!(ctx.client().get_stream_state(&stream_id).end_time, 150);
+
+    // 25 more seconds cost ceil(2.5) = 3 tokens.
+    let charged = ctx
+        .client()
+        .extend_stream_end_time(&stream_id, &175u64, &100_i128);
+    assert_eq!(charged, 3);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!((state.deposit_amount, state.end_time), (18, 175));
+}
+
+#[test]
+fn test_rate_denominator_next_unlock_skips_to_next_token() {
+    let ctx = TestContext::setup();
+    let stream_id = try_create_fractional_stream(&ctx, 10, 1, 10, 100).unwrap();
+
+    ctx.env.ledger().set_timestamp(12);
+    assert_eq!(ctx.client().get_next_unlock_time(&stream_id), 20);
+    assert_eq!(ctx.client().get_next_unlock_amount(&stream_id), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdrawal cooldown
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdrawal_cooldown_defaults_to_zero() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_config().withdrawal_cooldown, 0);
+
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(101);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1);
+}
+
+#[test]
+fn test_withdrawal_cooldown_enforced_between_withdrawals() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_withdrawal_cooldown(&60u64);
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+
+    ctx.env.ledger().set_timestamp(150);
+    let e = ContractError::WithdrawalCooldown;
+    assert_eq!(ctx.client().try_withdraw(&stream_id), Err(Ok(e)));
+    let destination = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.client().try_withdraw_to(&stream_id, &destination),
+        Err(Ok(e))
+    );
+
+    ctx.env.ledger().set_timestamp(160);
+    assert_eq!(ctx.client().withdraw(&stream_id), 60);
+    assert_eq!(ctx.client().get_withdrawal_count(&stream_id), 2);
+}
+
+#[test]
+fn test_withdrawal_cooldown_zero_disables_enforcement() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_withdrawal_cooldown(&60u64);
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+    ctx.client().set_withdrawal_cooldown(&0u64);
+
+    ctx.env.ledger().set_timestamp(110);
+    assert_eq!(ctx.client().withdraw(&stream_id), 10);
+}
+
+#[test]
+fn test_withdrawal_cooldown_skips_cancelled_streams() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_withdrawal_cooldown(&60u64);
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(120);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(ctx.client().withdraw(&stream_id), 20);
+}
+
+#[test]
+fn test_is_withdrawable_false_during_cooldown() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_withdrawal_cooldown(&60u64);
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(150);
+    assert!(ctx.client().get_withdrawable_amount(&stream_id) > 0);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(160);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+}
+
+#[test]
+fn test_withdrawal_cooldown_emits_config_event() {
+    let ctx = TestContext::setup();
+    ctx.client().set_withdrawal_cooldown(&30u64);
+
+    let events = ctx.env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &topics.get_unchecked(1)),
+        soroban_sdk::Symbol::new(&ctx.env, "cooldown")
+    );
+    assert_eq!(u64::from_val(&ctx.env, &data), 30);
+}
+
+#[test]
+#[should_panic]
+fn test_set_withdrawal_cooldown_requires_admin_auth() {
+    let ctx = TestContext::setup_strict();
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_withdrawal_cooldown",
+            args: (60_u64,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().set_withdrawal_cooldown(&60u64);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — min_withdraw_amount (dust threshold)
+// ---------------------------------------------------------------------------
+
+fn set_min_withdraw_amount(ctx: &TestContext, amount: i128) {
+    ctx.client()
+        .update_config(&None, &None, &None, &None, &Some(amount), &None);
+}
+
+#[test]
+fn test_dust_threshold_rejects_small_withdrawal() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+    assert_eq!(ctx.client().get_config().min_withdraw_amount, 100);
+
+    ctx.env.ledger().set_timestamp(50);
+    let e = ContractError::BelowDustThreshold;
+    assert_eq!(ctx.client().try_withdraw(&stream_id), Err(Ok(e)));
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+}
+
+#[test]
+fn test_dust_threshold_is_per_call() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+
+    ctx.env.ledger().set_timestamp(150);
+    assert_eq!(ctx.client().withdraw(&stream_id), 150);
+
+    // 50 more accrued: the earlier withdrawal does not count towards this one.
+    ctx.env.ledger().set_timestamp(200);
+    let e = ContractError::BelowDustThreshold;
+    assert_eq!(ctx.client().try_withdraw(&stream_id), Err(Ok(e)));
+}
+
+#[test]
+fn test_dust_threshold_zero_allows_any_amount() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+    set_min_withdraw_amount(&ctx, 0);
+
+    ctx.env.ledger().set_timestamp(1);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1);
+}
+
+#[test]
+fn test_dust_threshold_exempts_final_withdrawal() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+
+    ctx.env.ledger().set_timestamp(950);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 50);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_dust_threshold_exempts_cancelled_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(130);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(ctx.client().withdraw(&stream_id), 30);
+}
+
+#[test]
+fn test_is_withdrawable_respects_dust_threshold() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+
+    ctx.env.ledger().set_timestamp(50);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 50);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(99);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(100);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+}
+
+#[test]
+fn test_is_withdrawable_exempts_final_withdrawal_from_dust_threshold() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    set_min_withdraw_amount(&ctx, 100);
+
+    ctx.env.ledger().set_timestamp(950);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(990);
+    assert!(!ctx.client().is_withdrawable(&stream_id));
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert!(ctx.client().is_withdrawable(&stream_id));
+    assert_eq!(ctx.client().withdraw(&stream_id), 50);
+}
+
+#[test]
+fn test_dust_threshold_negative_rejected() {
+    let ctx = TestContext::setup();
+    let e = ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client()
+            .try_update_config(&None, &None, &None, &None, &Some(-1_i128), &None),
+        Err(Ok(e))
+    );
+    assert_eq!(ctx.client().get_config().min_withdraw_amount, 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — StreamOptions.recipient_must_accept
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_options_default_does_not_require_acceptance() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        offer_expiry: 500,
+        ..Default::default()
+    });
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.pending_expiry, None);
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 100);
+}
+
+#[test]
+fn test_options_recipient_must_accept_then_accept() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_must_accept: true,
+        offer_expiry: 500,
+        ..Default::default()
+    });
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Pending);
+    assert_eq!(state.pending_expiry, Some(500));
+
+    ctx.env.ledger().set_timestamp(100);
+    let e = ContractError::StreamPending;
+    assert_eq!(ctx.client().try_withdraw(&stream_id), Err(Ok(e)));
+
+    ctx.client().accept_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 200);
+}
+
+#[test]
+fn test_options_recipient_must_accept_expiry_refunds_sender() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_must_accept: true,
+        offer_expiry: 500,
+        ..Default::default()
+    });
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.env.ledger().set_timestamp(501);
+    let e = ContractError::PendingExpired;
+    assert_eq!(ctx.client().try_accept_stream(&stream_id), Err(Ok(e)));
+
+    ctx.client().claim_expired_pending(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before + 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_options_recipient_must_accept_reject_refunds_sender() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_must_accept: true,
+        offer_expiry: 500,
+        ..Default::default()
+    });
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.client().reject_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before + 1000);
+
+    let e = ContractError::InvalidState;
+    assert_eq!(ctx.client().try_accept_stream(&stream_id), Err(Ok(e)));
+}
+
+#[test]
+fn test_options_recipient_must_accept_rejects_past_expiry() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(100);
+    let result = ctx.client().try_create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &100u64,
+        &1100u64,
+        &crate::StreamOptions {
+            recipient_must_accept: true,
+            offer_expiry: 100,
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidParams)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_stream_ids_for_recipient
+// ---------------------------------------------------------------------------
+
+/// Five active default streams interleaved with three 100-unit streams that
+/// complete at t=100. Leaves the ledger at t=100.
+fn create_mixed_recipient_streams(ctx: &TestContext) -> (Vec<u64>, Vec<u64>) {
+    let mut active = Vec::new(&ctx.env);
+    let mut completed = Vec::new(&ctx.env);
+    for i in 0..8u32 {
+        if i % 3 == 1 {
+            completed.push_back(ctx.client().create_stream(
+                &ctx.sender,
+                &ctx.recipient,
+                &100_i128,
+                &1_i128,
+                &0u64,
+                &0u64,
+                &100u64,
+            ));
+        } else {
+            active.push_back(ctx.create_default_stream());
+        }
+    }
+    ctx.env.ledger().set_timestamp(100);
+    for id in completed.iter() {
+        ctx.client().withdraw(&id);
+    }
+    (active, completed)
+}
+
+#[test]
+fn test_recipient_streams_paginated_filters_by_status() {
+    let ctx = TestContext::setup();
+    let (active, completed) = create_mixed_recipient_streams(&ctx);
+    assert_eq!(active.len(), 5);
+
+    let client = ctx.client();
+    let page =
+        client.get_stream_ids_for_recipient(&ctx.recipient, &0, &100, &Some(StreamStatus::Active));
+    assert_eq!(page, active);
+
+    let page = client.get_stream_ids_for_recipient(
+        &ctx.recipient,
+        &0,
+        &100,
+        &Some(StreamStatus::Completed),
+    );
+    assert_eq!(page, completed);
+
+    let all = client.get_stream_ids_for_recipient(&ctx.recipient, &0, &100, &None);
+    assert_eq!(all, client.get_streams_by_recipient(&ctx.recipient));
+    assert_eq!(all.len(), 8);
+
+    let page =
+        client.get_stream_ids_for_recipient(&ctx.recipient, &0, &100, &Some(StreamStatus::Paused));
+    assert!(page.is_empty());
+}
+
+#[test]
+fn test_recipient_streams_paginated_pages() {
+    let ctx = TestContext::setup();
+    let (active, _) = create_mixed_recipient_streams(&ctx);
+    let client = ctx.client();
+    let filter = Some(StreamStatus::Active);
+
+    let first = client.get_stream_ids_for_recipient(&ctx.recipient, &0, &2, &filter);
+    let second = client.get_stream_ids_for_recipient(&ctx.recipient, &2, &2, &filter);
+    let third = client.get_stream_ids_for_recipient(&ctx.recipient, &4, &2, &filter);
+    let past_end = client.get_stream_ids_for_recipient(&ctx.recipient, &5, &2, &filter);
+
+    assert_eq!(first, active.slice(0..2));
+    assert_eq!(second, active.slice(2..4));
+    assert_eq!(third, active.slice(4..5));
+    assert!(past_end.is_empty());
+
+    let unknown = Address::generate(&ctx.env);
+    assert!(client
+        .get_stream_ids_for_recipient(&unknown, &0, &10, &None)
+        .is_empty());
+}
+
+#[test]
+fn test_recipient_streams_paginated_clamps_limit() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &200_000_i128);
+    for _ in 0..(crate::MAX_RECIPIENT_PAGE_SIZE + 3) {
+        // Each create is its own transaction on-chain, with its own budget.
+        ctx.env.budget().reset_default();
+        ctx.create_default_stream();
+    }
+
+    let page = ctx
+        .client()
+        .get_stream_ids_for_recipient(&ctx.recipient, &0, &u32::MAX, &None);
+    assert_eq!(page.len(), crate::MAX_RECIPIENT_PAGE_SIZE);
+
+    let rest = ctx.client().get_stream_ids_for_recipient(
+        &ctx.recipient,
+        &crate::MAX_RECIPIENT_PAGE_SIZE,
+        &u32::MAX,
+        &None,
+    );
+    assert_eq!(rest.len(), 3);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — expire_stream
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_expiry_time_uses_grace_period() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(
+        ctx.client().get_config().expiry_grace_period,
+        crate::DEFAULT_EXPIRY_GRACE_PERIOD
+    );
+    assert_eq!(
+        ctx.client().get_expiry_time(&stream_id),
+        1000 + crate::DEFAULT_EXPIRY_GRACE_PERIOD
+    );
+
+    ctx.client().set_expiry_grace_period(&60);
+    assert_eq!(ctx.client().get_expiry_time(&stream_id), 1060);
+}
+
+#[test]
+fn test_expire_stream_before_grace_period_fails() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_expiry_grace_period(&60);
+
+    ctx.env.ledger().set_timestamp(1059);
+    let e = ContractError::InvalidState;
+    assert_eq!(ctx.client().try_expire_stream(&stream_id), Err(Ok(e)));
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_expire_stream_pays_recipient_remainder() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().set_expiry_grace_period(&60);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.env.ledger().set_timestamp(1060);
+    ctx.client().expire_stream(&stream_id);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Expired);
+    assert_eq!(state.withdrawn_amount, 1000);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+    assert_eq!(ctx.client().get_total_value_locked(), 0);
+    assert_eq!(ctx.client().get_protocol_stats().completed_count, 1);
+
+    let e = ContractError::StreamExpired;
+    assert_eq!(ctx.client().try_withdraw(&stream_id), Err(Ok(e)));
+    assert_eq!(ctx.client().try_expire_stream(&stream_id), Err(Ok(e)));
+    let e = ContractError::InvalidState;
+    assert_eq!(ctx.client().try_cancel_stream(&stream_id), Err(Ok(e)));
+}
+
+#[test]
+fn test_expire_stream_rejects_paused_and_completed() {
+    let ctx = TestContext::setup();
+    ctx.client().set_expiry_grace_period(&0);
+    let paused_id = ctx.create_default_stream();
+    let completed_id = ctx.create_default_stream();
+    ctx.client().pause_stream(&paused_id);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&completed_id);
+
+    let e = ContractError::StreamPaused;
+    assert_eq!(ctx.client().try_expire_stream(&paused_id), Err(Ok(e)));
+    let e = ContractError::StreamCompleted;
+    assert_eq!(ctx.client().try_expire_stream(&completed_id), Err(Ok(e)));
+}
+
+#[test]
+fn test_expire_stream_waits_for_accrual_freeze_pauses() {
+    let ctx = TestContext::setup();
+    ctx.client().set_expiry_grace_period(&0);
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        pause_mode: crate::PauseMode::AccrualFreeze,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().resume_stream(&stream_id);
+    assert_eq!(ctx.client().get_expiry_time(&stream_id), 1100);
+
+    ctx.env.ledger().set_timestamp(1050);
+    let e = ContractError::InvalidState;
+    assert_eq!(ctx.client().try_expire_stream(&stream_id), Err(Ok(e)));
+
+    ctx.env.ledger().set_timestamp(1100);
+    ctx.client().expire_stream(&stream_id);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — bump_sender_streams_ttl / bump_recipient_streams_ttl
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_bump_sender_streams_ttl_extends_every_stream() {
+    let ctx = TestContext::setup();
+    let ids = create_default_streams(&ctx, 3);
+    let seq = ctx.env.ledger().sequence();
+
+    assert_eq!(
+        ctx.client().bump_sender_streams_ttl(&ctx.sender, &500_000),
+        3
+    );
+    for id in ids.iter() {
+        assert_eq!(stream_ttl(&ctx, id), 500_000);
+    }
+
+    ctx.env.ledger().set_sequence_number(seq + 200_000);
+    assert_eq!(ctx.client().get_streams_by_sender(&ctx.sender), ids);
+    for id in ids.iter() {
+        assert_eq!(ctx.client().get_stream_state(&id).stream_id, id);
+    }
+}
+
+#[test]
+fn test_bump_recipient_streams_ttl_extends_every_stream() {
+    let ctx = TestContext::setup();
+    let ids = create_default_streams(&ctx, 2);
+
+    let bumped = ctx
+        .client()
+        .bump_recipient_streams_ttl(&ctx.recipient, &400_000);
+    assert_eq!(bumped, 2);
+    for id in ids.iter() {
+        assert_eq!(stream_ttl(&ctx, id), 400_000);
+    }
+
+    // The recipient index does not include the sender's streams to others.
+    let other = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().bump_recipient_streams_ttl(&other, &400_000), 0);
+}
+
+#[test]
+fn test_bump_streams_ttl_rejections() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+
+    let e = crate::ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client().try_bump_sender_streams_ttl(&ctx.sender, &0),
+        Err(Ok(e))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_bump_recipient_streams_ttl(&ctx.recipient, &u32::MAX),
+        Err(Ok(e))
+    );
+}
+
+#[test]
+fn test_bump_sender_streams_ttl_is_capped_and_fits_budget() {
+    let ctx = TestContext::setup();
+    ctx.sac.mint(&ctx.sender, &100_000_i128);
+    let ids = create_default_streams(&ctx, crate::MAX_TTL_BUMP_BATCH + 2);
+
+    ctx.env.budget().reset_default();
+    let bumped = ctx.client().bump_sender_streams_ttl(&ctx.sender, &500_000);
+    assert!(ctx.env.budget().cpu_instruction_cost() <= 5_000_000);
+    assert!(ctx.env.budget().memory_bytes_cost() <= 1_500_000);
+
+    assert_eq!(bumped, crate::MAX_TTL_BUMP_BATCH);
+    let last_bumped = ids.get_unchecked(crate::MAX_TTL_BUMP_BATCH - 1);
+    assert_eq!(stream_ttl(&ctx, last_bumped), 500_000);
+    assert!(stream_ttl(&ctx, ids.last_unchecked()) < 500_000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — admin_set_token (token migration)
+// ---------------------------------------------------------------------------
+
+/// Register a second SAC token and mint 10_000 of it to the sender.
+fn register_second_token(ctx: &TestContext) -> Address {
+    let token_id = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    StellarAssetClient::new(&ctx.env, &token_id).mint(&ctx.sender, &10_000_i128);
+    token_id
+}
+
+#[test]
+fn test_admin_set_token_keeps_old_streams_on_old_token() {
+    let ctx = TestContext::setup();
+    let old_id = ctx.create_default_stream();
+    let token_b = register_second_token(&ctx);
+    let client_b = TokenClient::new(&ctx.env, &token_b);
+    assert_eq!(ctx.client().get_config_version(), 0);
+
+    ctx.client().admin_set_token(&token_b);
+    assert_eq!(ctx.client().get_config().token, token_b);
+    assert_eq!(ctx.client().get_config_version(), 1);
+
+    let new_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_stream_state(&old_id).token, ctx.token_id);
+    assert_eq!(ctx.client().get_stream_state(&new_id).token, token_b);
+    assert_eq!(client_b.balance(&ctx.sender), 9_000);
+    assert_eq!(client_b.balance(&ctx.contract_id), 1_000);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&old_id);
+    ctx.client().withdraw(&new_id);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(client_b.balance(&ctx.recipient), 300);
+
+    // Cancelling the old stream refunds in the old token.
+    ctx.client().cancel_stream(&old_id);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_700);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_batch_withdraw_across_tokens_pays_each_token() {
+    let ctx = TestContext::setup();
+    let old_id = ctx.create_default_stream();
+    let token_b = register_second_token(&ctx);
+    ctx.client().admin_set_token(&token_b);
+    let new_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(400);
+    let ids = soroban_sdk::vec![&ctx.env, old_id, new_id];
+    ctx.client().batch_withdraw(&ids);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &token_b).balance(&ctx.recipient),
+        400
+    );
+
+    let results = ctx.client().cancel_stream_batch(&ids);
+    assert_eq!(results.len(), 2);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_600);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &token_b).balance(&ctx.sender),
+        9_600
+    );
+}
+
+#[test]
+fn test_admin_set_token_rejections() {
+    let ctx = TestContext::setup();
+    let e = ContractError::InvalidParams;
+    assert_eq!(ctx.client().try_admin_set_token(&ctx.token_id), Err(Ok(e)));
+
+    ctx.client().set_fee_bps(&100);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2_000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    let token_b = register_second_token(&ctx);
+    let e = ContractError::InvalidState;
+    assert_eq!(ctx.client().try_admin_set_token(&token_b), Err(Ok(e)));
+
+    ctx.client().withdraw_protocol_fees(&ctx.admin);
+    ctx.client().admin_set_token(&token_b);
+    assert_eq!(ctx.client().get_config_version(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_set_token_requires_admin_auth() {
+    let ctx = TestContext::setup_strict();
+    let token_b = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "admin_set_token",
+            args: (token_b.clone(),).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().admin_set_token(&token_b);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — has_cliff / is_past_cliff / cliff_unlock_remaining
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cliff_views_without_cliff() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert!(!ctx.client().has_cliff(&stream_id));
+    assert!(ctx.client().is_past_cliff(&stream_id));
+    assert_eq!(ctx.client().cliff_unlock_remaining(&stream_id), 0);
+}
+
+#[test]
+fn test_cliff_views_track_ledger_time() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_cliff_stream();
+    assert!(ctx.client().has_cliff(&stream_id));
+
+    // Cliff in the future.
+    ctx.env.ledger().set_timestamp(200);
+    assert!(!ctx.client().is_past_cliff(&stream_id));
+    assert_eq!(ctx.client().cliff_unlock_remaining(&stream_id), 300);
+
+    // Cliff now.
+    ctx.env.ledger().set_timestamp(500);
+    assert!(ctx.client().is_past_cliff(&stream_id));
+    assert_eq!(ctx.client().cliff_unlock_remaining(&stream_id), 0);
+
+    // Cliff past.
+    ctx.env.ledger().set_timestamp(800);
+    assert!(ctx.client().is_past_cliff(&stream_id));
+    assert_eq!(ctx.client().cliff_unlock_remaining(&stream_id), 0);
+    assert!(ctx.client().has_cliff(&stream_id));
+}
+
+#[test]
+fn test_cliff_views_unknown_stream() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_has_cliff(&7), Err(Ok(e)));
+    assert_eq!(ctx.client().try_is_past_cliff(&7), Err(Ok(e)));
+    assert_eq!(ctx.client().try_cliff_unlock_remaining(&7), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — Config.allow_excess_deposit
+// ---------------------------------------------------------------------------
+
+/// 1500 tokens deposited for a 1000-token schedule over 0..1000.
+fn create_excess_stream(ctx: &TestContext) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1500_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    )
+}
+
+#[test]
+fn test_excess_deposit_accrues_normally_and_refunds_at_completion() {
+    let ctx = TestContext::setup();
+    ctx.client().set_allow_excess_deposit(&true);
+    assert!(ctx.client().get_config().allow_excess_deposit);
+    let stream_id = create_excess_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 400);
+    assert_eq!(ctx.client().withdraw(&stream_id), 400);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_500);
+
+    ctx.env.ledger().set_timestamp(1200);
+    assert_eq!(ctx.client().withdraw(&stream_id), 600);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().get_total_value_locked(), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(state.withdrawn_amount, 1000);
+}
+
+#[test]
+fn test_excess_deposit_stays_locked_when_disabled() {
+    let ctx = TestContext::setup();
+    let stream_id = create_excess_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(1200);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 500);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_excess_deposit_flag_leaves_exact_streams_unaffected() {
+    let ctx = TestContext::setup();
+    ctx.client().set_allow_excess_deposit(&true);
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.deposit_amount, 1000);
+}
+
+#[test]
+fn test_excess_deposit_not_refunded_before_end() {
+    let ctx = TestContext::setup();
+    ctx.client().set_allow_excess_deposit(&true);
+    let stream_id = create_excess_stream(&ctx);
+
+    ctx.env.ledger().set_timestamp(999);
+    assert_eq!(ctx.client().withdraw(&stream_id), 999);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_500);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdrawal history
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdrawal_history_accumulates_in_order() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(ctx.client().get_withdrawal_history(&stream_id).is_empty());
+
+    for t in [100u64, 250, 600] {
+        ctx.env.ledger().set_timestamp(t);
+        ctx.client().withdraw(&stream_id);
+    }
+    // A call that pays nothing is not recorded.
+    ctx.client().withdraw(&stream_id);
+
+    let history = ctx.client().get_withdrawal_history(&stream_id);
+    assert_eq!(history.len(), 3);
+    let expected = [(100u64, 100_i128, 1u32), (250, 150, 2), (600, 350, 3)];
+    for (record, (timestamp, amount, count)) in history.iter().zip(expected) {
+        assert_eq!(record.timestamp, timestamp);
+        assert_eq!(record.amount, amount);
+        assert_eq!(record.recipient, ctx.recipient);
+        assert_eq!(record.withdraw_count, count);
+    }
+    assert!(!ctx.client().is_withdrawal_history_truncated(&stream_id));
+}
+
+#[test]
+fn test_withdrawal_history_records_withdraw_to_destination() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let destination = Address::generate(&ctx.env);
+
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw_to(&stream_id, &destination);
+    ctx.env.ledger().set_timestamp(500);
+    ctx.client()
+        .batch_withdraw(&soroban_sdk::vec![&ctx.env, stream_id]);
+
+    let history = ctx.client().get_withdrawal_history(&stream_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get_unchecked(0).recipient, destination);
+    assert_eq!(history.get_unchecked(0).amount, 300);
+    assert_eq!(history.get_unchecked(1).recipient, ctx.recipient);
+    assert_eq!(history.get_unchecked(1).amount, 200);
+}
+
+#[test]
+fn test_withdrawal_history_truncation_drops_oldest() {
+    let ctx = TestContext::setup();
+    ctx.env.budget().reset_unlimited();
+    let stream_id = ctx.create_default_stream();
+    let total = crate::MAX_WITHDRAWAL_HISTORY as u64 + 2;
+
+    for t in 1..=total {
+        ctx.env.ledger().set_timestamp(t);
+        ctx.client().withdraw(&stream_id);
+    }
+
+    let history = ctx.client().get_withdrawal_history(&stream_id);
+    assert_eq!(history.len(), crate::MAX_WITHDRAWAL_HISTORY);
+    assert_eq!(history.first_unchecked().timestamp, 3);
+    assert_eq!(history.first_unchecked().withdraw_count, 3);
+    assert_eq!(history.last_unchecked().timestamp, total);
+    assert!(ctx.client().is_withdrawal_history_truncated(&stream_id));
+}
+
+#[test]
+fn test_withdrawal_history_unknown_stream() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_withdrawal_history(&3), Err(Ok(e)));
+    assert_eq!(
+        ctx.client().try_is_withdrawal_history_truncated(&3),
+        Err(Ok(e))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — StreamOptions.cancel_after
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cancel_before_deadline_succeeds() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_after: 400,
+        ..Default::default()
+    });
+    assert_eq!(ctx.client().get_cancel_deadline(&stream_id), Some(400));
+
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (600, 400));
+}
+
+#[test]
+fn test_cancel_after_deadline_fails() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_after: 400,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(401);
+    let e = ContractError::CancelDeadlinePassed;
+    assert_eq!(ctx.client().try_cancel_stream(&stream_id), Err(Ok(e)));
+    let ids = soroban_sdk::vec![&ctx.env, stream_id];
+    assert_eq!(ctx.client().try_cancel_stream_batch(&ids), Err(Ok(e)));
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_cancel_without_deadline_always_allowed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_cancel_deadline(&stream_id), None);
+
+    ctx.env.ledger().set_timestamp(999);
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (1, 999));
+}
+
+#[test]
+fn test_admin_cancel_bypasses_deadline() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_stream_with(crate::StreamOptions {
+        cancel_after: 400,
+        ..Default::default()
+    });
+    let second = ctx.create_stream_with(crate::StreamOptions {
+        cancel_after: 400,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(700);
+    assert_eq!(ctx.client().cancel_stream_as_admin(&first), (300, 700));
+    assert_eq!(ctx.client().admin_cancel_stream(&second, &1), (300, 700));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — create_swap_stream
+// ---------------------------------------------------------------------------
+
+/// Swap where the sender pays the recipient 1000 and the recipient pays back 500,
+/// both over 0–1000.
+fn create_default_swap(ctx: &TestContext) -> (u64, u64) {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.sac.mint(&ctx.recipient, &500_i128);
+    let params_a = CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 1000,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+    };
+    let params_b = CreateStreamParams {
+        recipient: ctx.sender.clone(),
+        deposit_amount: 500,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 500,
+    };
+    ctx.client()
+        .create_swap_stream(&ctx.sender, &ctx.recipient, &params_a, &params_b)
+}
+
+#[test]
+fn test_create_swap_stream_links_both_streams() {
+    let ctx = TestContext::setup();
+    let (a, b) = create_default_swap(&ctx);
+
+    assert_eq!(ctx.client().get_linked_stream_id(&a), Some(b));
+    assert_eq!(ctx.client().get_linked_stream_id(&b), Some(a));
+    let stream_a = ctx.client().get_stream_state(&a);
+    let stream_b = ctx.client().get_stream_state(&b);
+    assert_eq!(stream_a.sender, ctx.sender);
+    assert_eq!(stream_b.sender, ctx.recipient);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1500);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+}
+
+#[test]
+fn test_cancel_swap_stream_cancels_partner() {
+    let ctx = TestContext::setup();
+    let (a, b) = create_default_swap(&ctx);
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().cancel_stream(&a);
+
+    let stream_b = ctx.client().get_stream_state(&b);
+    assert_eq!(stream_b.status, StreamStatus::Cancelled);
+    assert_eq!(stream_b.cancelled_at, Some(200));
+    // Party B got its 300 unaccrued tokens back; both recipients keep accruals.
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 400);
+}
+
+#[test]
+fn test_swap_partner_cancel_via_recipient_path() {
+    let ctx = TestContext::setup();
+    let (a, b) = create_default_swap(&ctx);
+
+    // The sender is the recipient of stream B and cancels it from that side.
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().cancel_stream_as_recipient(&b);
+    assert_eq!(
+        ctx.client().get_stream_state(&a).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_swap_batch_cancel_cancels_partner() {
+    let ctx = TestContext::setup();
+    let (a, b) = create_default_swap(&ctx);
+
+    let ids = soroban_sdk::vec![&ctx.env, a];
+    ctx.client().cancel_stream_batch(&ids);
+    assert_eq!(
+        ctx.client().get_stream_state(&b).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_create_swap_stream_rejects_mismatched_parties() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let params = CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 100,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 100,
+    };
+    let e = ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client()
+            .try_create_swap_stream(&ctx.sender, &ctx.recipient, &params, &params),
+        Err(Ok(e))
+    );
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_unlinked_stream_has_no_partner() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_linked_stream_id(&stream_id), None);
+    ctx.client().cancel_stream(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — freeze_contract
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_freeze_contract_sets_flag_and_emits_event() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.client().is_frozen());
+
+    ctx.client().freeze_contract();
+    assert!(ctx.client().is_frozen());
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::try_from_val(&ctx.env, &last.1.get(1).unwrap()).unwrap(),
+        soroban_sdk::symbol_short!("frozen")
+    );
+}
+
+#[test]
+fn test_frozen_contract_rejects_config_mutations() {
+    let ctx = TestContext::setup();
+    ctx.client().freeze_contract();
+    let other = Address::generate(&ctx.env);
+    let e = Err(Ok(ContractError::ContractFrozen));
+
+    let client = ctx.client();
+    assert_eq!(
+        client.try_update_config(&Some(1), &None, &None, &None, &None, &None),
+        e
+    );
+    assert_eq!(client.try_set_fee_bps(&1), e);
+    assert_eq!(client.try_set_fee_on_withdrawal_bps(&1), e);
+    assert_eq!(client.try_set_min_deposit(&1), e);
+    assert_eq!(client.try_set_min_rate(&1), e);
+    assert_eq!(client.try_set_min_ttl_ledgers(&1), e);
+    assert_eq!(client.try_set_withdrawal_cooldown(&1), e);
+    assert_eq!(client.try_set_expiry_grace_period(&1), e);
+    assert_eq!(client.try_set_recovery_address(&Some(other.clone())), e);
+    assert_eq!(client.try_set_recovery_grace_period(&1), e);
+    assert_eq!(client.try_set_whitelist_enabled(&true), e);
+    assert_eq!(client.try_set_strict_deposit_mode(&true), e);
+    assert_eq!(client.try_add_to_sender_whitelist(&other), e);
+    assert_eq!(client.try_remove_from_sender_whitelist(&other), e);
+    assert_eq!(client.try_set_recipient_cancel_allowed(&false), e);
+    assert_eq!(client.try_set_allow_excess_deposit(&true), e);
+    assert_eq!(client.try_admin_set_token(&other), e);
+    assert_eq!(client.try_set_admin(&other), e);
+    assert_eq!(client.try_propose_admin(&other), e);
+    assert_eq!(client.try_admin_pause_protocol(), e);
+    assert_eq!(client.get_config().admin, ctx.admin);
+}
+
+#[test]
+fn test_frozen_contract_blocks_pending_admin_acceptance() {
+    let ctx = TestContext::setup();
+    let new_admin = Address::generate(&ctx.env);
+    ctx.client().propose_admin(&new_admin);
+    ctx.client().freeze_contract();
+
+    let e = ContractError::ContractFrozen;
+    assert_eq!(ctx.client().try_accept_admin(), Err(Ok(e)));
+    assert_eq!(ctx.client().try_cancel_admin_proposal(), Err(Ok(e)));
+}
+
+#[test]
+fn test_frozen_contract_allows_stream_operations() {
+    let ctx = TestContext::setup();
+    ctx.client().freeze_contract();
+
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    ctx.client().pause_stream(&stream_id);
+    ctx.client().resume_stream(&stream_id);
+    ctx.client().cancel_stream_as_admin(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_freeze_contract_is_one_way() {
+    let ctx = TestContext::setup();
+    ctx.client().freeze_contract();
+
+    let e = ContractError::ContractFrozen;
+    assert_eq!(ctx.client().try_freeze_contract(), Err(Ok(e)));
+    assert!(ctx.client().is_frozen());
+}
+
+#[test]
+fn test_freeze_contract_rejected_while_protocol_paused() {
+    let ctx = TestContext::setup();
+    ctx.client().admin_pause_protocol();
+
+    let e = ContractError::ProtocolPaused;
+    assert_eq!(ctx.client().try_freeze_contract(), Err(Ok(e)));
+    assert!(!ctx.client().is_frozen());
+}
+
+#[test]
+#[should_panic]
+fn test_freeze_contract_requires_admin_auth() {
+    let ctx = TestContext::setup_strict();
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "freeze_contract",
+            args: ().into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().freeze_contract();
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_amount_remaining / get_amount_streaming
+// ---------------------------------------------------------------------------
+
+/// Assert `withdrawn + streaming + accrued_not_withdrawn == deposit` and that
+/// `remaining` is the last two terms; both views are 0 on a cancelled stream.
+fn assert_amounts_consistent(ctx: &TestContext, stream_id: u64) {
+    let state = ctx.client().get_stream_state(&stream_id);
+    let accrued = ctx.client().calculate_accrued(&stream_id);
+    let streaming = ctx.client().get_amount_streaming(&stream_id);
+    let remaining = ctx.client().get_amount_remaining(&stream_id);
+    let unclaimed = accrued - state.withdrawn_amount;
+    if state.status == StreamStatus::Cancelled {
+        assert_eq!((remaining, streaming), (0, 0));
+    } else {
+        assert_eq!(remaining, streaming + unclaimed);
+        assert_eq!(
+            state.withdrawn_amount + streaming + unclaimed,
+            state.deposit_amount
+        );
+    }
+}
+
+#[test]
+fn test_amounts_active_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_amount_remaining(&stream_id), 1000);
+    assert_eq!(ctx.client().get_amount_streaming(&stream_id), 1000);
+
+    for t in [0u64, 250, 400, 999] {
+        ctx.env.ledger().set_timestamp(t);
+        assert_amounts_consistent(&ctx, stream_id);
+        if t == 400 {
+            ctx.client().withdraw(&stream_id);
+            assert_eq!(ctx.client().get_amount_remaining(&stream_id), 600);
+            assert_eq!(ctx.client().get_amount_streaming(&stream_id), 600);
+            assert_amounts_consistent(&ctx, stream_id);
+        }
+    }
+}
+
+#[test]
+fn test_amounts_paused_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().pause_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().get_amount_remaining(&stream_id), 1000);
+    assert_eq!(ctx.client().get_amount_streaming(&stream_id), 500);
+    assert_amounts_consistent(&ctx, stream_id);
+}
+
+#[test]
+fn test_amounts_completed_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    assert_eq!(ctx.client().get_amount_remaining(&stream_id), 0);
+    assert_eq!(ctx.client().get_amount_streaming(&stream_id), 0);
+    assert_amounts_consistent(&ctx, stream_id);
+}
+
+#[test]
+fn test_amounts_cancelled_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(700);
+    ctx.client().cancel_stream(&stream_id);
+
+    // Both views report 0; the frozen accrual is still claimable.
+    assert_eq!(ctx.client().get_amount_streaming(&stream_id), 0);
+    assert_eq!(ctx.client().get_amount_remaining(&stream_id), 0);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 400);
+    assert_amounts_consistent(&ctx, stream_id);
+
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_amount_remaining(&stream_id), 0);
+}
+
+#[test]
+fn test_amounts_stream_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_amount_remaining(&99), Err(Ok(e)));
+    assert_eq!(ctx.client().try_get_amount_streaming(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_status_history
+// ---------------------------------------------------------------------------
+
+fn status_history(ctx: &TestContext, stream_id: u64) -> std::vec::Vec<(u64, StreamStatus)> {
+    ctx.client().get_status_history(&stream_id).iter().collect()
+}
+
+#[test]
+fn test_status_history_starts_with_creation_status() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(50);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &100u64,
+        &1100u64,
+    );
+    assert_eq!(
+        status_history(&ctx, stream_id),
+        [(50, StreamStatus::Scheduled)]
+    );
+
+    ctx.env.ledger().set_timestamp(150);
+    ctx.client().activate_stream(&stream_id);
+    assert_eq!(
+        status_history(&ctx, stream_id),
+        [(50, StreamStatus::Scheduled), (150, StreamStatus::Active)]
+    );
+}
+
+#[test]
+fn test_status_history_full_lifecycle() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().resume_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    let history = status_history(&ctx, stream_id);
+    assert_eq!(
+        history,
+        [
+            (0, StreamStatus::Active),
+            (200, StreamStatus::Paused),
+            (300, StreamStatus::Active),
+            (1000, StreamStatus::Completed),
+        ]
+    );
+    assert!(history.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+}
+
+#[test]
+fn test_status_history_cancelled() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(
+        status_history(&ctx, stream_id),
+        [(0, StreamStatus::Active), (400, StreamStatus::Cancelled)]
+    );
+}
+
+#[test]
+fn test_status_history_capped() {
+    let ctx = TestContext::setup();
+    ctx.env.budget().reset_unlimited();
+    let stream_id = ctx.create_default_stream();
+
+    for t in 1..=20u64 {
+        ctx.env.ledger().set_timestamp(t);
+        if t % 2 == 1 {
+            ctx.client().pause_stream(&stream_id);
+        } else {
+            ctx.client().resume_stream(&stream_id);
+        }
+    }
+
+    let history = status_history(&ctx, stream_id);
+    assert_eq!(history.len() as u32, crate::MAX_STATUS_HISTORY);
+    // The creation entry was dropped; the last 20 transitions remain.
+    assert_eq!(history[0], (1, StreamStatus::Paused));
+    assert_eq!(history[19], (20, StreamStatus::Active));
+    assert!(history.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+}
+
+#[test]
+fn test_status_history_stream_not_found() {
+    let ctx = TestContext::setup();
+    let e = ContractError::StreamNotFound;
+    assert_eq!(ctx.client().try_get_status_history(&99), Err(Ok(e)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — StreamOptions.strict_deposit
+// ---------------------------------------------------------------------------
+
+/// 1000 tokens deposited for a 300-token schedule (3/s over 0..100).
+fn create_flexible_deposit_stream(ctx: &TestContext, strict_deposit: bool) -> u64 {
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &3_i128,
+        &0u64,
+        &0u64,
+        &100u64,
+        &crate::StreamOptions {
+            strict_deposit,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_non_strict_deposit_refunds_excess_at_completion() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.client().get_config().allow_excess_deposit);
+    let stream_id = create_flexible_deposit_stream(&ctx, false);
+    assert!(!ctx.client().get_stream_state(&stream_id).strict_deposit);
+
+    ctx.env.ledger().set_timestamp(50);
+    assert_eq!(ctx.client().withdraw(&stream_id), 150);
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 150);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_700);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.deposit_amount, 300);
+}
+
+#[test]
+fn test_strict_deposit_keeps_excess_locked() {
+    let ctx = TestContext::setup();
+    let stream_id = create_flexible_deposit_stream(&ctx, true);
+    assert!(ctx.client().get_stream_state(&stream_id).strict_deposit);
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 700);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+fn test_strict_deposit_follows_config_flag() {
+    let ctx = TestContext::setup();
+    ctx.client().set_allow_excess_deposit(&true);
+    let stream_id = create_flexible_deposit_stream(&ctx, true);
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_700);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_non_strict_deposit_with_exact_funding_unaffected() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &300_i128,
+        &3_i128,
+        &0u64,
+        &0u64,
+        &100u64,
+        &crate::StreamOptions {
+            strict_deposit: false,
+            ..Default::default()
+        },
+    );
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_700);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.deposit_amount, 300);
+}
+
+#[test]
+fn test_non_strict_deposit_still_rejects_underfunding() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let result = ctx.client().try_create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &200_i128,
+        &3_i128,
+        &0u64,
+        &0u64,
+        &100u64,
+        &crate::StreamOptions {
+            strict_deposit: false,
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::UnfundedDeposit)));
+}
+
+#[test]
+fn test_plain_create_stream_is_strict() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(ctx.client().get_stream_state(&stream_id).strict_deposit);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — admin_force_complete
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_force_complete_at_zero_percent_pays_full_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+
+    assert_eq!(ctx.client().admin_force_complete(&stream_id, &7u32), 1000);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.withdrawn_amount, 1000);
+    assert_eq!(state.completed_at, Some(0));
+    assert_eq!(ctx.client().get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_force_complete_at_half_pays_full_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().admin_force_complete(&stream_id, &7u32), 700);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1000);
+    assert_eq!(ctx.client().get_total_streamed(), 1000);
+
+    let events = ctx.env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &topics.get_unchecked(1)),
+        soroban_sdk::Symbol::new(&ctx.env, "force_completed")
+    );
+    assert_eq!(
+        crate::StreamForceCompleted::from_val(&ctx.env, &data),
+        crate::StreamForceCompleted {
+            stream_id,
+            reason_code: 7,
+            accrued: 500,
+            recipient_amount: 700,
+        }
+    );
+}
+
+#[test]
+fn test_force_complete_paused_stream_clears_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().pause_stream_as_admin(&stream_id);
+
+    assert_eq!(ctx.client().admin_force_complete(&stream_id, &1u32), 1000);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Completed);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_force_complete_terminal_stream_is_invalid_state() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().cancel_stream(&stream_id);
+
+    let e = ContractError::InvalidState;
+    assert_eq!(
+        ctx.client().try_admin_force_complete(&stream_id, &1u32),
+        Err(Ok(e))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_force_complete_by_non_admin_panics() {
+    let ctx = TestContext::setup_strict();
+    let stream_id = {
+        ctx.env.mock_all_auths();
+        ctx.create_default_stream()
+    };
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "admin_force_complete",
+            args: (stream_id, 1u32).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().admin_force_complete(&stream_id, &1u32);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — Config.fee_on_withdrawal_bps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_withdrawal_fee_deducted_from_payout() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_on_withdrawal_bps(&100); // 1%
+    assert_eq!(ctx.client().get_config().fee_on_withdrawal_bps, 100);
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 990);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 990);
+    assert_eq!(ctx.client().get_accumulated_fees(), 10);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 10);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 1000);
+    assert_eq!(state.status, StreamStatus::Completed);
+
+    let history = ctx.client().get_withdrawal_history(&stream_id);
+    assert_eq!(history.get_unchecked(0).amount, 990);
+
+    let treasury = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().withdraw_protocol_fees(&treasury), 10);
+    assert_eq!(ctx.client().get_accumulated_fees(), 0);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_withdrawal_fee_reported_alike_in_event_and_history() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_on_withdrawal_bps(&100); // 1%
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 495);
+
+    let events = ctx.env.events().all();
+    let withdrawn = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &withdrawn.1.get(1).unwrap()),
+        soroban_sdk::symbol_short!("withdrawn")
+    );
+    assert_eq!(
+        crate::StreamWithdrawn::from_val(&ctx.env, &withdrawn.2),
+        crate::StreamWithdrawn {
+            stream_id,
+            amount: 495,
+            fee: 5,
+            withdrawn_total: 500,
+        }
+    );
+
+    let record = ctx
+        .client()
+        .get_withdrawal_history(&stream_id)
+        .get_unchecked(0);
+    assert_eq!(record.amount, 495);
+    assert_eq!(record.fee, 5);
+}
+
+#[test]
+fn test_withdrawal_fee_stacks_with_creation_fee() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_bps(&1000); // 10% at creation
+    ctx.client().set_fee_on_withdrawal_bps(&100); // 1% per withdrawal
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &2000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(ctx.client().get_accumulated_fees(), 200);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 495);
+    assert_eq!(ctx.client().get_accumulated_fees(), 205);
+}
+
+#[test]
+fn test_withdrawal_fee_applies_to_batch_and_redirected_withdrawals() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_on_withdrawal_bps(&500); // 5%
+    let first = ctx.create_default_stream();
+    let second = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(200);
+    let destination = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().withdraw_to(&first, &destination), 190);
+    assert_eq!(ctx.token().balance(&destination), 190);
+
+    ctx.env.ledger().set_timestamp(400);
+    let amounts = ctx
+        .client()
+        .batch_withdraw(&soroban_sdk::vec![&ctx.env, first, second]);
+    assert_eq!(amounts, soroban_sdk::vec![&ctx.env, 190, 380]);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 570);
+    assert_eq!(ctx.client().get_accumulated_fees(), 40);
+}
+
+#[test]
+fn test_withdrawal_fee_zero_by_default() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_config().fee_on_withdrawal_bps, 0);
+    let stream_id = ctx.create_default_stream();
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_eq!(ctx.client().get_accumulated_fees(), 0);
+}
+
+#[test]
+fn test_set_fee_on_withdrawal_bps_rejects_above_denominator() {
+    let ctx = TestContext::setup();
+    let e = ContractError::InvalidParams;
+    assert_eq!(
+        ctx.client().try_set_fee_on_withdrawal_bps(&10_001),
+        Err(Ok(e))
+    );
+}
+
+#[test]
+fn test_withdrawal_fee_skips_streams_in_old_token() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let new_token = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    ctx.client().admin_set_token(&new_token);
+    ctx.client().set_fee_on_withdrawal_bps(&100);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(ctx.client().get_accumulated_fees(), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — is_sender / is_recipient / is_operator
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_role_views_match_stream_parties() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let operator = Address::generate(&ctx.env);
+    ctx.client().approve_operator(&stream_id, &operator);
+
+    let client = ctx.client();
+    assert!(client.is_sender(&stream_id, &ctx.sender));
+    assert!(!client.is_sender(&stream_id, &ctx.recipient));
+    assert!(client.is_recipient(&stream_id, &ctx.recipient));
+    assert!(!client.is_recipient(&stream_id, &ctx.sender));
+    assert!(client.is_operator(&stream_id, &operator));
+    assert!(!client.is_operator(&stream_id, &ctx.recipient));
+
+    client.revoke_operator(&stream_id);
+    assert!(!client.is_operator(&stream_id, &operator));
+}
+
+#[test]
+fn test_role_views_false_for_unknown_stream() {
+    let ctx = TestContext::setup();
+    let client = ctx.client();
+    assert!(!client.is_sender(&99, &ctx.sender));
+    assert!(!client.is_recipient(&99, &ctx.recipient));
+    assert!(!client.is_operator(&99, &ctx.recipient));
+}
+
+#[test]
+fn test_is_recipient_follows_recipient_update() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.client().update_recipient(&stream_id, &new_recipient);
+
+    assert!(!ctx.client().is_recipient(&stream_id, &ctx.recipient));
+    assert!(ctx.client().is_recipient(&stream_id, &new_recipient));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — StreamOptions.cancel_penalty_bps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_cancel_penalty_at_zero_elapsed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_penalty_bps: 500,
+        ..Default::default()
+    });
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).cancel_penalty_bps,
+        500
+    );
+    assert_eq!(ctx.client().simulate_cancel(&stream_id, &0), (950, 50));
+
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (950, 50));
+    assert_eq!(ctx.token().balance(&ctx.sender), 9950);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 50);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 50);
+    assert_eq!(ctx.client().get_total_value_locked(), 50);
+
+    assert_eq!(ctx.client().withdraw(&stream_id), 50);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 50);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_cancel_penalty_applies_to_unstreamed_part_only() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_penalty_bps: 500,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(400);
+
+    // 600 unstreamed: 30 penalty, 570 refunded.
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (570, 430));
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 430);
+    // History before the cancellation is unaffected.
+    assert_eq!(ctx.client().calculate_accrued_at(&stream_id, &200), 200);
+}
+
+#[test]
+fn test_cancel_penalty_zero_when_fully_elapsed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_penalty_bps: 500,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(1000);
+
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (0, 1000));
+    assert_eq!(ctx.token().balance(&ctx.sender), 9000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+}
+
+#[test]
+fn test_cancel_penalty_zero_matches_plain_cancel() {
+    let ctx = TestContext::setup();
+    let penalty_free = ctx.create_stream_with(crate::StreamOptions {
+        cancel_penalty_bps: 0,
+        ..Default::default()
+    });
+    let plain = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(250);
+
+    assert_eq!(
+        ctx.client().cancel_stream(&penalty_free),
+        ctx.client().cancel_stream(&plain)
+    );
+    assert_eq!(ctx.client().calculate_accrued(&penalty_free), 250);
+}
+
+#[test]
+fn test_cancel_penalty_not_paid_on_recipient_cancel() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_penalty_bps: 500,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(100);
+
+    assert_eq!(ctx.client().cancel_stream_as_recipient(&stream_id), 100);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9900);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).cancel_penalty_bps,
+        0
+    );
+}
+
+#[test]
+fn test_cancel_penalty_above_100_percent_rejected() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &crate::StreamOptions {
+            cancel_penalty_bps: 10_001,
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidParams)));
+}
+
+#[test]
+fn test_cancel_penalty_matches_pre_cancel_views() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancel_penalty_bps: 500,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(400);
+    let refundable = ctx.client().get_refundable_amount(&stream_id);
+    let withdrawable = ctx.client().get_withdrawable_amount(&stream_id);
+    // 600 unstreamed, 5% of it (30) goes to the recipient as the penalty.
+    assert_eq!((refundable, withdrawable), (570, 200));
+
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, refundable);
+    assert_eq!(recipient_accrued, 1000 - refundable);
+    assert_eq!(recipient_accrued, 200 + withdrawable + 30);
+    assert_eq!(
+        ctx.client().get_withdrawable_amount(&stream_id),
+        withdrawable + 30
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_streams_bulk
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_streams_bulk_mixes_found_and_missing() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let second = ctx.create_default_stream();
+    let ids = soroban_sdk::vec![&ctx.env, second, 99, first, second];
+
+    let streams = ctx.client().get_streams_bulk(&ids);
+    assert_eq!(streams.len(), 4);
+    assert_eq!(streams.get_unchecked(0).unwrap().stream_id, second);
+    assert!(streams.get_unchecked(1).is_none());
+    assert_eq!(streams.get_unchecked(2).unwrap().stream_id, first);
+    assert_eq!(streams.get_unchecked(3).unwrap().stream_id, second);
+}
+
+#[test]
+fn test_get_streams_bulk_empty_input() {
+    let ctx = TestContext::setup();
+    let streams = ctx
+        .client()
+        .get_streams_bulk(&soroban_sdk::Vec::new(&ctx.env));
+    assert!(streams.is_empty());
+}
+
+#[test]
+fn test_get_streams_bulk_limit() {
+    let ctx = TestContext::setup();
+    let mut ids = soroban_sdk::Vec::new(&ctx.env);
+    for id in 0..u64::from(crate::MAX_BULK_READ) {
+        ids.push_back(id);
+    }
+    assert_eq!(
+        ctx.client().get_streams_bulk(&ids).len(),
+        crate::MAX_BULK_READ
+    );
+
+    ids.push_back(0);
+    let result = ctx.client().try_get_streams_bulk(&ids);
+    assert_eq!(result, Err(Ok(ContractError::InvalidParams)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — StreamOptions.recipient_can_pause
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_recipient_pauses_and_resumes_when_allowed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_can_pause: true,
+        ..Default::default()
+    });
+    assert!(ctx.client().get_can_recipient_pause(&stream_id));
+    ctx.env.ledger().set_timestamp(100);
+
+    ctx.client().pause_stream_as_recipient(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.paused_by, crate::PauserKind::Recipient);
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().resume_stream_as_recipient(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.paused_by, crate::PauserKind::NotPaused);
+    assert_eq!(state.total_paused_duration, 100);
+}
+
+#[test]
+#[should_panic]
+fn test_recipient_pause_panics_when_disallowed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert!(!ctx.client().get_can_recipient_pause(&stream_id));
+    ctx.client().pause_stream_as_recipient(&stream_id);
+}
+
+#[test]
+fn test_recipient_pause_and_resume_rejected_when_disallowed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_can_pause: false,
+        ..Default::default()
+    });
+    let e = ContractError::Unauthorized;
+    let result = ctx.client().try_pause_stream_as_recipient(&stream_id);
+    assert_eq!(result, Err(Ok(e)));
+
+    ctx.client().pause_stream(&stream_id);
+    let result = ctx.client().try_resume_stream_as_recipient(&stream_id);
+    assert_eq!(result, Err(Ok(e)));
+}
+
+#[test]
+fn test_sender_can_pause_regardless_of_recipient_flag() {
+    for recipient_can_pause in [false, true] {
+        let ctx = TestContext::setup();
+        let stream_id = ctx.create_stream_with(crate::StreamOptions {
+            recipient_can_pause,
+            ..Default::default()
+        });
+        ctx.client().pause_stream(&stream_id);
+        let state = ctx.client().get_stream_state(&stream_id);
+        assert_eq!(state.paused_by, crate::PauserKind::Sender);
+    }
+}
+
+#[test]
+fn test_sender_and_recipient_lift_each_others_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_can_pause: true,
+        ..Default::default()
+    });
+
+    ctx.client().pause_stream(&stream_id);
+    ctx.client().resume_stream_as_recipient(&stream_id);
+    ctx.client().pause_stream_as_recipient(&stream_id);
+    ctx.client().resume_stream(&stream_id);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Active);
+}
+
+#[test]
+fn test_recipient_cannot_resume_admin_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_can_pause: true,
+        ..Default::default()
+    });
+    ctx.client().pause_stream_as_admin(&stream_id);
+
+    let result = ctx.client().try_resume_stream_as_recipient(&stream_id);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_get_can_recipient_pause_unknown_stream() {
+    let ctx = TestContext::setup();
+    let result = ctx.client().try_get_can_recipient_pause(&99);
+    assert_eq!(result, Err(Ok(ContractError::StreamNotFound)));
+}
+
+#[test]
+#[should_panic]
+fn test_pause_stream_as_recipient_requires_recipient_auth() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_can_pause: true,
+        ..Default::default()
+    });
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "pause_stream_as_recipient",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().pause_stream_as_recipient(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — create_stream_with_nonce
+// ---------------------------------------------------------------------------
+
+fn create_nonce_stream(ctx: &TestContext, sender: &Address, nonce: Option<u64>) -> u64 {
+    ctx.client().create_stream_with_nonce(
+        sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &nonce,
+    )
+}
+
+#[test]
+fn test_nonce_stream_id_is_hash_derived() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.client().is_nonce_used(&ctx.sender, &7));
+    let stream_id = create_nonce_stream(&ctx, &ctx.sender, Some(7));
+
+    use soroban_sdk::xdr::ToXdr;
+    let mut preimage = ctx.sender.clone().to_xdr(&ctx.env);
+    preimage.extend_from_array(&7u64.to_be_bytes());
+    let digest = ctx.env.crypto().sha256(&preimage).to_array();
+    let mut expected = [0u8; 8];
+    expected.copy_from_slice(&digest[..8]);
+    assert_eq!(stream_id, u64::from_be_bytes(expected));
+
+    assert!(ctx.client().is_nonce_used(&ctx.sender, &7));
+    assert_eq!(ctx.client().get_next_stream_id(), 0);
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.stream_id, stream_id);
+    assert_eq!(state.deposit_amount, 1000);
+    assert_eq!(
+        ctx.client().get_streams_by_sender(&ctx.sender),
+        soroban_sdk::vec![&ctx.env, stream_id]
+    );
+
+    ctx.env.ledger().set_timestamp(300);
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+}
+
+#[test]
+#[should_panic]
+fn test_nonce_replay_by_same_sender_panics() {
+    let ctx = TestContext::setup();
+    create_nonce_stream(&ctx, &ctx.sender, Some(7));
+    create_nonce_stream(&ctx, &ctx.sender, Some(7));
+}
+
+#[test]
+fn test_nonce_replay_rejected() {
+    let ctx = TestContext::setup();
+    create_nonce_stream(&ctx, &ctx.sender, Some(7));
+    let result = ctx.client().try_create_stream_with_nonce(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &Some(7),
+    );
+    assert_eq!(result, Err(Ok(ContractError::NonceAlreadyUsed)));
+    assert_eq!(ctx.token().balance(&ctx.sender), 9000);
+}
+
+#[test]
+fn test_same_nonce_from_different_senders() {
+    let ctx = TestContext::setup();
+    let other_sender = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&other_sender, &1000);
+
+    let first = create_nonce_stream(&ctx, &ctx.sender, Some(7));
+    let second = create_nonce_stream(&ctx, &other_sender, Some(7));
+    assert_ne!(first, second);
+    assert!(ctx.client().is_nonce_used(&other_sender, &7));
+}
+
+#[test]
+fn test_nonce_none_uses_counter() {
+    let ctx = TestContext::setup();
+    assert_eq!(create_nonce_stream(&ctx, &ctx.sender, None), 0);
+    assert_eq!(create_nonce_stream(&ctx, &ctx.sender, None), 1);
+    assert_eq!(ctx.client().get_next_stream_id(), 2);
+}
+
+#[test]
+fn test_nonce_streams_counted_in_protocol_stats() {
+    let ctx = TestContext::setup();
+    create_nonce_stream(&ctx, &ctx.sender, Some(7));
+    create_nonce_stream(&ctx, &ctx.sender, None);
+
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!(stats.total_streams, 2);
+    assert_eq!(
+        stats.active_count + stats.paused_count + stats.completed_count + stats.cancelled_count,
+        stats.total_streams
+    );
+    assert_eq!(ctx.client().get_next_stream_id(), 1);
+}
+
+#[test]
+fn test_sequential_id_skips_nonce_stream() {
+    let ctx = TestContext::setup();
+    let hashed = create_nonce_stream(&ctx, &ctx.sender, Some(7));
+    // Move the sequential counter onto the hashed ID.
+    ctx.env.as_contract(&ctx.contract_id, || {
+        ctx.env
+            .storage()
+            .instance()
+            .set(&crate::DataKey::NextStreamId, &hashed);
+    });
+
+    let sequential = ctx.create_default_stream();
+    assert_eq!(sequential, hashed + 1);
+    assert_eq!(ctx.client().get_next_stream_id(), hashed + 2);
+    assert_eq!(ctx.client().get_stream_state(&hashed).deposit_amount, 1000);
+    assert_eq!(ctx.client().get_protocol_stats().total_streams, 2);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_time_to_completion
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_time_to_completion_active_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    assert_eq!(ctx.client().get_time_to_completion(&stream_id), Some(600));
+}
+
+#[test]
+fn test_time_to_completion_paused_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(500);
+    // WithdrawalBlock pauses do not move the end.
+    assert_eq!(ctx.client().get_time_to_completion(&stream_id), Some(500));
+}
+
+#[test]
+fn test_time_to_completion_accrual_freeze_pause() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        pause_mode: crate::PauseMode::AccrualFreeze,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().pause_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(700);
+    assert_eq!(ctx.client().get_time_to_completion(&stream_id), Some(600));
+
+    ctx.client().resume_stream(&stream_id);
+    ctx.env.ledger().set_timestamp(800);
+    assert_eq!(ctx.client().get_time_to_completion(&stream_id), Some(500));
+}
+
+#[test]
+fn test_time_to_completion_past_end_time() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1500);
+    assert_eq!(ctx.client().get_time_to_completion(&stream_id), Some(0));
+}
+
+#[test]
+fn test_time_to_completion_terminal_streams() {
+    let ctx = TestContext::setup();
+    let completed = ctx.create_default_stream();
+    let cancelled = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&completed);
+    ctx.client().cancel_stream(&cancelled);
+
+    assert_eq!(ctx.client().get_time_to_completion(&completed), None);
+    assert_eq!(ctx.client().get_time_to_completion(&cancelled), None);
+    let result = ctx.client().try_get_time_to_completion(&99);
+    assert_eq!(result, Err(Ok(ContractError::StreamNotFound)));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — create_split_stream
+// ---------------------------------------------------------------------------
+
+/// Splits 10_000 tokens over 0..1000 (10/s) 50/30/20 between three recipients.
+fn create_three_way_split(ctx: &TestContext) -> (soroban_sdk::Vec<u64>, [Address; 3]) {
+    ctx.env.ledger().set_timestamp(0);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&ctx.sender, &10_000);
+    let recipients = [
+        ctx.recipient.clone(),
+        Address::generate(&ctx.env),
+        Address::generate(&ctx.env),
+    ];
+    let weights = soroban_sdk::vec![
+        &ctx.env,
+        (recipients[0].clone(), 5_000u32),
+        (recipients[1].clone(), 3_000u32),
+        (recipients[2].clone(), 2_000u32),
+    ];
+    let ids =
+        ctx.client()
+            .create_split_stream(&ctx.sender, &weights, &10_000_i128, &0u64, &1000u64);
+    (ids, recipients)
+}
+
+#[test]
+fn test_split_stream_deposits_and_rates() {
+    let ctx = TestContext::setup();
+    let (ids, recipients) = create_three_way_split(&ctx);
+    assert_eq!(ids.len(), 3);
+    assert_eq!(ctx.token().balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 10_000);
+
+    let expected = [(5_000, 5), (3_000, 3), (2_000, 2)];
+    for (i, (deposit, rate)) in expected.into_iter().enumerate() {
+        let state = ctx.client().get_stream_state(&ids.get_unchecked(i as u32));
+        assert_eq!(state.recipient, recipients[i]);
+        assert_eq!(state.deposit_amount, deposit);
+        assert_eq!(state.rate_per_second, rate);
+        assert_eq!(state.end_time, 1000);
+    }
+}
+
+#[test]
+fn test_split_stream_independent_withdrawals() {
+    let ctx = TestContext::setup();
+    let (ids, recipients) = create_three_way_split(&ctx);
+    ctx.env.ledger().set_timestamp(400);
+
+    assert_eq!(ctx.client().withdraw(&ids.get_unchecked(0)), 2_000);
+    assert_eq!(ctx.token().balance(&recipients[0]), 2_000);
+    assert_eq!(
+        ctx.client().get_withdrawable_amount(&ids.get_unchecked(1)),
+        1_200
+    );
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&ids.get_unchecked(2)), 2_000);
+    assert_eq!(ctx.token().balance(&recipients[2]), 2_000);
+}
+
+#[test]
+fn test_split_stream_cancel_leaves_others_running() {
+    let ctx = TestContext::setup();
+    let (ids, _) = create_three_way_split(&ctx);
+    ctx.env.ledger().set_timestamp(500);
+
+    assert_eq!(
+        ctx.client().cancel_stream(&ids.get_unchecked(1)),
+        (1_500, 1_500)
+    );
+    let status = |i: u32| ctx.client().get_stream_state(&ids.get_unchecked(i)).status;
+    assert_eq!(status(0), StreamStatus::Active);
+    assert_eq!(status(1), StreamStatus::Cancelled);
+    assert_eq!(status(2), StreamStatus::Active);
+    assert_eq!(ctx.client().withdraw(&ids.get_unchecked(0)), 2_500);
+}
+
+#[test]
+fn test_split_stream_remainder_goes_to_last_recipient() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let weights = soroban_sdk::vec![
+        &ctx.env,
+        (ctx.recipient.clone(), 3_333u32),
+        (Address::generate(&ctx.env), 6_667u32),
+    ];
+    let ids = ctx
+        .client()
+        .create_split_stream(&ctx.sender, &weights, &1_000_i128, &0u64, &100u64);
+    let deposit = |i: u32| {
+        ctx.client()
+            .get_stream_state(&ids.get_unchecked(i))
+            .deposit_amount
+    };
+    assert_eq!(deposit(0), 333);
+    assert_eq!(deposit(1), 667);
+    assert_eq!(ctx.token().balance(&ctx.sender), 9_000);
+}
+
+#[test]
+fn test_split_stream_below_one_token_per_second() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let thirty_days = 30 * 24 * 3600;
+    let recipients = [
+        ctx.recipient.clone(),
+        Address::generate(&ctx.env),
+        Address::generate(&ctx.env),
+    ];
+    let weights = soroban_sdk::vec![
+        &ctx.env,
+        (recipients[0].clone(), 5_000u32),
+        (recipients[1].clone(), 3_000u32),
+        (recipients[2].clone(), 2_000u32),
+    ];
+    let ids =
+        ctx.client()
+            .create_split_stream(&ctx.sender, &weights, &3_000_i128, &0u64, &thirty_days);
+
+    let first = ctx.client().get_stream_state(&ids.get_unchecked(0));
+    assert_eq!(first.deposit_amount, 1_500);
+    assert_eq!((first.rate_per_second, first.rate_denominator), (1, 1_728));
+
+    ctx.env.ledger().set_timestamp(thirty_days / 2);
+    assert_eq!(ctx.client().withdraw(&ids.get_unchecked(0)), 750);
+
+    ctx.env.ledger().set_timestamp(thirty_days);
+    let expected = [750, 900, 600];
+    for (i, amount) in expected.into_iter().enumerate() {
+        assert_eq!(ctx.client().withdraw(&ids.get_unchecked(i as u32)), amount);
+    }
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn test_split_stream_with_creation_fee() {
+    let ctx = TestContext::setup();
+    ctx.client().set_fee_bps(&100); // 1%
+    let (ids, recipients) = create_three_way_split(&ctx);
+    assert_eq!(ctx.client().get_accumulated_fees(), 100);
+
+    let first = ctx.client().get_stream_state(&ids.get_unchecked(0));
+    assert_eq!(first.deposit_amount, 4_950);
+    assert_eq!((first.rate_per_second, first.rate_denominator), (99, 20));
+
+    ctx.env.ledger().set_timestamp(1000);
+    let expected = [4_950, 2_970, 1_980];
+    for (i, amount) in expected.into_iter().enumerate() {
+        assert_eq!(ctx.client().withdraw(&ids.get_unchecked(i as u32)), amount);
+        assert_eq!(ctx.token().balance(&recipients[i]), amount);
+    }
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 100);
+}
+
+#[test]
+fn test_split_stream_weights_must_sum_to_10000() {
+    let ctx = TestContext::setup();
+    let e = ContractError::InvalidParams;
+    let short = soroban_sdk::vec![&ctx.env, (ctx.recipient.clone(), 9_999u32)];
+    let result =
+        ctx.client()
+            .try_create_split_stream(&ctx.sender, &short, &1_000_i128, &0u64, &100u64);
+    assert_eq!(result, Err(Ok(e)));
+
+    let empty = soroban_sdk::Vec::new(&ctx.env);
+    let result =
+        ctx.client()
+            .try_create_split_stream(&ctx.sender, &empty, &1_000_i128, &0u64, &100u64);
+    assert_eq!(result, Err(Ok(e)));
+}
+
+#[test]
+fn test_recipient_pause_and_resume_are_idempotent() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        recipient_can_pause: true,
+        ..Default::default()
+    });
+    ctx.client().resume_stream_as_recipient(&stream_id);
+    ctx.client().pause_stream(&stream_id);
+    ctx.client().pause_stream_as_recipient(&stream_id);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.paused_by, crate::PauserKind::Sender);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — withdraw_and_create_stream
+// ---------------------------------------------------------------------------
+
+fn reinvest_params(ctx: &TestContext, deposit_amount: i128) -> CreateStreamParams {
+    CreateStreamParams {
+        recipient: ctx.admin.clone(),
+        deposit_amount,
+        rate_per_second: 1,
+        start_time: 1000,
+        cliff_time: 1000,
+        end_time: 1000 + deposit_amount as u64,
+    }
+}
+
+#[test]
+fn test_withdraw_and_create_stream_exact_funding() {
+    let ctx = TestContext::setup();
+    let from_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+
+    let params = reinvest_params(&ctx, 1000);
+    let (withdrawn, new_id) = ctx.client().withdraw_and_create_stream(&from_id, &params);
+    assert_eq!(withdrawn, 1000);
+
+    let old = ctx.client().get_stream_state(&from_id);
+    assert_eq!(old.status, StreamStatus::Completed);
+    let new = ctx.client().get_stream_state(&new_id);
+    assert_eq!(new.sender, ctx.recipient);
+    assert_eq!(new.recipient, ctx.admin);
+    assert_eq!(new.deposit_amount, 1000);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+
+    ctx.env.ledger().set_timestamp(1250);
+    assert_eq!(ctx.client().withdraw(&new_id), 250);
+}
+
+#[test]
+fn test_withdraw_and_create_stream_keeps_surplus() {
+    let ctx = TestContext::setup();
+    let from_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(800);
+
+    let params = reinvest_params(&ctx, 500);
+    let (withdrawn, _) = ctx.client().withdraw_and_create_stream(&from_id, &params);
+    assert_eq!(withdrawn, 800);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+}
+
+#[test]
+fn test_withdraw_and_create_stream_underfunded_rolls_back() {
+    let ctx = TestContext::setup();
+    let from_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+
+    let params = reinvest_params(&ctx, 1000);
+    let result = ctx
+        .client()
+        .try_withdraw_and_create_stream(&from_id, &params);
+    assert_eq!(result, Err(Ok(ContractError::UnfundedDeposit)));
+
+    assert_eq!(ctx.client().get_stream_state(&from_id).withdrawn_amount, 0);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1000);
+    assert_eq!(ctx.client().get_next_stream_id(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_withdraw_and_create_stream_underfunded_panics() {
+    let ctx = TestContext::setup();
+    let from_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client()
+        .withdraw_and_create_stream(&from_id, &reinvest_params(&ctx, 1000));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — recover_orphaned_stream
+// ---------------------------------------------------------------------------
+
+const RECOVERY_AT: u64 = 1000 + crate::DEFAULT_RECOVERY_GRACE_PERIOD;
+
+fn setup_recovery(ctx: &TestContext) -> Address {
+    let recovery = Address::generate(&ctx.env);
+    ctx.client().set_recovery_address(&Some(recovery.clone()));
+    recovery
+}
+
+#[test]
+fn test_recover_orphaned_stream_transfers_remaining_balance() {
+    let ctx = TestContext::setup();
+    let recovery = setup_recovery(&ctx);
+    assert_eq!(
+        ctx.client().get_config().recovery_address,
+        Some(recovery.clone())
+    );
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(RECOVERY_AT);
+    assert_eq!(ctx.client().recover_orphaned_stream(&stream_id), 700);
+    assert_eq!(ctx.token().balance(&recovery), 700);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().get_total_value_locked(), 0);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.status, StreamStatus::Expired);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(
+        soroban_sdk::Symbol::from_val(&ctx.env, &last.1.get_unchecked(1)),
+        soroban_sdk::symbol_short!("recovered")
+    );
+    let payload = crate::OrphanedStreamRecovered::from_val(&ctx.env, &last.2);
+    assert_eq!(payload.recovery_address, recovery);
+    assert_eq!(payload.amount, 700);
+}
+
+#[test]
+fn test_recover_cancelled_stream_takes_unclaimed_accrual() {
+    let ctx = TestContext::setup();
+    let recovery = setup_recovery(&ctx);
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(400);
+    ctx.client().cancel_stream(&stream_id);
+
+    ctx.env.ledger().set_timestamp(RECOVERY_AT);
+    assert_eq!(ctx.client().recover_orphaned_stream(&stream_id), 400);
+    assert_eq!(ctx.token().balance(&recovery), 400);
+    assert_eq!(ctx.client().get_withdrawable_amount(&stream_id), 0);
+    let result = ctx.client().try_withdraw(&stream_id);
+    assert_eq!(result, Err(Ok(ContractError::StreamExpired)));
+}
+
+#[test]
+fn test_recover_orphaned_stream_before_grace_period_rejected() {
+    let ctx = TestContext::setup();
+    setup_recovery(&ctx);
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(RECOVERY_AT - 1);
+
+    let result = ctx.client().try_recover_orphaned_stream(&stream_id);
+    assert_eq!(result, Err(Ok(ContractError::InvalidState)));
+
+    ctx.client().set_recovery_grace_period(&0);
+    assert_eq!(ctx.client().recover_orphaned_stream(&stream_id), 1000);
+}
+
+#[test]
+#[should_panic]
+fn test_recover_orphaned_stream_before_grace_period_panics() {
+    let ctx = TestContext::setup();
+    setup_recovery(&ctx);
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().recover_orphaned_stream(&stream_id);
+}
+
+#[test]
+fn test_recover_orphaned_stream_disabled_without_address() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_config().recovery_address, None);
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(RECOVERY_AT);
+
+    let result = ctx.client().try_recover_orphaned_stream(&stream_id);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    setup_recovery(&ctx);
+    ctx.client().set_recovery_address(&None);
+    let result = ctx.client().try_recover_orphaned_stream(&stream_id);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_recover_orphaned_stream_terminal_rejected() {
+    let ctx = TestContext::setup();
+    setup_recovery(&ctx);
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(RECOVERY_AT);
+    let result = ctx.client().try_recover_orphaned_stream(&stream_id);
+    assert_eq!(result, Err(Ok(ContractError::InvalidState)));
+}
+
+#[test]
+#[should_panic]
+fn test_recover_orphaned_stream_requires_recovery_auth() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    setup_recovery(&ctx);
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(RECOVERY_AT);
+
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.admin,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "recover_orphaned_stream",
+            args: (stream_id,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().recover_orphaned_stream(&stream_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — cliff_time == 0 means "no cliff"
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_zero_cliff_with_later_start_means_no_cliff() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &500u64,
+        &0u64,
+        &1500u64,
+    );
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.cliff_time, 500);
+    assert!(!ctx.client().has_cliff(&stream_id));
+
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().calculate_accrued(&stream_id), 100);
+}
+
+#[test]
+fn test_zero_cliff_accepted_by_validation_and_batch() {
+    let ctx = TestContext::setup();
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &500u64,
+        &0u64,
+        &1500u64,
+    );
+    assert!(errors.is_empty());
+
+    let params = soroban_sdk::vec![
+        &ctx.env,
+        CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 1000,
+            rate_per_second: 1,
+            start_time: 500,
+            cliff_time: 0,
+            end_time: 1500,
+        },
+    ];
+    let ids = ctx.client().create_streams(&ctx.sender, &params);
+    let state = ctx.client().get_stream_state(&ids.get_unchecked(0));
+    assert_eq!(state.cliff_time, 500);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — accrual at boundary times
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_accrual_at_boundary_times() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    // 2000 tokens at 2/s over 100..1100; one stream without a cliff, one with a
+    // cliff at 300.
+    let create = |cliff_time: u64| {
+        ctx.client().create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &2000_i128,
+            &2_i128,
+            &100u64,
+            &cliff_time,
+            &1100u64,
+        )
+    };
+    let no_cliff = create(100);
+    let cliff = create(300);
+    let accrued_at = |stream_id: u64, t: u64| {
+        ctx.env.ledger().set_timestamp(t);
+        ctx.client().calculate_accrued(&stream_id)
+    };
+
+    assert_eq!(accrued_at(no_cliff, 100), 0); // t == start_time
+    assert_eq!(accrued_at(no_cliff, 101), 2); // t == start_time + 1: one second at rate
+    assert_eq!(accrued_at(no_cliff, 1099), 1998); // t == end_time - 1
+    assert_eq!(accrued_at(no_cliff, 1100), 2000); // t == end_time: full deposit
+    assert_eq!(accrued_at(no_cliff, 1101), 2000); // t == end_time + 1: capped
+
+    assert_eq!(accrued_at(cliff, 101), 0); // before the cliff
+    assert_eq!(accrued_at(cliff, 299), 0); // t == cliff_time - 1
+    assert_eq!(accrued_at(cliff, 300), 400); // t == cliff_time: elapsed since start
+    assert_eq!(accrued_at(cliff, 1099), 1998);
+    assert_eq!(accrued_at(cliff, 1100), 2000);
+    assert_eq!(accrued_at(cliff, 1101), 2000);
+}
+
+#[test]
+fn test_accrual_at_boundary_times_with_cliff_unlock() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    // 200 released at the cliff (300), the remaining 800 at 1/s over 300..1100.
+    let stream_id = ctx.client().create_stream_with_options(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &100u64,
+        &300u64,
+        &1100u64,
+        &crate::StreamOptions {
+            cliff_unlock_amount: 200,
+            ..Default::default()
+        },
+    );
+    let accrued_at = |t: u64| ctx.client().calculate_accrued_at(&stream_id, &t);
+
+    assert_eq!(accrued_at(100), 0);
+    assert_eq!(accrued_at(299), 0);
+    assert_eq!(accrued_at(300), 200); // just the lump sum
+    assert_eq!(accrued_at(301), 201);
+    assert_eq!(accrued_at(1100), 1000);
+    assert_eq!(accrued_at(1101), 1000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — sender whitelist
+// ---------------------------------------------------------------------------
+
+fn try_create_default(
+    ctx: &TestContext,
+) -> Result<Result<u64, soroban_sdk::Error>, Result<ContractError, soroban_sdk::InvokeError>> {
+    ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    )
+}
+
+#[test]
+fn test_sender_whitelist_disabled_by_default() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.client().get_config().whitelist_enabled);
+    assert!(!ctx.client().is_sender_whitelisted(&ctx.sender));
+    assert_eq!(ctx.client().get_sender_whitelist().len(), 0);
+    assert!(try_create_default(&ctx).is_ok());
+}
+
+#[test]
+fn test_sender_whitelist_gates_stream_creation() {
+    let ctx = TestContext::setup();
+    ctx.client().set_whitelist_enabled(&true);
+    assert!(ctx.client().get_config().whitelist_enabled);
+
+    // Unlisted sender is rejected.
+    assert_eq!(
+        try_create_default(&ctx),
+        Err(Ok(ContractError::SenderNotWhitelisted))
+    );
+
+    // Listed sender succeeds.
+    ctx.client().add_to_sender_whitelist(&ctx.sender);
+    assert!(ctx.client().is_sender_whitelisted(&ctx.sender));
+    assert_eq!(
+        ctx.client().get_sender_whitelist(),
+        soroban_sdk::vec![&ctx.env, ctx.sender.clone()]
+    );
+    let stream_id = try_create_default(&ctx).unwrap().unwrap();
+
+    // After removal the sender is rejected again; the existing stream is untouched.
+    ctx.client().remove_from_sender_whitelist(&ctx.sender);
+    assert!(!ctx.client().is_sender_whitelisted(&ctx.sender));
+    assert_eq!(
+        try_create_default(&ctx),
+        Err(Ok(ContractError::SenderNotWhitelisted))
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+
+    // Disabling the whitelist lets anyone create again.
+    ctx.client().set_whitelist_enabled(&false);
+    assert!(try_create_default(&ctx).is_ok());
+}
+
+#[test]
+fn test_sender_whitelist_gates_batch_creation() {
+    let ctx = TestContext::setup();
+    ctx.client().set_whitelist_enabled(&true);
+    let params = soroban_sdk::vec![
+        &ctx.env,
+        crate::CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 1000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        }
+    ];
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &params),
+        Err(Ok(ContractError::SenderNotWhitelisted))
+    );
+    ctx.client().add_to_sender_whitelist(&ctx.sender);
+    assert_eq!(ctx.client().create_streams(&ctx.sender, &params).len(), 1);
+}
+
+#[test]
+fn test_validate_stream_params_reports_unlisted_sender() {
+    let ctx = TestContext::setup();
+    ctx.client().set_whitelist_enabled(&true);
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![&ctx.env, ContractError::SenderNotWhitelisted]
+    );
+}
+
+#[test]
+fn test_sender_whitelist_duplicate_add_and_missing_remove_fail() {
+    let ctx = TestContext::setup();
+    ctx.client().add_to_sender_whitelist(&ctx.sender);
+    assert_eq!(
+        ctx.client().try_add_to_sender_whitelist(&ctx.sender),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_remove_from_sender_whitelist(&ctx.recipient),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(ctx.client().get_sender_whitelist().len(), 1);
+}
+
+#[test]
+fn test_sender_whitelist_changes_emit_events() {
+    let ctx = TestContext::setup();
+    let topic = |ctx: &TestContext| {
+        let last = ctx.env.events().all().last().unwrap();
+        soroban_sdk::Symbol::from_val(&ctx.env, &last.1.get_unchecked(1))
+    };
+    ctx.client().set_whitelist_enabled(&true);
+    assert_eq!(topic(&ctx), soroban_sdk::Symbol::new(&ctx.env, "wl_on"));
+    ctx.client().add_to_sender_whitelist(&ctx.sender);
+    assert_eq!(topic(&ctx), soroban_sdk::Symbol::new(&ctx.env, "wl_add"));
+    ctx.client().remove_from_sender_whitelist(&ctx.sender);
+    assert_eq!(topic(&ctx), soroban_sdk::Symbol::new(&ctx.env, "wl_remove"));
+}
+
+#[test]
+#[should_panic]
+fn test_add_to_sender_whitelist_requires_admin_auth() {
+    let ctx = TestContext::setup_strict();
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "add_to_sender_whitelist",
+            args: (&ctx.sender,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().add_to_sender_whitelist(&ctx.sender);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — recipient blacklist
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_blacklisted_recipient_cannot_receive_new_streams() {
+    let ctx = TestContext::setup();
+    assert!(!ctx.client().is_recipient_blacklisted(&ctx.recipient));
+    assert_eq!(ctx.client().blacklist_recipient(&ctx.recipient), 0);
+    assert!(ctx.client().is_recipient_blacklisted(&ctx.recipient));
+
+    assert_eq!(
+        try_create_default(&ctx),
+        Err(Ok(ContractError::RecipientBlacklisted))
+    );
+    let params = soroban_sdk::vec![
+        &ctx.env,
+        crate::CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 1000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        }
+    ];
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &params),
+        Err(Ok(ContractError::RecipientBlacklisted))
+    );
+    let errors = ctx.client().validate_stream_params(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(
+        errors,
+        soroban_sdk::vec![&ctx.env, ContractError::RecipientBlacklisted]
+    );
+
+    // Nor can an existing stream be redirected to it.
+    let other = Address::generate(&ctx.env);
+    ctx.client().create_stream(
+        &ctx.sender,
+        &other,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    let stream_id = ctx
+        .client()
+        .get_streams_by_recipient(&other)
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        ctx.client()
+            .try_update_recipient(&stream_id, &ctx.recipient),
+        Err(Ok(ContractError::RecipientBlacklisted))
+    );
+}
+
+#[test]
+fn test_blacklist_recipient_cancels_active_streams() {
+    let ctx = TestContext::setup();
+    let first = ctx.create_default_stream();
+    let second = ctx.create_default_stream();
+    let other = Address::generate(&ctx.env);
+    let unaffected = ctx.client().create_stream(
+        &ctx.sender,
+        &other,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    ctx.env.ledger().set_timestamp(300);
+    let sender_before = ctx.token().balance(&ctx.sender);
+    assert_eq!(ctx.client().blacklist_recipient(&ctx.recipient), 2);
+
+    for stream_id in [first, second] {
+        let stream = ctx.client().get_stream_state(&stream_id);
+        assert_eq!(stream.status, StreamStatus::Cancelled);
+    }
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before + 1400);
+    assert_eq!(
+        ctx.client().get_stream_state(&unaffected).status,
+        StreamStatus::Active
+    );
+
+    // Tokens accrued before the blacklisting remain withdrawable.
+    ctx.env.ledger().set_timestamp(800);
+    assert_eq!(ctx.client().withdraw(&first), 300);
+    assert_eq!(ctx.client().withdraw(&second), 300);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 600);
+}
+
+#[test]
+fn test_unblacklist_recipient_restores_stream_creation() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.client().blacklist_recipient(&ctx.recipient);
+    ctx.client().unblacklist_recipient(&ctx.recipient);
+    assert!(!ctx.client().is_recipient_blacklisted(&ctx.recipient));
+
+    assert!(try_create_default(&ctx).is_ok());
+    // The stream cancelled by the blacklisting stays cancelled.
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_blacklist_twice_and_unblacklist_unknown_fail() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().try_unblacklist_recipient(&ctx.recipient),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    ctx.client().blacklist_recipient(&ctx.recipient);
+    assert_eq!(
+        ctx.client().try_blacklist_recipient(&ctx.recipient),
+        Err(Ok(ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn test_blacklist_recipient_skips_non_cancellable_streams() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        cancellable: false,
+        ..Default::default()
+    });
+    assert_eq!(ctx.client().blacklist_recipient(&ctx.recipient), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Active
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_blacklist_recipient_requires_admin_auth() {
+    let ctx = TestContext::setup_strict();
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "blacklist_recipient",
+            args: (&ctx.recipient,).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().blacklist_recipient(&ctx.recipient);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — per-address totals
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_sender_and_recipient_totals_across_stream_states() {
+    let ctx = TestContext::setup();
+    let active = ctx.create_default_stream();
+    let paused = ctx.create_default_stream();
+    let cancelled = ctx.create_default_stream();
+    let completed = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &100_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &100u64,
+    );
+    assert_eq!(
+        ctx.client().get_sender_active_deposit_total(&ctx.sender),
+        3100
+    );
+    assert_eq!(
+        ctx.client().get_recipient_claimable_total(&ctx.recipient),
+        0
+    );
+
+    ctx.env.ledger().set_timestamp(200);
+    ctx.client().withdraw(&active); // 200
+    ctx.client().withdraw(&completed); // 100, drains the stream
+    ctx.client().pause_stream(&paused); // 200 accrued, frozen while paused
+    ctx.client().cancel_stream(&cancelled); // 200 left for the recipient
+
+    ctx.env.ledger().set_timestamp(300);
+    // Active: 1000 - 200; Paused: 1000 - 0. Cancelled and Completed drop out.
+    assert_eq!(
+        ctx.client().get_sender_active_deposit_total(&ctx.sender),
+        1800
+    );
+    // Active: 300 - 200; Paused: 0; Cancelled: 200; Completed: 0.
+    assert_eq!(
+        ctx.client().get_recipient_claimable_total(&ctx.recipient),
+        300
+    );
+    let manual: i128 = [active, paused, cancelled, completed]
+        .iter()
+        .map(|id| ctx.client().get_withdrawable_amount(id))
+        .sum();
+    assert_eq!(manual, 300);
+}
+
+#[test]
+fn test_totals_for_unknown_addresses_are_zero() {
+    let ctx = TestContext::setup();
+    ctx.create_default_stream();
+    let stranger = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().get_sender_active_deposit_total(&stranger), 0);
+    assert_eq!(ctx.client().get_recipient_claimable_total(&stranger), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — exponential curves
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_linear_and_quadratic_curves_at_quarters() {
+    let ctx = TestContext::setup();
+    let linear = ctx.create_stream_with(crate::StreamOptions {
+        curve_type: crate::CurveType::Linear,
+        ..Default::default()
+    });
+    let unit = ctx.create_stream_with(crate::StreamOptions {
+        curve_type: crate::CurveType::Exponential(10_000),
+        ..Default::default()
+    });
+    let quadratic = ctx.create_stream_with(crate::StreamOptions {
+        curve_type: crate::CurveType::Exponential(20_000),
+        ..Default::default()
+    });
+    assert_eq!(
+        ctx.client().get_stream_state(&quadratic).curve_type,
+        crate::CurveType::Exponential(20_000)
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&linear).curve_type,
+        crate::CurveType::Linear
+    );
+
+    let at = |stream_id: u64, t: u64| ctx.client().calculate_accrued_at(&stream_id, &t);
+    for (t, linear_expected, quadratic_expected) in [
+        (250, 250, 62),
+        (500, 500, 250),
+        (750, 750, 562),
+        (1000, 1000, 1000),
+    ] {
+        assert_eq!(at(linear, t), linear_expected);
+        assert_eq!(at(unit, t), linear_expected);
+        assert_eq!(at(quadratic, t), quadratic_expected);
+    }
+}
+
+#[test]
+fn test_quadratic_curve_withdraw_at_halfway_pays_quarter() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        curve_type: crate::CurveType::Exponential(20_000),
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 250);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 750);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn test_quadratic_curve_cancel_refunds_unaccrued() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        curve_type: crate::CurveType::Exponential(20_000),
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (750, 250));
+}
+
+#[test]
+fn test_exponential_curve_rejects_invalid_options() {
+    let ctx = TestContext::setup();
+    let try_create = |options: crate::StreamOptions| {
+        ctx.client().try_create_stream_with_options(
+            &ctx.sender,
+            &ctx.recipient,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &100u64,
+            &1000u64,
+            &options,
+        )
+    };
+    let invalid = Err(Ok(ContractError::InvalidParams));
+    assert_eq!(
+        try_create(crate::StreamOptions {
+            curve_type: crate::CurveType::Exponential(0),
+            ..Default::default()
+        }),
+        invalid
+    );
+    assert_eq!(
+        try_create(crate::StreamOptions {
+            curve_type: crate::CurveType::Exponential(crate::MAX_CURVE_EXPONENT_BPS + 1),
+            ..Default::default()
+        }),
+        invalid
+    );
+    assert_eq!(
+        try_create(crate::StreamOptions {
+            curve_type: crate::CurveType::Exponential(20_000),
+            cliff_unlock_amount: 100,
+            ..Default::default()
+        }),
+        invalid
+    );
+}
+
+#[test]
+fn test_curved_stream_cannot_be_topped_up_or_extended() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        curve_type: crate::CurveType::Exponential(20_000),
+        ..Default::default()
+    });
+    assert_eq!(
+        ctx.client().try_topup_stream(&stream_id, &100_i128),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_extend_stream_end_time(&stream_id, &2000u64, &1000_i128),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — trigger_auto_withdraw
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_trigger_auto_withdraw_fires_once_interval_elapsed() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        auto_withdraw: true,
+        auto_withdraw_interval: 100,
+        ..Default::default()
+    });
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert!(state.auto_withdraw);
+    assert_eq!(state.auto_withdraw_interval, 100);
+
+    ctx.env.ledger().set_timestamp(100);
+    assert_eq!(ctx.client().trigger_auto_withdraw(&stream_id), 100);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 100);
+
+    // The next interval counts from that withdrawal.
+    ctx.env.ledger().set_timestamp(250);
+    assert_eq!(ctx.client().trigger_auto_withdraw(&stream_id), 150);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 250);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        250
+    );
+}
+
+#[test]
+fn test_trigger_auto_withdraw_fails_before_interval() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        auto_withdraw: true,
+        auto_withdraw_interval: 100,
+        ..Default::default()
+    });
+
+    ctx.env.ledger().set_timestamp(99);
+    assert_eq!(
+        ctx.client().try_trigger_auto_withdraw(&stream_id),
+        Err(Ok(ContractError::WithdrawalCooldown))
+    );
+
+    ctx.env.ledger().set_timestamp(150);
+    ctx.client().withdraw(&stream_id);
+    ctx.env.ledger().set_timestamp(200);
+    assert_eq!(
+        ctx.client().try_trigger_auto_withdraw(&stream_id),
+        Err(Ok(ContractError::WithdrawalCooldown))
+    );
+}
+
+#[test]
+fn test_trigger_auto_withdraw_requires_opt_in() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        auto_withdraw: false,
+        auto_withdraw_interval: 100,
+        ..Default::default()
+    });
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(
+        ctx.client().try_trigger_auto_withdraw(&stream_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        ctx.client().try_trigger_auto_withdraw(&99),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
+
+#[test]
+fn test_trigger_auto_withdraw_needs_no_auth() {
+    let ctx = TestContext::setup_strict();
+    ctx.env.mock_all_auths();
+    let stream_id = ctx.create_stream_with(crate::StreamOptions {
+        auto_withdraw: true,
+        auto_withdraw_interval: 100,
+        ..Default::default()
+    });
+
+    // No authorizations are mocked for the trigger itself.
+    ctx.env.mock_auths(&[]);
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().trigger_auto_withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_stream_utilization_bps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_stream_utilization_follows_withdrawals_not_accrual() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 0);
+
+    // Half accrued but nothing withdrawn yet.
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().get_stream_completion_bps(&stream_id), 5_000);
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 0);
+
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 5_000);
+
+    // Accrual moves on; utilization waits for the next withdrawal.
+    ctx.env.ledger().set_timestamp(800);
+    assert_eq!(ctx.client().get_stream_completion_bps(&stream_id), 8_000);
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 5_000);
+
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 10_000);
+}
+
+#[test]
+fn test_stream_utilization_of_cancelled_stream() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().cancel_stream(&stream_id);
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 0);
+    ctx.client().withdraw(&stream_id);
+    assert_eq!(ctx.client().get_stream_utilization_bps(&stream_id), 3_000);
+    assert_eq!(
+        ctx.client().try_get_stream_utilization_bps(&99),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — create_recurring_stream
+// ---------------------------------------------------------------------------
+
+const MONTH: u64 = 30 * 24 * 60 * 60;
+
+fn monthly_params(ctx: &TestContext, cliff_time: u64) -> crate::CreateStreamParams {
+    crate::CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: MONTH as i128,
+        rate_per_second: 1,
+        start_time: 1000,
+        cliff_time,
+        end_time: 1000 + MONTH,
+    }
+}
+
+#[test]
+fn test_create_recurring_stream_schedules_back_to_back_streams() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    let sac = StellarAssetClient::new(&ctx.env, &ctx.token_id);
+    sac.mint(&ctx.sender, &(3 * MONTH as i128));
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    let ids = ctx
+        .client()
+        .create_recurring_stream(&ctx.sender, &monthly_params(&ctx, 0), &3, &60);
+    assert_eq!(ids, soroban_sdk::vec![&ctx.env, 0u64, 1, 2]);
+
+    let mut expected_start = 1000;
+    for stream_id in ids.iter() {
+        let stream = ctx.client().get_stream_state(&stream_id);
+        assert_eq!(stream.start_time, expected_start);
+        assert_eq!(stream.end_time, expected_start + MONTH);
+        assert_eq!(stream.cliff_time, expected_start);
+        assert_eq!(stream.deposit_amount, MONTH as i128);
+        assert_eq!(stream.rate_per_second, 1);
+        expected_start += MONTH + 60;
+    }
+    assert_eq!(
+        sender_before - ctx.token().balance(&ctx.sender),
+        3 * MONTH as i128
+    );
+}
+
+#[test]
+fn test_create_recurring_stream_keeps_cliff_offset() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&ctx.sender, &(2 * MONTH as i128));
+    let ids =
+        ctx.client()
+            .create_recurring_stream(&ctx.sender, &monthly_params(&ctx, 1100), &2, &0);
+    let second = ctx.client().get_stream_state(&ids.get(1).unwrap());
+    assert_eq!(second.start_time, 1000 + MONTH);
+    assert_eq!(second.cliff_time, 1100 + MONTH);
+}
+
+#[test]
+fn test_create_recurring_stream_rejects_invalid_params() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client()
+            .try_create_recurring_stream(&ctx.sender, &monthly_params(&ctx, 0), &0, &0),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    let mut params = monthly_params(&ctx, 0);
+    params.end_time = params.start_time;
+    assert_eq!(
+        ctx.client()
+            .try_create_recurring_stream(&ctx.sender, &params, &2, &0),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_create_recurring_stream(
+            &ctx.sender,
+            &monthly_params(&ctx, 0),
+            &2,
+            &u64::MAX
+        ),
+        Err(Ok(ContractError::ArithmeticOverflow))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — global stream cap
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_global_stream_cap_blocks_creation_until_raised() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_global_stream_cap(), u64::MAX);
+
+    ctx.client()
+        .update_config(&None, &None, &None, &None, &None, &Some(3));
+    assert_eq!(ctx.client().get_global_stream_cap(), 3);
+    assert_eq!(ctx.client().get_config().global_stream_cap, 3);
+    for _ in 0..3 {
+        ctx.create_default_stream();
+    }
+    assert_eq!(
+        try_create_default(&ctx),
+        Err(Ok(ContractError::GlobalCapReached))
+    );
+
+    ctx.client()
+        .update_config(&None, &None, &None, &None, &None, &Some(10));
+    assert_eq!(try_create_default(&ctx), Ok(Ok(3)));
+}
+
+#[test]
+fn test_global_stream_cap_rejects_batches_that_overshoot() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &None, &None, &Some(2));
+    ctx.create_default_stream();
+    let params = crate::CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: 1000,
+        rate_per_second: 1,
+        start_time: 0,
+        cliff_time: 0,
+        end_time: 1000,
+    };
+    let two = soroban_sdk::vec![&ctx.env, params.clone(), params.clone()];
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &two),
+        Err(Ok(ContractError::GlobalCapReached))
+    );
+    let one = soroban_sdk::vec![&ctx.env, params];
+    assert_eq!(ctx.client().create_streams(&ctx.sender, &one).len(), 1);
+}
+
+#[test]
+fn test_global_stream_cap_counts_nonce_streams() {
+    let ctx = TestContext::setup();
+    ctx.client()
+        .update_config(&None, &None, &None, &None, &None, &Some(2));
+    create_nonce_stream(&ctx, &ctx.sender, Some(7));
+    ctx.create_default_stream();
+    // Only one stream advanced `NextStreamId`, but the cap is already full.
+    assert_eq!(ctx.client().get_next_stream_id(), 1);
+
+    let result = ctx.client().try_create_stream_with_nonce(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &Some(8),
+    );
+    assert_eq!(result, Err(Ok(ContractError::GlobalCapReached)));
+    assert!(!ctx.client().is_nonce_used(&ctx.sender, &8));
+    assert_eq!(
+        try_create_default(&ctx),
+        Err(Ok(ContractError::GlobalCapReached))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests — strict deposit mode
+// ---------------------------------------------------------------------------
+
+fn try_create_with_deposit(
+    ctx: &TestContext,
+    deposit: i128,
+) -> Result<Result<u64, soroban_sdk::Error>, Result<ContractError, soroban_sdk::InvokeError>> {
+    ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &deposit,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    )
+}
+
+#[test]
+fn test_strict_deposit_mode_requires_exact_deposit() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().set_strict_deposit_mode(&true);
+    assert!(ctx.client().get_config().strict_deposit_mode);
+
+    assert!(try_create_with_deposit(&ctx, 1000).is_ok());
+    assert_eq!(
+        try_create_with_deposit(&ctx, 1001),
+        Err(Ok(ContractError::ExcessDeposit))
+    );
+    assert_eq!(
+        try_create_with_deposit(&ctx, 999),
+        Err(Ok(ContractError::UnfundedDeposit))
+    );
+}
+
+#[test]
+fn test_strict_deposit_mode_off_allows_surplus() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    assert!(!ctx.client().get_config().strict_deposit_mode);
+
+    assert!(try_create_with_deposit(&ctx, 1000).is_ok());
+    assert!(try_create_with_deposit(&ctx, 1001).is_ok());
+    assert_eq!(
+        try_create_with_deposit(&ctx, 999),
+        Err(Ok(ContractError::UnfundedDeposit))
+    );
+}
+
+#[test]
+fn test_strict_deposit_mode_applies_to_batches_and_validation() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().set_strict_deposit_mode(&true);
+    let params = soroban_sdk::vec![
+        &ctx.env,
+        crate::CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 1001,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1000,
+        }
+    ];
+    assert_eq!(
+        ctx.client().try_create_streams(&ctx.sender, &params),
+        Err(Ok(ContractError::ExcessDeposit))
+    );
+    assert_eq!(
+        ctx.client().validate_stream_params(
+            &ctx.sender,
+            &ctx.recipient,
+            &1001_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        ),
+        soroban_sdk::vec![&ctx.env, ContractError::ExcessDeposit]
+    );
+}
+
+#[test]
+fn test_strict_deposit_mode_counts_cliff_unlock() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+    ctx.client().set_strict_deposit_mode(&true);
+    // 200 at the cliff plus 1/s over 500..1000.
+    let try_create = |deposit: i128| {
+        ctx.client().try_create_stream_with_options(
+            &ctx.sender,
+            &ctx.recipient,
+            &deposit,
+            &1_i128,
+            &0u64,
+            &500u64,
+            &1000u64,
+            &crate::StreamOptions {
+                cliff_unlock_amount: 200,
+                ..Default::default()
+            },
+        )
+    };
+    assert_eq!(try_create(701), Err(Ok(ContractError::ExcessDeposit)));
+    assert!(try_create(700).is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// Tests — get_version
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_version_matches_version_constants() {
+    let ctx = TestContext::setup();
+    assert_eq!(
+        ctx.client().get_version(),
+        crate::ContractVersion {
+            major: crate::version::MAJOR,
+            minor: crate::version::MINOR,
+            patch: crate::version::PATCH,
+        }
+    );
+    assert_eq!(
+        ctx.client().get_version(),
+        crate::ContractVersion {
+            major: 1,
+            minor: 0,
+            patch: 0,
+        }
+    );
+}
+
+#[test]
+fn test_get_version_major_matches_contract_version() {
+    let ctx = TestContext::setup();
+    assert_eq!(ctx.client().get_version().major, ctx.client().version());
+    assert_eq!(ctx.client().version(), crate::CONTRACT_VERSION);
+}
@@ -0,0 +1,13 @@
+//! Semantic version of the contract, fixed at compile time.
+//!
+//! Bump `MAJOR` for changes that break existing callers (which also bumps
+//! `CONTRACT_VERSION`), `MINOR` for backwards-compatible additions and `PATCH`
+//! for fixes. `get_version` returns all three so callers, and future `migrate_*`
+//! entrypoints, can tell which build is deployed.
+
+/// Incremented for breaking changes to the contract interface or storage.
+pub const MAJOR: u32 = 1;
+/// Incremented for backwards-compatible additions.
+pub const MINOR: u32 = 0;
+/// Incremented for backwards-compatible fixes.
+pub const PATCH: u32 = 0;
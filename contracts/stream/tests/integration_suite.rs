@@ -1,6 +1,8 @@
 extern crate std;
 
-use fluxora_stream::{FluxoraStream, FluxoraStreamClient, StreamStatus};
+use fluxora_stream::{
+    ContractError, FluxoraStream, FluxoraStreamClient, ProtocolStats, StreamStatus,
+};
 use soroban_sdk::{
     log,
     testutils::{Address as _, Ledger},
@@ -94,7 +96,7 @@ fn init_sets_config_and_keeps_token_address() {
 }
 
 #[test]
-#[should_panic(expected = "already initialised")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn init_twice_panics() {
     let ctx = TestContext::setup();
     ctx.client().init(&ctx.token_id, &ctx.admin);
@@ -221,6 +223,57 @@ fn withdraw_before_cliff_returns_zero() {
     assert_eq!(state.withdrawn_amount, 0);
 }
 
+/// At exactly `cliff_time` the cliff has been reached: `withdraw` must not fail
+/// and pays `rate × (cliff_time - start_time)` (0 when the cliff is the start).
+#[test]
+fn withdraw_at_cliff_time_returns_zero_or_accrued() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with_cliff(500);
+
+    ctx.env.ledger().set_timestamp(499);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&stream_id), 500);
+    assert_eq!(ctx.token.balance(&ctx.recipient), 500);
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    assert_eq!(state.withdrawn_amount, 500);
+    assert_eq!(state.status, StreamStatus::Active);
+}
+
+#[test]
+fn withdraw_at_cliff_equal_to_start_returns_zero() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with_cliff(0);
+
+    // cliff_time == start_time == now: the cliff is reached but nothing has accrued yet.
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).withdrawn_amount,
+        0
+    );
+
+    ctx.env.ledger().set_timestamp(1);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1);
+}
+
+#[test]
+fn withdraw_at_cliff_equal_to_end_releases_full_deposit() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with_cliff(1000);
+
+    ctx.env.ledger().set_timestamp(999);
+    assert_eq!(ctx.client().withdraw(&stream_id), 0);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&stream_id), 1000);
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).status,
+        StreamStatus::Completed
+    );
+}
+
 #[test]
 fn get_stream_state_returns_latest_status() {
     let ctx = TestContext::setup();
@@ -261,6 +314,130 @@ fn get_stream_state_unknown_id_panics() {
     assert!(result.is_err());
 }
 
+/// Cross-contract callers should not have to handle a failed call to probe an ID:
+/// `get_stream_state_option` returns `None` for a miss instead of erroring.
+#[test]
+fn get_stream_state_option_unknown_id_returns_none() {
+    let ctx = TestContext::setup();
+    assert!(ctx.client().get_stream_state_option(&99).is_none());
+    assert!(matches!(
+        ctx.client().try_get_stream_state_option(&99),
+        Ok(Ok(None))
+    ));
+
+    // The panicking getter keeps reporting the typed error.
+    assert!(matches!(
+        ctx.client().try_get_stream_state(&99),
+        Err(Ok(ContractError::StreamNotFound))
+    ));
+}
+
+#[test]
+fn get_stream_state_option_matches_get_stream_state_after_create() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    let state = ctx.client().get_stream_state(&stream_id);
+    let found = ctx
+        .client()
+        .get_stream_state_option(&stream_id)
+        .expect("existing stream is returned");
+    assert_eq!(found.stream_id, state.stream_id);
+    assert_eq!(found.deposit_amount, state.deposit_amount);
+    assert_eq!(found.status, state.status);
+    assert!(ctx
+        .client()
+        .get_stream_state_option(&(stream_id + 1))
+        .is_none());
+}
+
+/// Attempt a `create_stream` from `ctx.sender` and assert it fails with
+/// `InvalidParams` without moving any tokens.
+fn assert_create_stream_invalid(
+    ctx: &TestContext,
+    recipient: &Address,
+    deposit_amount: i128,
+    rate_per_second: i128,
+    start_time: u64,
+    cliff_time: u64,
+    end_time: u64,
+) {
+    ctx.env.ledger().set_timestamp(0);
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        recipient,
+        &deposit_amount,
+        &rate_per_second,
+        &start_time,
+        &cliff_time,
+        &end_time,
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidParams)));
+    assert_eq!(ctx.token.balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token.balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn create_stream_sender_equals_recipient_panics() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        ctx.client().create_stream(
+            &ctx.sender,
+            &ctx.sender,
+            &1000_i128,
+            &1_i128,
+            &0u64,
+            &0u64,
+            &1000u64,
+        );
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(ctx.token.balance(&ctx.sender), 10_000);
+    assert_eq!(ctx.token.balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn create_stream_rejects_non_positive_deposit() {
+    let ctx = TestContext::setup();
+    let recipient = ctx.recipient.clone();
+    assert_create_stream_invalid(&ctx, &recipient, 0, 1, 0, 0, 1000);
+    assert_create_stream_invalid(&ctx, &recipient, -1000, 1, 0, 0, 1000);
+}
+
+#[test]
+fn create_stream_rejects_non_positive_rate() {
+    let ctx = TestContext::setup();
+    let recipient = ctx.recipient.clone();
+    assert_create_stream_invalid(&ctx, &recipient, 1000, 0, 0, 0, 1000);
+    assert_create_stream_invalid(&ctx, &recipient, 1000, -1, 0, 0, 1000);
+}
+
+#[test]
+fn create_stream_rejects_start_not_before_end() {
+    let ctx = TestContext::setup();
+    let recipient = ctx.recipient.clone();
+    assert_create_stream_invalid(&ctx, &recipient, 1000, 1, 1000, 1000, 1000);
+    assert_create_stream_invalid(&ctx, &recipient, 1000, 1, 1000, 1000, 500);
+}
+
+#[test]
+fn create_stream_rejects_cliff_after_end() {
+    let ctx = TestContext::setup();
+    let recipient = ctx.recipient.clone();
+    assert_create_stream_invalid(&ctx, &recipient, 1000, 1, 0, 1001, 1000);
+}
+
 #[test]
 fn create_stream_rejects_underfunded_deposit() {
     let ctx = TestContext::setup();
@@ -466,7 +643,9 @@ fn integration_cancel_immediately_full_refund() {
 
     // Cancel immediately (no time elapsed)
     ctx.env.ledger().set_timestamp(1000);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 3000);
+    assert_eq!(recipient_accrued, 0);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -523,7 +702,9 @@ fn integration_cancel_partial_accrual_partial_refund() {
 
     // Cancel stream
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 3500);
+    assert_eq!(recipient_accrued, 1500);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -584,7 +765,9 @@ fn integration_cancel_fully_accrued_no_refund() {
 
     // Cancel stream
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 0);
+    assert_eq!(recipient_accrued, 2000);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -647,7 +830,9 @@ fn integration_cancel_after_partial_withdrawal() {
     assert_eq!(accrued, 2400);
 
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 1600);
+    assert_eq!(recipient_accrued, 2400);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -729,7 +914,9 @@ fn integration_cancel_after_multiple_partial_withdrawals() {
     assert_eq!(accrued, 4200);
 
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 800);
+    assert_eq!(recipient_accrued, 4200);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -796,7 +983,9 @@ fn integration_cancel_before_cliff_full_refund() {
     assert_eq!(accrued, 0);
 
     // Cancel stream
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 3000);
+    assert_eq!(recipient_accrued, 0);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -845,7 +1034,9 @@ fn integration_cancel_after_cliff_partial_refund() {
 
     // Cancel stream
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 1500);
+    assert_eq!(recipient_accrued, 2500);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -1021,7 +1212,9 @@ fn integration_cancel_paused_stream() {
 
     // Cancel paused stream
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 1000);
+    assert_eq!(recipient_accrued, 2000);
 
     // Verify stream status is Cancelled
     let state = ctx.client().get_stream_state(&stream_id);
@@ -1129,7 +1322,7 @@ fn integration_pause_resume_withdraw_lifecycle() {
         .or_else(|| err.downcast_ref::<String>().map(|s| s.as_str()))
         .unwrap_or("<non-string panic payload>");
     assert!(
-        panic_msg.contains("cannot withdraw from paused stream"),
+        panic_msg.contains("Error(Contract, #8)"),
         "unexpected panic message when withdrawing from paused stream: {}",
         panic_msg
     );
@@ -1225,6 +1418,8 @@ fn integration_multiple_pause_resume_cycles() {
     ctx.client().pause_stream(&stream_id);
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.paused_at, Some(500));
+    assert_eq!(state.total_paused_duration, 0);
 
     ctx.env.ledger().set_timestamp(1000);
     let accrued_at_1000 = ctx.client().calculate_accrued(&stream_id);
@@ -1233,12 +1428,15 @@ fn integration_multiple_pause_resume_cycles() {
     ctx.client().resume_stream(&stream_id);
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.total_paused_duration, 500);
 
     // Second pause/resume cycle
     ctx.env.ledger().set_timestamp(1500);
     ctx.client().pause_stream(&stream_id);
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.status, StreamStatus::Paused);
+    assert_eq!(state.paused_at, Some(1500));
 
     ctx.env.ledger().set_timestamp(1800);
     let accrued_at_1800 = ctx.client().calculate_accrued(&stream_id);
@@ -1250,6 +1448,11 @@ fn integration_multiple_pause_resume_cycles() {
     ctx.client().resume_stream(&stream_id);
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(
+        state.total_paused_duration, 800,
+        "paused time accumulates across cycles without affecting accrual"
+    );
 
     // Withdraw at t=1800
     let withdrawn_1 = ctx.client().withdraw(&stream_id);
@@ -1366,7 +1569,9 @@ fn integration_pause_then_cancel_preserves_accrual() {
 
     // Cancel paused stream
     let sender_before_cancel = ctx.token.balance(&ctx.sender);
-    ctx.client().cancel_stream(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+    assert_eq!(sender_refund, 1200);
+    assert_eq!(recipient_accrued, 1800);
 
     let state = ctx.client().get_stream_state(&stream_id);
     assert_eq!(state.status, StreamStatus::Cancelled);
@@ -1436,12 +1641,487 @@ fn test_create_many_streams_from_same_sender() {
         }
     }
 
+    // Measured for these 50 creates: 63.5M CPU instructions, 13.7M memory bytes.
+    // The ceilings leave about 6% headroom; re-measure and update both figures
+    // in the change that raises them.
     let cpu_insns = ctx.env.budget().cpu_instruction_cost();
     log!(&ctx.env, "cpu_insns", cpu_insns);
-    assert!(cpu_insns <= 22_000_000); // Increased to account for StreamCreated and Withdrawal event payloads
+    assert!(cpu_insns <= 67_000_000);
 
     // Check memory bytes consumed
     let mem_bytes = ctx.env.budget().memory_bytes_cost();
     log!(&ctx.env, "mem_bytes", mem_bytes);
-    assert!(mem_bytes <= 4_400_000); // Increased to account for richer event payloads
+    assert!(mem_bytes <= 14_500_000);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — sender stream index
+// ---------------------------------------------------------------------------
+
+#[test]
+fn sender_index_empty_for_unknown_sender() {
+    let ctx = TestContext::setup();
+    let stranger = Address::generate(&ctx.env);
+
+    assert_eq!(ctx.client().get_streams_by_sender(&stranger).len(), 0);
+    assert_eq!(ctx.client().get_sender_stream_count(&stranger), 0);
+}
+
+#[test]
+fn sender_index_tracks_streams_in_creation_order() {
+    let ctx = TestContext::setup();
+
+    let id0 = ctx.create_default_stream();
+    let id1 = ctx.create_stream_with_cliff(500);
+    let id2 = ctx.create_default_stream();
+
+    let ids = ctx.client().get_streams_by_sender(&ctx.sender);
+    assert_eq!(ids, soroban_sdk::vec![&ctx.env, id0, id1, id2]);
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 3);
+
+    // Streams from another sender are indexed separately.
+    let other_sender = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&other_sender, &1000_i128);
+    let other_id = ctx.client().create_stream(
+        &other_sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert_eq!(
+        ctx.client().get_streams_by_sender(&other_sender),
+        soroban_sdk::vec![&ctx.env, other_id]
+    );
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 3);
+}
+
+#[test]
+fn sender_index_keeps_terminal_streams() {
+    let ctx = TestContext::setup();
+
+    let cancelled = ctx.create_default_stream();
+    let completed = ctx.create_default_stream();
+    ctx.client().cancel_stream(&cancelled);
+    ctx.env.ledger().set_timestamp(1000);
+    ctx.client().withdraw(&completed);
+
+    assert_eq!(
+        ctx.client().get_streams_by_sender(&ctx.sender),
+        soroban_sdk::vec![&ctx.env, cancelled, completed]
+    );
+}
+
+#[test]
+fn sender_index_unchanged_by_failed_creation() {
+    let ctx = TestContext::setup();
+    let id0 = ctx.create_default_stream();
+
+    // Underfunded deposit: creation must fail and leave the index untouched.
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &100_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert!(result.is_err());
+
+    // Insufficient balance: token transfer fails after validation.
+    let result = ctx.client().try_create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1_000_000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+    assert!(result.is_err());
+
+    assert_eq!(
+        ctx.client().get_streams_by_sender(&ctx.sender),
+        soroban_sdk::vec![&ctx.env, id0]
+    );
+    assert_eq!(ctx.client().get_sender_stream_count(&ctx.sender), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Tests — recipient stream index
+// ---------------------------------------------------------------------------
+
+#[test]
+fn recipient_index_empty_for_unknown_recipient() {
+    let ctx = TestContext::setup();
+    let stranger = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().get_streams_by_recipient(&stranger).len(), 0);
+}
+
+#[test]
+fn recipient_index_includes_streams_from_multiple_senders() {
+    let ctx = TestContext::setup();
+    let id0 = ctx.create_default_stream();
+
+    let other_sender = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&other_sender, &1000_i128);
+    let id1 = ctx.client().create_stream(
+        &other_sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    // A stream to someone else must not appear in this recipient's index.
+    let other_recipient = Address::generate(&ctx.env);
+    let id2 = ctx.client().create_stream(
+        &ctx.sender,
+        &other_recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+    );
+
+    assert_eq!(
+        ctx.client().get_streams_by_recipient(&ctx.recipient),
+        soroban_sdk::vec![&ctx.env, id0, id1]
+    );
+    assert_eq!(
+        ctx.client().get_streams_by_recipient(&other_recipient),
+        soroban_sdk::vec![&ctx.env, id2]
+    );
+}
+
+/// Three streams from the same sender to the same recipient stay fully
+/// independent, and the recipient index lists all of them throughout their
+/// lifecycle (including after completion).
+#[test]
+fn integration_same_sender_same_recipient_multiple_streams() {
+    let ctx = TestContext::setup();
+    ctx.env.ledger().set_timestamp(0);
+
+    let id0 = ctx.create_default_stream();
+    let id1 = ctx.create_default_stream();
+    let id2 = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &500_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &500u64,
+    );
+    assert_eq!((id0, id1, id2), (0, 1, 2));
+    assert_eq!(ctx.token.balance(&ctx.sender), 7500);
+    assert_eq!(ctx.token.balance(&ctx.contract_id), 2500);
+
+    let expected = soroban_sdk::vec![&ctx.env, id0, id1, id2];
+    assert_eq!(
+        ctx.client().get_streams_by_recipient(&ctx.recipient),
+        expected
+    );
+
+    ctx.env.ledger().set_timestamp(200);
+    assert_eq!(ctx.client().withdraw(&id1), 200);
+    assert_eq!(ctx.client().get_stream_state(&id0).withdrawn_amount, 0);
+    assert_eq!(ctx.client().get_stream_state(&id2).withdrawn_amount, 0);
+
+    ctx.env.ledger().set_timestamp(500);
+    assert_eq!(ctx.client().withdraw(&id2), 500);
+    assert_eq!(
+        ctx.client().get_stream_state(&id2).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&id0).status,
+        StreamStatus::Active
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&id1).status,
+        StreamStatus::Active
+    );
+
+    ctx.env.ledger().set_timestamp(600);
+    assert_eq!(ctx.client().withdraw(&id0), 600);
+
+    ctx.env.ledger().set_timestamp(1000);
+    assert_eq!(ctx.client().withdraw(&id1), 800);
+    assert_eq!(ctx.client().withdraw(&id0), 400);
+
+    assert_eq!(ctx.token.balance(&ctx.sender), 7500);
+    assert_eq!(ctx.token.balance(&ctx.recipient), 2500);
+    assert_eq!(ctx.token.balance(&ctx.contract_id), 0);
+
+    // Completed streams remain indexed; callers filter by status themselves.
+    assert_eq!(
+        ctx.client().get_streams_by_recipient(&ctx.recipient),
+        expected
+    );
+}
+
+/// `simulate_cancel` must predict exactly what the cancel integration scenarios
+/// observe: before the cliff, after the cliff, mid-stream and past the end.
+#[test]
+fn integration_simulate_cancel_matches_actual_cancel() {
+    for (cliff, cancel_at, expected_refund) in [
+        (2000u64, 0u64, 4000_i128),
+        (2000, 1000, 4000),
+        (2000, 2500, 1500),
+        (0, 3000, 1000),
+        (0, 5000, 0),
+    ] {
+        let ctx = TestContext::setup();
+        ctx.env.ledger().set_timestamp(0);
+        let stream_id = ctx.client().create_stream(
+            &ctx.sender,
+            &ctx.recipient,
+            &4000_i128,
+            &1_i128,
+            &0u64,
+            &cliff,
+            &4000u64,
+        );
+
+        // Simulating ahead of time gives the same projection as simulating "now".
+        let projected = ctx.client().simulate_cancel(&stream_id, &cancel_at);
+        ctx.env.ledger().set_timestamp(cancel_at);
+        assert_eq!(
+            ctx.client().simulate_cancel(&stream_id, &cancel_at),
+            projected
+        );
+
+        let sender_before = ctx.token.balance(&ctx.sender);
+        let returned = ctx.client().cancel_stream(&stream_id);
+        let refund = ctx.token.balance(&ctx.sender) - sender_before;
+
+        assert_eq!(projected, (expected_refund, 4000 - expected_refund));
+        assert_eq!(returned, projected);
+        assert_eq!(refund, projected.0);
+        assert_eq!(ctx.client().calculate_accrued(&stream_id), projected.1);
+    }
+}
+
+/// Read `get_protocol_stats` and check it against the stream counter: every
+/// stream sits in exactly one status bucket.
+fn assert_stats_consistent(ctx: &TestContext) -> ProtocolStats {
+    let stats = ctx.client().get_protocol_stats();
+    assert_eq!(stats.total_streams, ctx.client().get_next_stream_id());
+    assert_eq!(
+        stats.active_count + stats.paused_count + stats.completed_count + stats.cancelled_count,
+        stats.total_streams
+    );
+    stats
+}
+
+/// Drive streams through every status transition and check the protocol stats
+/// stay internally consistent at each step.
+#[test]
+fn integration_protocol_stats_consistent_through_lifecycle() {
+    let ctx = TestContext::setup();
+    let client = ctx.client();
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!(stats.total_streams, 0);
+    assert_eq!(stats.total_deposited, 0);
+
+    let s0 = ctx.create_default_stream();
+    let s1 = ctx.create_default_stream();
+    let scheduled = client.create_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &500u64,
+        &500u64,
+        &1500u64,
+    );
+    let pending = client.create_pending_stream(
+        &ctx.sender,
+        &ctx.recipient,
+        &1000_i128,
+        &1_i128,
+        &0u64,
+        &0u64,
+        &1000u64,
+        &100u64,
+    );
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!(stats.total_streams, 4);
+    assert_eq!(
+        stats.active_count, 4,
+        "pending and scheduled count as active"
+    );
+    assert_eq!(stats.total_deposited, 4000);
+
+    client.pause_stream(&s0);
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!((stats.active_count, stats.paused_count), (3, 1));
+
+    client.topup_stream(&s1, &500_i128);
+    assert_eq!(assert_stats_consistent(&ctx).total_deposited, 4500);
+
+    client.reject_stream(&pending);
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!((stats.active_count, stats.cancelled_count), (2, 1));
+
+    client.resume_stream(&s0);
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!((stats.active_count, stats.paused_count), (3, 0));
+
+    ctx.env.ledger().set_timestamp(300);
+    client.cancel_stream(&s0);
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!((stats.active_count, stats.cancelled_count), (2, 2));
+
+    // A refunded cancelled stream stays cancelled after the recipient withdraws.
+    client.withdraw(&s0);
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!((stats.cancelled_count, stats.completed_count), (2, 0));
+    assert_eq!(stats.total_withdrawn, 300);
+
+    ctx.env.ledger().set_timestamp(2000);
+    client.withdraw(&s1);
+    client.withdraw(&scheduled);
+    let stats = assert_stats_consistent(&ctx);
+    assert_eq!(
+        stats,
+        ProtocolStats {
+            total_streams: 4,
+            active_count: 0,
+            paused_count: 0,
+            completed_count: 2,
+            cancelled_count: 2,
+            total_deposited: 4500,
+            total_withdrawn: 2800,
+        }
+    );
+    assert_eq!(stats.total_withdrawn, client.get_total_streamed());
+}
+
+/// `cancel_stream` returns exactly what `get_refundable_amount` and
+/// `get_withdrawable_amount` reported just before the cancel.
+#[test]
+fn integration_cancel_returns_pre_cancel_view_amounts() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_stream_with_cliff(200);
+
+    ctx.env.ledger().set_timestamp(650);
+    let refundable = ctx.client().get_refundable_amount(&stream_id);
+    let withdrawable = ctx.client().get_withdrawable_amount(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!((sender_refund, recipient_accrued), (350, 650));
+    assert_eq!(sender_refund, refundable);
+    assert_eq!(recipient_accrued, withdrawable);
+}
+
+/// After a partial withdrawal the returned `recipient_accrued` still covers the
+/// already-withdrawn part: it equals withdrawable plus withdrawn.
+#[test]
+fn integration_cancel_after_withdrawal_returns_total_accrued() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+    ctx.client().withdraw(&stream_id);
+
+    ctx.env.ledger().set_timestamp(700);
+    let refundable = ctx.client().get_refundable_amount(&stream_id);
+    let withdrawable = ctx.client().get_withdrawable_amount(&stream_id);
+    let (sender_refund, recipient_accrued) = ctx.client().cancel_stream(&stream_id);
+
+    assert_eq!(sender_refund, refundable);
+    assert_eq!(recipient_accrued, withdrawable + 300);
+    assert_eq!((sender_refund, recipient_accrued), (300, 700));
+}
+
+// ---------------------------------------------------------------------------
+// Tests — per-operation budget regression
+// ---------------------------------------------------------------------------
+
+/// CPU and memory consumed since the budget was last reset.
+#[derive(Debug, Clone, Copy)]
+struct BudgetSummary {
+    cpu: u64,
+    mem: u64,
+}
+
+impl<'a> TestContext<'a> {
+    /// Budget consumed by the calls made since `env.budget().reset_default()`.
+    fn get_last_operation_budget(&self) -> BudgetSummary {
+        BudgetSummary {
+            cpu: self.env.budget().cpu_instruction_cost(),
+            mem: self.env.budget().memory_bytes_cost(),
+        }
+    }
+}
+
+// Measured ceilings (cpu, mem) per single call on a fresh ledger, with ~25%
+// headroom. Each operation touches one stream, so a regression here means new
+// work (e.g. an accidental scan of an index) on a hot path. The test host's
+// per-call cost also grows with the total number of ledger entries, so these
+// are only meaningful on a ledger holding just the stream under test.
+const WITHDRAW_BUDGET: (u64, u64) = (700_000, 115_000);
+const PAUSE_BUDGET: (u64, u64) = (480_000, 82_000);
+const RESUME_BUDGET: (u64, u64) = (500_000, 83_000);
+const CANCEL_BUDGET: (u64, u64) = (770_000, 120_000);
+
+fn assert_within_budget(name: &str, budget: BudgetSummary, (cpu, mem): (u64, u64)) {
+    assert!(budget.cpu <= cpu, "{name}: cpu {} > {cpu}", budget.cpu);
+    assert!(budget.mem <= mem, "{name}: mem {} > {mem}", budget.mem);
+}
+
+#[test]
+fn test_withdraw_budget() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+
+    ctx.env.budget().reset_default();
+    assert_eq!(ctx.client().withdraw(&stream_id), 300);
+    assert_within_budget("withdraw", ctx.get_last_operation_budget(), WITHDRAW_BUDGET);
+}
+
+#[test]
+fn test_pause_and_resume_budget() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+
+    ctx.env.budget().reset_default();
+    ctx.client().pause_stream(&stream_id);
+    assert_within_budget(
+        "pause_stream",
+        ctx.get_last_operation_budget(),
+        PAUSE_BUDGET,
+    );
+
+    ctx.env.budget().reset_default();
+    ctx.client().resume_stream(&stream_id);
+    assert_within_budget(
+        "resume_stream",
+        ctx.get_last_operation_budget(),
+        RESUME_BUDGET,
+    );
+}
+
+#[test]
+fn test_cancel_budget() {
+    let ctx = TestContext::setup();
+    let stream_id = ctx.create_default_stream();
+    ctx.env.ledger().set_timestamp(300);
+
+    ctx.env.budget().reset_default();
+    assert_eq!(ctx.client().cancel_stream(&stream_id), (700, 300));
+    assert_within_budget(
+        "cancel_stream",
+        ctx.get_last_operation_budget(),
+        CANCEL_BUDGET,
+    );
 }